@@ -0,0 +1,101 @@
+use std::convert::Infallible;
+
+use axum::http::header::{COOKIE, SET_COOKIE};
+use axum::http::request::Parts;
+use axum::http::HeaderValue;
+use axum::response::{IntoResponse, IntoResponseParts, Response, ResponseParts};
+use cookie::{Cookie, SameSite};
+
+use sesame::context::{Context, ContextData};
+use sesame::error::SesameResult;
+use sesame::extensions::{ExtensionContext, SesameExtension};
+use sesame::pcon::PCon;
+use sesame::policy::{Policy, Reason};
+
+use crate::policy::FrontendPolicy;
+
+// Cookies sent with the request, each protected by the policy P constructs from it.
+#[derive(Clone, Copy)]
+pub struct PConCookieJar<'a> {
+    parts: &'a Parts,
+}
+impl<'a> PConCookieJar<'a> {
+    pub(crate) fn new(parts: &'a Parts) -> Self {
+        PConCookieJar { parts }
+    }
+
+    pub fn get<P: FrontendPolicy>(&self, name: &str) -> Option<PCon<String, P>> {
+        let cookie = self
+            .parts
+            .headers
+            .get_all(COOKIE)
+            .iter()
+            .filter_map(|header| header.to_str().ok())
+            .flat_map(Cookie::split_parse_encoded)
+            .filter_map(Result::ok)
+            .find(|cookie| cookie.name() == name)?
+            .into_owned();
+        let policy = P::from_cookie(name, &cookie, self.parts);
+        Some(PCon::new(String::from(cookie.value()), policy))
+    }
+}
+
+// Check the policy then give the value back to set the cookie with.
+struct CookiePolicyCheck {}
+impl<P: Policy> SesameExtension<String, P, String> for CookiePolicyCheck {
+    fn apply(&mut self, data: String, _policy: P) -> String {
+        data
+    }
+}
+
+// Cookies to set with the response, return it alongside the response, e.g. (cookies, body).
+// Values are checked with Reason::Cookie when they are added.
+#[derive(Default)]
+pub struct PConSetCookies {
+    cookies: Vec<Cookie<'static>>,
+}
+impl PConSetCookies {
+    pub fn new() -> Self {
+        Self::default()
+    }
+    pub fn add<P: Policy, D: ContextData>(
+        &mut self,
+        name: &str,
+        value: PCon<String, P>,
+        context: Context<D>,
+    ) -> SesameResult<()> {
+        let context = ExtensionContext::new(context);
+        let mut ext = CookiePolicyCheck {};
+        let value = value.checked_extension(&mut ext, &context, Reason::Cookie(name))?;
+        let cookie = Cookie::build((String::from(name), value))
+            .path("/")
+            .http_only(true)
+            .secure(true)
+            .same_site(SameSite::Strict)
+            .build();
+        self.cookies.push(cookie);
+        Ok(())
+    }
+    pub fn remove(&mut self, name: &str) {
+        let mut cookie = Cookie::build((String::from(name), "")).path("/").build();
+        cookie.make_removal();
+        self.cookies.push(cookie);
+    }
+}
+
+impl IntoResponseParts for PConSetCookies {
+    type Error = Infallible;
+    fn into_response_parts(self, mut response: ResponseParts) -> Result<ResponseParts, Infallible> {
+        for cookie in self.cookies {
+            if let Ok(value) = HeaderValue::from_str(&cookie.encoded().to_string()) {
+                response.headers_mut().append(SET_COOKIE, value);
+            }
+        }
+        Ok(response)
+    }
+}
+impl IntoResponse for PConSetCookies {
+    fn into_response(self) -> Response {
+        (self, ()).into_response()
+    }
+}
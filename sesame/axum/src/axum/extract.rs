@@ -0,0 +1,67 @@
+use std::ops::{Deref, DerefMut};
+
+use axum::extract::rejection::PathRejection;
+use axum::extract::{FromRequestParts, Path};
+use axum::http::request::Parts;
+use serde::de::DeserializeOwned;
+
+use sesame::pcon::PCon;
+
+use crate::axum::{FromPConRequest, PConRequest};
+use crate::policy::FrontendPolicy;
+
+// Extracts any FromPConRequest (e.g. Context<D>) in handlers, e.g.
+// `async fn handler(Sesame(context): Sesame<Context<MyData>>) -> ...`.
+pub struct Sesame<T>(pub T);
+impl<T> Sesame<T> {
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+}
+impl<T> Deref for Sesame<T> {
+    type Target = T;
+    #[inline(always)]
+    fn deref(&self) -> &T {
+        &self.0
+    }
+}
+impl<T> DerefMut for Sesame<T> {
+    #[inline(always)]
+    fn deref_mut(&mut self) -> &mut T {
+        &mut self.0
+    }
+}
+
+#[async_trait::async_trait]
+impl<S: Send + Sync, T: FromPConRequest> FromRequestParts<S> for Sesame<T> {
+    type Rejection = T::Rejection;
+
+    async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
+        let result = T::from_pcon_request(PConRequest::new(parts)).await;
+        result.map(Sesame)
+    }
+}
+
+// Parameters in the url (e.g. /endpoint/:id), protected by the policy P constructs from the
+// request.
+pub struct PConPath<T, P: FrontendPolicy>(pub PCon<T, P>);
+impl<T, P: FrontendPolicy> PConPath<T, P> {
+    pub fn into_inner(self) -> PCon<T, P> {
+        self.0
+    }
+}
+
+#[async_trait::async_trait]
+impl<S, T, P> FromRequestParts<S> for PConPath<T, P>
+where
+    S: Send + Sync,
+    T: DeserializeOwned + Send,
+    P: FrontendPolicy,
+{
+    type Rejection = PathRejection;
+
+    async fn from_request_parts(parts: &mut Parts, state: &S) -> Result<Self, Self::Rejection> {
+        let Path(value) = Path::<T>::from_request_parts(parts, state).await?;
+        Ok(PConPath(PCon::new(value, P::from_request(parts))))
+    }
+}
@@ -0,0 +1,54 @@
+use std::ops::{Deref, DerefMut};
+
+use axum::extract::{FromRequest, Request};
+use axum::http::StatusCode;
+
+use crate::axum::request::read_body;
+use crate::axum::{InputPConValue, PConRequest, RequestPConJson};
+
+// Url encoded forms, e.g. name=Max&dog.age=10.
+// Fields are converted the same way as JSON (with RequestPConJson), values are parsed from
+// strings when they are turned into PCons.
+pub struct PConForm<T>(pub T);
+impl<T> PConForm<T> {
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+}
+impl<T> Deref for PConForm<T> {
+    type Target = T;
+    #[inline(always)]
+    fn deref(&self) -> &T {
+        &self.0
+    }
+}
+impl<T> DerefMut for PConForm<T> {
+    #[inline(always)]
+    fn deref_mut(&mut self) -> &mut T {
+        &mut self.0
+    }
+}
+
+// Allows us to use this as an extractor in handlers (must be the last one, as it consumes the
+// body).
+#[async_trait::async_trait]
+impl<S: Send + Sync, T: RequestPConJson> FromRequest<S> for PConForm<T> {
+    type Rejection = (StatusCode, &'static str);
+
+    async fn from_request(request: Request, _state: &S) -> Result<Self, Self::Rejection> {
+        let (parts, body) = request.into_parts();
+        let bytes = read_body(body).await?;
+        let fields = match serde_urlencoded::from_bytes(&bytes) {
+            Err(_) => return Err((StatusCode::BAD_REQUEST, "Request data is not a form")),
+            Ok(fields) => fields,
+        };
+        let value = match InputPConValue::from_form(fields) {
+            Err(e) => return Err((StatusCode::BAD_REQUEST, e)),
+            Ok(value) => value,
+        };
+        match T::from_json(value, PConRequest::new(&parts)) {
+            Ok(t) => Ok(PConForm(t)),
+            Err(e) => Err((StatusCode::BAD_REQUEST, e)),
+        }
+    }
+}
@@ -0,0 +1,30 @@
+use std::ops::{Deref, DerefMut};
+
+mod request;
+mod response;
+mod traits;
+
+pub use response::*;
+pub use traits::*;
+pub use sesame_web::json::*;
+
+// Can use this as an argument or return this from handlers.
+pub struct PConJson<T>(pub T);
+impl<T> PConJson<T> {
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+}
+impl<T> Deref for PConJson<T> {
+    type Target = T;
+    #[inline(always)]
+    fn deref(&self) -> &T {
+        &self.0
+    }
+}
+impl<T> DerefMut for PConJson<T> {
+    #[inline(always)]
+    fn deref_mut(&mut self) -> &mut T {
+        &mut self.0
+    }
+}
@@ -0,0 +1,24 @@
+use axum::extract::{FromRequest, Request};
+use axum::http::StatusCode;
+
+use crate::axum::request::read_body;
+use crate::axum::{InputPConValue, PConJson, PConRequest, RequestPConJson};
+
+// Allows us to use this as an extractor in handlers (must be the last one, as it consumes the
+// body).
+#[async_trait::async_trait]
+impl<S: Send + Sync, T: RequestPConJson> FromRequest<S> for PConJson<T> {
+    type Rejection = (StatusCode, &'static str);
+
+    async fn from_request(request: Request, _state: &S) -> Result<Self, Self::Rejection> {
+        let (parts, body) = request.into_parts();
+        let bytes = read_body(body).await?;
+        match serde_json::from_slice(&bytes) {
+            Err(_) => Err((StatusCode::BAD_REQUEST, "Request data is not JSON")),
+            Ok(value) => match T::from_json(InputPConValue::new(value), PConRequest::new(&parts)) {
+                Ok(t) => Ok(PConJson(t)),
+                Err(e) => Err((StatusCode::BAD_REQUEST, e)),
+            },
+        }
+    }
+}
@@ -0,0 +1,29 @@
+use axum::response::{IntoResponse, Response};
+use axum::Json;
+
+use sesame::context::{Context, ContextData};
+use sesame::extensions::ExtensionContext;
+use sesame::policy::Reason;
+
+use crate::axum::ResponsePConJson;
+use crate::error::sesame_error_response;
+
+// Handlers can return JsonResponse(T: ResponsePConJson, Context) which Sesame turns into JSON
+// after a policy check.
+pub struct JsonResponse<T: ResponsePConJson, D: ContextData>(pub T, pub Context<D>);
+impl<T: ResponsePConJson, D: ContextData> From<(T, Context<D>)> for JsonResponse<T, D> {
+    fn from((json, context): (T, Context<D>)) -> Self {
+        Self(json, context)
+    }
+}
+
+impl<T: ResponsePConJson, D: ContextData> IntoResponse for JsonResponse<T, D> {
+    fn into_response(self) -> Response {
+        let (json, context) = (self.0, self.1);
+        let context = ExtensionContext::new(context);
+        match json.to_json().transform_with(&context, &Reason::Response) {
+            Err(err) => sesame_error_response(err),
+            Ok(json) => Json(json).into_response(),
+        }
+    }
+}
@@ -0,0 +1,73 @@
+use serde::de::DeserializeOwned;
+use std::collections::HashMap;
+
+use sesame::pcon::PCon;
+
+use crate::axum::{InputPConValue, JsonRequest, PConRequest};
+use crate::policy::FrontendPolicy;
+
+// Trait for transformation from JSON (or form) data to structs.
+// Structs implement this by getting each of their fields, e.g.
+// `name: value.get("name")?.into_json(request)?`, or derive it with
+// #[sesame(crate = "::sesame_axum")].
+// ResponsePConJson (the other direction) is shared with the other frontends in sesame_web.
+pub trait RequestPConJson {
+    fn from_json(value: InputPConValue, request: PConRequest<'_>) -> Result<Self, &'static str>
+    where
+        Self: Sized;
+}
+
+// Lets InputPConValue::into_json(request) construct our RequestPConJson types.
+impl<'a, T: RequestPConJson> JsonRequest<T> for PConRequest<'a> {
+    fn from_json(self, value: InputPConValue) -> Result<T, &'static str> {
+        T::from_json(value, self)
+    }
+}
+
+// Implement this for predefined types.
+impl<T: DeserializeOwned, P: FrontendPolicy> RequestPConJson for PCon<T, P> {
+    fn from_json(value: InputPConValue, request: PConRequest<'_>) -> Result<Self, &'static str> {
+        let value = value.deserialize()?;
+        Ok(PCon::new(value, P::from_request(request.get_parts())))
+    }
+}
+
+// Option (for nulls).
+impl<T: RequestPConJson> RequestPConJson for Option<T> {
+    fn from_json(value: InputPConValue, request: PConRequest<'_>) -> Result<Self, &'static str>
+    where
+        Self: Sized,
+    {
+        if value.is_null() {
+            Ok(None)
+        } else {
+            Ok(Some(T::from_json(value, request)?))
+        }
+    }
+}
+
+// Request containers.
+impl<T: RequestPConJson> RequestPConJson for Vec<T> {
+    fn from_json(value: InputPConValue, request: PConRequest<'_>) -> Result<Self, &'static str>
+    where
+        Self: Sized,
+    {
+        value
+            .into_array()?
+            .into_iter()
+            .map(|v| T::from_json(v, request))
+            .collect()
+    }
+}
+impl<T: RequestPConJson> RequestPConJson for HashMap<String, T> {
+    fn from_json(value: InputPConValue, request: PConRequest<'_>) -> Result<Self, &'static str>
+    where
+        Self: Sized,
+    {
+        value
+            .into_object()?
+            .into_iter()
+            .map(|(k, v)| Ok((k, T::from_json(v, request)?)))
+            .collect()
+    }
+}
@@ -0,0 +1,15 @@
+mod cookie;
+mod extract;
+mod form;
+mod json;
+mod redirect;
+mod request;
+mod template;
+
+pub use crate::axum::cookie::*;
+pub use crate::axum::extract::*;
+pub use crate::axum::form::*;
+pub use crate::axum::json::*;
+pub use crate::axum::redirect::*;
+pub use crate::axum::request::*;
+pub use crate::axum::template::*;
@@ -0,0 +1,60 @@
+use axum::response::{IntoResponse, Redirect, Response};
+
+use sesame::context::{Context, ContextData};
+use sesame::error::SesameResult;
+
+pub use sesame_web::redirect::{IntoRedirectParams, RedirectParam, RedirectParams};
+
+// A redirect response (303 See Other).
+pub struct PConRedirect {
+    redirect: Redirect,
+}
+impl PConRedirect {
+    // Each {} in url is replaced by the corresponding parameter, after checking its policy.
+    pub fn to<P: IntoRedirectParams, D: ContextData>(
+        url: &str,
+        params: P,
+        context: Context<D>,
+    ) -> SesameResult<Self> {
+        let params = params.into(url, context)?;
+        Ok(PConRedirect {
+            redirect: Redirect::to(&params.into_url()),
+        })
+    }
+}
+impl IntoResponse for PConRedirect {
+    fn into_response(self) -> Response {
+        self.redirect.into_response()
+    }
+}
+
+// Unit tests.
+#[cfg(test)]
+mod tests {
+    use crate::axum::PConRedirect;
+    use axum::http::header::LOCATION;
+    use axum::http::StatusCode;
+    use axum::response::IntoResponse;
+    use sesame::context::Context;
+    use sesame::pcon::PCon;
+    use sesame::policy::NoPolicy;
+
+    #[test]
+    fn test_mixed_redirect() {
+        let context = Context::test(());
+
+        let b1 = PCon::new(String::from("hello"), NoPolicy {});
+        let b2 = PCon::new(10u32, NoPolicy {});
+        let b3 = -20i32;
+        let b4 = "my_str";
+
+        let redirect =
+            PConRedirect::to("/test/{}/more/{}/{}/less/{}", (&b1, &b2, &b3, &b4), context);
+        let response = redirect.unwrap().into_response();
+        assert_eq!(response.status(), StatusCode::SEE_OTHER);
+        assert_eq!(
+            response.headers().get(LOCATION).unwrap(),
+            "/test/hello/more/10/-20/less/my_str"
+        );
+    }
+}
@@ -0,0 +1,86 @@
+use axum::body::{Body, Bytes};
+use axum::extract::MatchedPath;
+use axum::http::request::Parts;
+use axum::http::{Method, StatusCode};
+use axum::response::IntoResponse;
+
+use sesame::pcon::PCon;
+
+use crate::axum::cookie::PConCookieJar;
+use crate::axum::json::{InputPConValue, RequestPConJson};
+use crate::policy::FrontendPolicy;
+
+// Request, this only wraps the parts of the request (not the body), which is all that extractors
+// other than PConJson and PConForm can see.
+#[derive(Clone, Copy)]
+pub struct PConRequest<'a> {
+    parts: &'a Parts,
+}
+
+impl<'a> PConRequest<'a> {
+    pub fn new(parts: &'a Parts) -> Self {
+        PConRequest { parts }
+    }
+
+    pub(crate) fn get_parts(&self) -> &'a Parts {
+        self.parts
+    }
+
+    pub fn method(&self) -> &'a Method {
+        &self.parts.method
+    }
+
+    // The route the router matched (e.g. "/users/:id"), or the path if there is none.
+    pub fn route(&self) -> &'a str {
+        match self.parts.extensions.get::<MatchedPath>() {
+            Some(path) => path.as_str(),
+            None => self.parts.uri.path(),
+        }
+    }
+
+    pub fn cookies(&self) -> PConCookieJar<'a> {
+        PConCookieJar::new(self.parts)
+    }
+
+    pub fn header<P: FrontendPolicy>(&self, name: &str) -> Option<PCon<String, P>> {
+        let value = self.parts.headers.get(name)?.to_str().ok()?;
+        Some(PCon::new(String::from(value), P::from_request(self.parts)))
+    }
+
+    // Use this to retrieve (boxed) guards, e.g. ApiKey struct with PCons inside.
+    pub async fn guard<T: FromPConRequest>(&self) -> Result<T, T::Rejection> {
+        T::from_pcon_request(*self).await
+    }
+
+    // Retrieve (boxed) get parameter(s) that has given name (e.g. /endpoint?a=<THIS>).
+    pub fn query_value<T: RequestPConJson>(
+        &self,
+        name: &str,
+    ) -> Option<Result<T, &'static str>> {
+        let fields = serde_urlencoded::from_str(self.parts.uri.query()?).ok()?;
+        let mut query = InputPConValue::from_form(fields).ok()?;
+        let value = query.get(name).ok()?;
+        if value.is_null() {
+            return None;
+        }
+        Some(T::from_json(value, *self))
+    }
+}
+
+// Like FromRequestParts, but the request is only given as a PConRequest, so that everything
+// extracted from it is in PCon form.
+// Use Sesame<T> to extract these in handlers.
+#[async_trait::async_trait]
+pub trait FromPConRequest: Sized {
+    type Rejection: IntoResponse;
+    async fn from_pcon_request(request: PConRequest<'_>) -> Result<Self, Self::Rejection>;
+}
+
+// Reads the body of requests (for PConJson and PConForm).
+const BODY_LIMIT: usize = 1 << 20;
+pub(crate) async fn read_body(body: Body) -> Result<Bytes, (StatusCode, &'static str)> {
+    match axum::body::to_bytes(body, BODY_LIMIT).await {
+        Ok(bytes) => Ok(bytes),
+        Err(_) => Err((StatusCode::BAD_REQUEST, "Request data is incomplete")),
+    }
+}
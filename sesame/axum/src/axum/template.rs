@@ -0,0 +1,49 @@
+use std::path::Path;
+
+use axum::response::Html;
+use handlebars::Handlebars;
+
+use sesame::context::{Context, ContextData};
+use sesame::extensions::ExtensionContext;
+use sesame::policy::Reason;
+
+use crate::axum::ResponsePConJson;
+use crate::error::{SesameAxumError, SesameAxumResult};
+
+// Handlebars templates, keep these in the state of the router, e.g.
+// `Router::new().route(...).with_state(Arc::new(PConTemplates::from_dir("templates/")?))`.
+pub struct PConTemplates {
+    registry: Handlebars<'static>,
+}
+impl PConTemplates {
+    pub fn new(registry: Handlebars<'static>) -> Self {
+        Self { registry }
+    }
+    // Registers every *.hbs file in dir, named by its path relative to dir without extension.
+    pub fn from_dir<T: AsRef<Path>>(dir: T) -> SesameAxumResult<Self> {
+        let mut registry = Handlebars::new();
+        match registry.register_templates_directory(".hbs", dir) {
+            Err(err) => Err(SesameAxumError::RenderError(err.to_string())),
+            Ok(()) => Ok(Self::new(registry)),
+        }
+    }
+    pub fn register(&mut self, name: &str, template: &str) -> SesameAxumResult<()> {
+        match self.registry.register_template_string(name, template) {
+            Err(err) => Err(SesameAxumError::RenderError(err.to_string())),
+            Ok(()) => Ok(()),
+        }
+    }
+
+    // Checks the policies of all PCons in params with Reason::TemplateRender, then renders.
+    pub fn render<T: ResponsePConJson, D: ContextData>(
+        &self,
+        name: &str,
+        params: T,
+        context: Context<D>,
+    ) -> SesameAxumResult<Html<String>> {
+        let context = ExtensionContext::new(context);
+        let reason = Reason::TemplateRender(name);
+        let params = params.to_json().transform_with(&context, &reason)?;
+        Ok(Html(self.registry.render(name, &params)?))
+    }
+}
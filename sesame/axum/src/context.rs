@@ -0,0 +1,22 @@
+use axum::response::{IntoResponse, Response};
+
+use sesame::context::{Context, ContextData};
+
+use crate::axum::{FromPConRequest, PConRequest};
+
+// The only way to construct a Context is to get via from a PConRequest using below trait.
+// This also implies that D has to be constructed that way as well, meaning that any sensitive
+// information stored in D (e.g. something from a cookie, like a UserID), will have to originate
+// from the PConRequest (and thus be in PCon form, at least initially).
+// The route of the context is the path the router matched (e.g. "/users/:id").
+#[async_trait::async_trait]
+impl<D: ContextData + FromPConRequest> FromPConRequest for Context<D> {
+    type Rejection = Response;
+
+    async fn from_pcon_request(request: PConRequest<'_>) -> Result<Self, Self::Rejection> {
+        match request.guard::<D>().await {
+            Ok(data) => Ok(Context::new(String::from(request.route()), data)),
+            Err(rejection) => Err(rejection.into_response()),
+        }
+    }
+}
@@ -0,0 +1,45 @@
+use axum::http::StatusCode;
+use axum::response::{IntoResponse, Response};
+use sesame::error::SesameError;
+use std::fmt::{Debug, Display, Formatter};
+
+// Failed policy checks respond with this status, same as sesame_rocket.
+pub(crate) fn sesame_error_response(_error: SesameError) -> Response {
+    StatusCode::from_u16(491).unwrap().into_response()
+}
+
+// SesameError cannot be returned from handlers directly, use this instead.
+#[derive(Clone, Debug)]
+pub enum SesameAxumError {
+    SesameError(SesameError),
+    RenderError(String),
+}
+impl Display for SesameAxumError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        Debug::fmt(self, f)
+    }
+}
+impl std::error::Error for SesameAxumError {}
+impl IntoResponse for SesameAxumError {
+    fn into_response(self) -> Response {
+        match self {
+            SesameAxumError::SesameError(err) => sesame_error_response(err),
+            SesameAxumError::RenderError(_err) => StatusCode::INTERNAL_SERVER_ERROR.into_response(),
+        }
+    }
+}
+
+// Error conversion.
+impl From<SesameError> for SesameAxumError {
+    fn from(e: SesameError) -> Self {
+        SesameAxumError::SesameError(e)
+    }
+}
+impl From<handlebars::RenderError> for SesameAxumError {
+    fn from(e: handlebars::RenderError) -> Self {
+        SesameAxumError::RenderError(e.to_string())
+    }
+}
+
+// Results.
+pub type SesameAxumResult<T> = Result<T, SesameAxumError>;
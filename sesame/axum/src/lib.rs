@@ -0,0 +1,15 @@
+// Export these
+pub mod axum;
+pub mod context;
+pub mod error;
+pub mod policy;
+
+// Framework independent parts (JSON, redirects), also used by derived code.
+pub use sesame_web as web;
+
+// Frontend specific items derived code refers to, found under the same path in every frontend
+// crate (e.g. sesame_rocket::frontend).
+#[doc(hidden)]
+pub mod frontend {
+    pub use crate::axum::{PConRequest, RequestPConJson};
+}
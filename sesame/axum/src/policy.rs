@@ -0,0 +1,48 @@
+use axum::http::request::Parts;
+use cookie::Cookie;
+
+use sesame::policy::{NoPolicy, Policy, PolicyAnd, PolicyOr};
+
+// Front end policy can be constructed from HTTP requests and from cookies.
+// Same as sesame_rocket's FrontendPolicy, but given the parts (head) of an axum request.
+pub trait FrontendPolicy: Policy {
+    fn from_request(parts: &Parts) -> Self
+    where
+        Self: Sized;
+
+    fn from_cookie(name: &str, cookie: &Cookie<'static>, parts: &Parts) -> Self
+    where
+        Self: Sized;
+}
+
+// Impl FrontendPolicy for some policy containers
+impl FrontendPolicy for NoPolicy {
+    fn from_request(_parts: &Parts) -> Self {
+        Self {}
+    }
+    fn from_cookie(_name: &str, _cookie: &Cookie<'static>, _parts: &Parts) -> Self {
+        Self {}
+    }
+}
+impl<P1: FrontendPolicy, P2: FrontendPolicy> FrontendPolicy for PolicyAnd<P1, P2> {
+    fn from_request(parts: &Parts) -> Self {
+        PolicyAnd::new(P1::from_request(parts), P2::from_request(parts))
+    }
+    fn from_cookie(name: &str, cookie: &Cookie<'static>, parts: &Parts) -> Self {
+        PolicyAnd::new(
+            P1::from_cookie(name, cookie, parts),
+            P2::from_cookie(name, cookie, parts),
+        )
+    }
+}
+impl<P1: FrontendPolicy, P2: FrontendPolicy> FrontendPolicy for PolicyOr<P1, P2> {
+    fn from_request(parts: &Parts) -> Self {
+        PolicyOr::new(P1::from_request(parts), P2::from_request(parts))
+    }
+    fn from_cookie(name: &str, cookie: &Cookie<'static>, parts: &Parts) -> Self {
+        PolicyOr::new(
+            P1::from_cookie(name, cookie, parts),
+            P2::from_cookie(name, cookie, parts),
+        )
+    }
+}
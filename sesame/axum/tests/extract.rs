@@ -0,0 +1,175 @@
+use std::collections::HashMap;
+
+use axum::body::Body;
+use axum::extract::{FromRequest, FromRequestParts, Request};
+use axum::http::StatusCode;
+
+use sesame::context::Context;
+use sesame::pcon::PCon;
+use sesame::policy::NoPolicy;
+use sesame::SesameType;
+use sesame_derive::RequestPConJson;
+
+use sesame_axum::axum::{
+    FromPConRequest, InputPConValue, PConForm, PConJson, PConRequest, RequestPConJson, Sesame,
+};
+
+#[derive(SesameType)]
+pub struct User {
+    pub name: Option<PCon<String, NoPolicy>>,
+}
+#[async_trait::async_trait]
+impl FromPConRequest for User {
+    type Rejection = StatusCode;
+    async fn from_pcon_request(request: PConRequest<'_>) -> Result<Self, Self::Rejection> {
+        Ok(User {
+            name: request.cookies().get("user"),
+        })
+    }
+}
+
+pub struct Dog {
+    pub name: PCon<String, NoPolicy>,
+    pub age: PCon<u8, NoPolicy>,
+    pub tags: Vec<PCon<String, NoPolicy>>,
+}
+impl RequestPConJson for Dog {
+    fn from_json(
+        mut value: InputPConValue,
+        request: PConRequest<'_>,
+    ) -> Result<Self, &'static str> {
+        Ok(Dog {
+            name: value.get("name")?.into_json(request)?,
+            age: value.get("age")?.into_json(request)?,
+            tags: value.get("tags")?.into_json(request)?,
+        })
+    }
+}
+
+pub struct Adoption {
+    pub owner: PCon<String, NoPolicy>,
+    pub dog: Dog,
+}
+impl RequestPConJson for Adoption {
+    fn from_json(
+        mut value: InputPConValue,
+        request: PConRequest<'_>,
+    ) -> Result<Self, &'static str> {
+        Ok(Adoption {
+            owner: value.get("owner")?.into_json(request)?,
+            dog: value.get("dog")?.into_json(request)?,
+        })
+    }
+}
+
+// Same as Dog, but derived.
+#[derive(RequestPConJson)]
+#[sesame(crate = "::sesame_axum")]
+pub struct DerivedDog {
+    pub name: PCon<String, NoPolicy>,
+    pub age: PCon<u8, NoPolicy>,
+    pub tags: Vec<PCon<String, NoPolicy>>,
+}
+
+fn request(uri: &str, body: &'static str) -> Request {
+    Request::builder()
+        .uri(uri)
+        .header("Cookie", "theme=dark; user=kinan")
+        .header("X-Api-Key", "secret")
+        .body(Body::from(body))
+        .unwrap()
+}
+
+#[tokio::test]
+async fn test_context() {
+    let (mut parts, _) = request("/users/10?page=2", "").into_parts();
+    let Sesame(context) = Sesame::<Context<User>>::from_request_parts(&mut parts, &())
+        .await
+        .unwrap_or_else(|_| panic!("context is constructible"));
+    assert_eq!(context.route(), "/users/10");
+    let name = context.data().unwrap().name.as_ref().unwrap();
+    assert_eq!(name.as_ref().discard_box(), "kinan");
+}
+
+#[tokio::test]
+async fn test_request_parts() {
+    let (parts, _) =
+        request("/dogs?dog.name=Max&dog.age=10&dog.tags=a&dog.tags=b", "").into_parts();
+    let request = PConRequest::new(&parts);
+    assert_eq!(request.route(), "/dogs");
+
+    let header: PCon<String, NoPolicy> = request.header("X-Api-Key").unwrap();
+    assert_eq!(header.discard_box(), "secret");
+    assert!(request.header::<NoPolicy>("X-Missing").is_none());
+
+    let dog: Dog = request.query_value("dog").unwrap().unwrap();
+    assert_eq!(dog.name.discard_box(), "Max");
+    assert_eq!(dog.age.discard_box(), 10);
+    let tags: Vec<String> = dog.tags.into_iter().map(|tag| tag.discard_box()).collect();
+    assert_eq!(tags, vec!["a", "b"]);
+    assert!(request.query_value::<Dog>("cat").is_none());
+}
+
+#[tokio::test]
+async fn test_json() {
+    let body = r#"{"owner": "kinan", "dog": {"name": "Max", "age": 10, "tags": ["good"]}}"#;
+    let PConJson(adoption) = PConJson::<Adoption>::from_request(request("/adopt", body), &())
+        .await
+        .unwrap();
+    assert_eq!(adoption.owner.discard_box(), "kinan");
+    assert_eq!(adoption.dog.name.discard_box(), "Max");
+    assert_eq!(adoption.dog.age.discard_box(), 10);
+    assert_eq!(adoption.dog.tags.len(), 1);
+
+    // The age is a string, which is only allowed in forms.
+    let body = r#"{"owner": "kinan", "dog": {"name": "Max", "age": "10", "tags": []}}"#;
+    let result = PConJson::<Adoption>::from_request(request("/adopt", body), &()).await;
+    assert_eq!(result.err().unwrap().0, StatusCode::BAD_REQUEST);
+}
+
+#[tokio::test]
+async fn test_form() {
+    let body = "owner=kinan&dog.name=Max&dog.age=10&dog.tags=good";
+    let PConForm(adoption) = PConForm::<Adoption>::from_request(request("/adopt", body), &())
+        .await
+        .unwrap();
+    assert_eq!(adoption.owner.discard_box(), "kinan");
+    assert_eq!(adoption.dog.name.discard_box(), "Max");
+    assert_eq!(adoption.dog.age.discard_box(), 10);
+    let tags: Vec<String> = adoption
+        .dog
+        .tags
+        .into_iter()
+        .map(|tag| tag.discard_box())
+        .collect();
+    assert_eq!(tags, vec!["good"]);
+
+    let body = "owner=kinan&dog.name=Max&dog.age=old";
+    let result = PConForm::<Adoption>::from_request(request("/adopt", body), &()).await;
+    assert_eq!(result.err().unwrap().0, StatusCode::BAD_REQUEST);
+
+    let body = "a=1&b=2";
+    let PConForm(map) =
+        PConForm::<HashMap<String, PCon<u32, NoPolicy>>>::from_request(request("/", body), &())
+            .await
+            .unwrap();
+    assert_eq!(map.len(), 2);
+}
+
+#[tokio::test]
+async fn test_derived_json() {
+    let body = r#"{"name": "Max", "age": 10, "tags": ["good"]}"#;
+    let PConJson(dog) = PConJson::<DerivedDog>::from_request(request("/dogs", body), &())
+        .await
+        .unwrap();
+    assert_eq!(dog.name.discard_box(), "Max");
+    assert_eq!(dog.age.discard_box(), 10);
+    assert_eq!(dog.tags.len(), 1);
+
+    let body = "name=Max&age=10";
+    let PConForm(dog) = PConForm::<DerivedDog>::from_request(request("/dogs", body), &())
+        .await
+        .unwrap();
+    assert_eq!(dog.age.discard_box(), 10);
+    assert!(dog.tags.is_empty());
+}
@@ -0,0 +1,112 @@
+use axum::http::header::SET_COOKIE;
+use axum::http::StatusCode;
+use axum::response::{IntoResponse, Response};
+
+use sesame::context::{Context, UnprotectedContext};
+use sesame::pcon::PCon;
+use sesame::policy::{Reason, SimplePolicy};
+
+use sesame_axum::axum::{
+    JsonResponse, OutputPConValue, PConSetCookies, PConTemplates, ResponsePConJson,
+};
+
+#[derive(Clone)]
+pub struct HardcodedPolicy(pub bool);
+impl SimplePolicy for HardcodedPolicy {
+    fn simple_name(&self) -> String {
+        String::from("HardcodedPolicy")
+    }
+    fn simple_check(&self, _context: &UnprotectedContext, reason: Reason<'_>) -> bool {
+        match reason {
+            Reason::Response | Reason::Cookie(_) => self.0,
+            Reason::TemplateRender(template) => self.0 && template == "grade",
+            _ => false,
+        }
+    }
+    fn simple_join_direct(&mut self, other: &mut Self) {
+        self.0 = self.0 && other.0;
+    }
+}
+
+pub struct Grade {
+    pub name: String,
+    pub grade: PCon<u64, HardcodedPolicy>,
+}
+impl ResponsePConJson for Grade {
+    fn to_json(self) -> OutputPConValue {
+        OutputPConValue::Object(
+            vec![
+                (String::from("name"), self.name.to_json()),
+                (String::from("grade"), self.grade.to_json()),
+            ]
+            .into_iter()
+            .collect(),
+        )
+    }
+}
+
+fn grade(allowed: bool) -> Grade {
+    Grade {
+        name: String::from("Kinan"),
+        grade: PCon::new(90, HardcodedPolicy(allowed)),
+    }
+}
+
+async fn body(response: Response) -> String {
+    let bytes = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    String::from_utf8(bytes.to_vec()).unwrap()
+}
+
+#[tokio::test]
+async fn test_json_response() {
+    let response = JsonResponse(grade(true), Context::test(())).into_response();
+    assert_eq!(response.status(), StatusCode::OK);
+    let json: serde_json::Value = serde_json::from_str(&body(response).await).unwrap();
+    assert_eq!(json, serde_json::json!({"name": "Kinan", "grade": 90}));
+
+    let response = JsonResponse(grade(false), Context::test(())).into_response();
+    assert_eq!(response.status(), StatusCode::from_u16(491).unwrap());
+}
+
+#[tokio::test]
+async fn test_cookies() {
+    let mut cookies = PConSetCookies::new();
+    let allowed = PCon::new(String::from("kinan"), HardcodedPolicy(true));
+    assert!(cookies.add("user", allowed, Context::test(())).is_ok());
+    let denied = PCon::new(String::from("secret"), HardcodedPolicy(false));
+    assert!(cookies.add("token", denied, Context::test(())).is_err());
+    cookies.remove("theme");
+
+    let response = (cookies, "ok").into_response();
+    let set_cookies: Vec<&str> = response
+        .headers()
+        .get_all(SET_COOKIE)
+        .iter()
+        .map(|header| header.to_str().unwrap())
+        .collect();
+    assert_eq!(set_cookies.len(), 2);
+    assert!(set_cookies[0].starts_with("user=kinan;"));
+    assert!(set_cookies[0].contains("; Secure"));
+    assert!(set_cookies[1].starts_with("theme=;"));
+}
+
+#[tokio::test]
+async fn test_template() {
+    let mut templates = PConTemplates::new(handlebars::Handlebars::new());
+    templates
+        .register("grade", "{{name}} got {{grade}}")
+        .unwrap();
+    templates.register("other", "{{grade}}").unwrap();
+
+    let html = templates.render("grade", grade(true), Context::test(()));
+    assert_eq!(body(html.unwrap().into_response()).await, "Kinan got 90");
+
+    assert!(templates
+        .render("grade", grade(false), Context::test(()))
+        .is_err());
+    assert!(templates
+        .render("other", grade(true), Context::test(()))
+        .is_err());
+}
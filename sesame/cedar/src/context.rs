@@ -0,0 +1,15 @@
+use std::any::Any;
+
+use cedar_policy::{Entity, EntityUid};
+
+// Implemented by the (folded) context data type of the application to expose the principal
+// making the request to cedar.
+pub trait CedarContext: Any {
+    // The principal making the request, if any (e.g. User::"alice").
+    fn principal(&self) -> Option<EntityUid>;
+    // Additional entities the policies may refer to, e.g. the principal's entity with its
+    // attributes and the groups it belongs to.
+    fn entities(&self) -> Vec<Entity> {
+        Vec::new()
+    }
+}
@@ -0,0 +1,17 @@
+#[macro_use]
+extern crate lazy_static;
+
+mod context;
+mod policy;
+mod registry;
+#[cfg(feature = "mysql")]
+mod schema;
+
+pub use context::*;
+pub use policy::*;
+pub use registry::*;
+#[cfg(feature = "mysql")]
+pub use schema::*;
+
+// Re-export cedar so applications use the same version.
+pub use cedar_policy as cedar;
@@ -0,0 +1,161 @@
+use std::fmt::{Debug, Formatter};
+use std::marker::PhantomData;
+use std::str::FromStr;
+use std::sync::Arc;
+
+use cedar_policy::{
+    Authorizer, Context, Decision, Entities, Entity, EntityId, EntityTypeName, EntityUid,
+    PolicySet, Request, RestrictedExpression,
+};
+
+use sesame::context::UnprotectedContext;
+use sesame::policy::{Reason, SimplePolicy};
+
+use crate::context::CedarContext;
+use crate::registry::get_policy_set;
+
+// Sesame reasons are exposed to cedar as actions:
+// Action::"DB", Action::"Render", Action::"Cookie", Action::"Redirect", Action::"Session",
+// Action::"Stream", Action::"FileWrite", Action::"Response", and Action::"Custom". The target of the reason (e.g. the template name) is in context.target.
+pub fn reason_to_action(reason: &Reason<'_>) -> Option<EntityUid> {
+    let action = match reason {
+        Reason::DB(_, _) => "DB",
+        Reason::TemplateRender(_) => "Render",
+        Reason::Cookie(_) => "Cookie",
+        Reason::Redirect(_) => "Redirect",
+        Reason::Session(_) => "Session",
+        Reason::Stream(_) => "Stream",
+        Reason::FileWrite(_) => "FileWrite",
+        Reason::Response => "Response",
+        Reason::Custom(_) => "Custom",
+    };
+    let type_name = EntityTypeName::from_str("Action").ok()?;
+    let id = EntityId::from_str(action).ok()?;
+    Some(EntityUid::from_type_name_and_id(type_name, id))
+}
+fn reason_to_context(reason: &Reason<'_>) -> Context {
+    let target = match reason {
+        Reason::DB(query, _) => Some(query),
+        Reason::TemplateRender(template) => Some(template),
+        Reason::Cookie(cookie) => Some(cookie),
+        Reason::Redirect(path) => Some(path),
+        Reason::Session(session) => Some(session),
+        Reason::Stream(path) => Some(path),
+        Reason::FileWrite(path) => Some(path),
+        Reason::Response | Reason::Custom(_) => None,
+    };
+    match target {
+        None => Context::empty(),
+        Some(target) => Context::from_pairs([(
+            String::from("target"),
+            RestrictedExpression::new_string(target.to_string()),
+        )]),
+    }
+}
+
+// A leaf policy whose check is delegated to a cedar policy set.
+// The data protected by this policy is the resource. Joining CedarPolicies never stacks: the
+// joined policy keeps all resources and only allows access if cedar allows all of them.
+// Data whose resource could not be built (e.g. rows of unregistered tables) gets a policy that
+// denies everything, see deny_all.
+pub struct CedarPolicy<C: CedarContext> {
+    resources: Vec<(Arc<PolicySet>, Entity)>,
+    deny: bool,
+    _c: PhantomData<fn() -> C>,
+}
+
+impl<C: CedarContext> CedarPolicy<C> {
+    // Uses the global policy set (see set_policy_set).
+    pub fn new(resource: Entity) -> Self {
+        Self::with_policy_set(get_policy_set(), resource)
+    }
+    pub fn with_policy_set(policies: Arc<PolicySet>, resource: Entity) -> Self {
+        Self {
+            resources: vec![(policies, resource)],
+            deny: false,
+            _c: PhantomData,
+        }
+    }
+    pub fn deny_all() -> Self {
+        Self {
+            resources: Vec::new(),
+            deny: true,
+            _c: PhantomData,
+        }
+    }
+    pub fn resources(&self) -> Vec<EntityUid> {
+        self.resources.iter().map(|(_, r)| r.uid()).collect()
+    }
+
+    // Check directly against a CedarContext.
+    pub fn is_authorized(&self, context: &C, reason: &Reason<'_>) -> bool {
+        if self.deny {
+            return false;
+        }
+        let action = match reason_to_action(reason) {
+            None => return false,
+            Some(action) => action,
+        };
+        let authorizer = Authorizer::new();
+        let principal = context.principal();
+        self.resources.iter().all(|(policies, resource)| {
+            let mut entities = context.entities();
+            entities.push(resource.clone());
+            let entities = match Entities::from_entities(entities) {
+                Ok(entities) => entities,
+                Err(_) => return false,
+            };
+            let request = Request::new(
+                principal.clone(),
+                Some(action.clone()),
+                Some(resource.uid()),
+                reason_to_context(reason),
+            );
+            let response = authorizer.is_authorized(&request, policies, &entities);
+            response.decision() == Decision::Allow
+        })
+    }
+}
+
+impl<C: CedarContext> SimplePolicy for CedarPolicy<C> {
+    fn simple_name(&self) -> String {
+        if self.deny {
+            String::from("CedarPolicy(deny all)")
+        } else {
+            format!("CedarPolicy(resources: {:?})", self.resources())
+        }
+    }
+    fn simple_check(&self, context: &UnprotectedContext, reason: Reason<'_>) -> bool {
+        match context.downcast_ref::<C>() {
+            None => false,
+            Some(context) => self.is_authorized(context, &reason),
+        }
+    }
+    fn simple_join_direct(&mut self, other: &mut Self) {
+        self.deny = self.deny || other.deny;
+        for (policies, resource) in other.resources.drain(..) {
+            let duplicate = self
+                .resources
+                .iter()
+                .any(|(p, r)| Arc::ptr_eq(p, &policies) && r == &resource);
+            if !duplicate {
+                self.resources.push((policies, resource));
+            }
+        }
+    }
+}
+
+impl<C: CedarContext> Clone for CedarPolicy<C> {
+    fn clone(&self) -> Self {
+        Self {
+            resources: self.resources.clone(),
+            deny: self.deny,
+            _c: PhantomData,
+        }
+    }
+}
+impl<C: CedarContext> Debug for CedarPolicy<C> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.simple_name())
+    }
+}
@@ -0,0 +1,55 @@
+use std::collections::HashMap;
+use std::str::FromStr;
+use std::sync::{Arc, RwLock};
+
+use cedar_policy::{ParseErrors, PolicySet};
+
+// How rows of a DB table are turned into cedar resources.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CedarTable {
+    // The cedar entity type of rows, e.g. "Chat".
+    pub entity_type: String,
+    // The index of the column holding the row's entity id.
+    pub id_column: usize,
+    // The names of the columns in order, these become the attributes of the resource.
+    pub columns: Vec<String>,
+}
+impl CedarTable {
+    pub fn new<S: ToString>(entity_type: S, id_column: usize, columns: &[&str]) -> Self {
+        Self {
+            entity_type: entity_type.to_string(),
+            id_column,
+            columns: columns.iter().map(|c| c.to_string()).collect(),
+        }
+    }
+}
+
+// Global static singletons.
+lazy_static! {
+    static ref POLICY_SET: RwLock<Arc<PolicySet>> = RwLock::new(Arc::new(PolicySet::new()));
+    static ref TABLES: RwLock<HashMap<String, CedarTable>> = RwLock::new(HashMap::new());
+}
+
+// Set the cedar policies that CedarPolicy instances created from now on evaluate.
+// Should be called once at startup.
+pub fn set_policy_set(policies: PolicySet) {
+    *POLICY_SET.write().unwrap() = Arc::new(policies);
+}
+pub fn load_policy_set(source: &str) -> Result<(), ParseErrors> {
+    set_policy_set(PolicySet::from_str(source)?);
+    Ok(())
+}
+pub fn get_policy_set() -> Arc<PolicySet> {
+    POLICY_SET.read().unwrap().clone()
+}
+
+// Register how rows of the given table map to cedar resources.
+pub fn register_table(table_name: &str, table: CedarTable) {
+    TABLES
+        .write()
+        .unwrap()
+        .insert(String::from(table_name), table);
+}
+pub fn get_table(table_name: &str) -> Option<CedarTable> {
+    TABLES.read().unwrap().get(table_name).cloned()
+}
@@ -0,0 +1,94 @@
+use std::collections::{HashMap, HashSet};
+use std::convert::TryFrom;
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+
+use cedar_policy::{Entity, EntityId, EntityTypeName, EntityUid, RestrictedExpression};
+
+use sesame_mysql::SchemaPolicy;
+
+use crate::context::CedarContext;
+use crate::policy::CedarPolicy;
+use crate::registry::{get_table, CedarTable};
+
+fn value_to_string(value: &mysql::Value) -> String {
+    match value {
+        mysql::Value::Bytes(bytes) => String::from_utf8_lossy(bytes).to_string(),
+        mysql::Value::Int(i) => i.to_string(),
+        mysql::Value::UInt(u) => u.to_string(),
+        other => other.as_sql(true),
+    }
+}
+fn value_to_expression(value: &mysql::Value) -> Option<RestrictedExpression> {
+    match value {
+        mysql::Value::NULL => None,
+        mysql::Value::Int(i) => Some(RestrictedExpression::new_long(*i)),
+        mysql::Value::UInt(u) => match i64::try_from(*u) {
+            Ok(i) => Some(RestrictedExpression::new_long(i)),
+            Err(_) => Some(RestrictedExpression::new_string(u.to_string())),
+        },
+        other => Some(RestrictedExpression::new_string(value_to_string(other))),
+    }
+}
+
+// Errors building the resource entity of a row.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CedarRowError {
+    UnregisteredTable(String),
+    InvalidEntityType(String),
+    MissingIdColumn(usize),
+}
+impl Display for CedarRowError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CedarRowError::UnregisteredTable(table) => {
+                write!(f, "table {} is not registered with sesame_cedar", table)
+            }
+            CedarRowError::InvalidEntityType(name) => {
+                write!(f, "{} is not a valid cedar entity type name", name)
+            }
+            CedarRowError::MissingIdColumn(column) => {
+                write!(f, "row has no id column {}", column)
+            }
+        }
+    }
+}
+impl Error for CedarRowError {}
+
+// Build the resource entity of a row, e.g. Chat::"5" with one attribute per (non-null) column.
+pub fn entity_from_row(table: &CedarTable, row: &[mysql::Value]) -> Result<Entity, CedarRowError> {
+    let type_name = EntityTypeName::from_str(&table.entity_type)
+        .map_err(|_| CedarRowError::InvalidEntityType(table.entity_type.clone()))?;
+    let id = match row.get(table.id_column) {
+        None => return Err(CedarRowError::MissingIdColumn(table.id_column)),
+        Some(id) => value_to_string(id),
+    };
+    let id =
+        EntityId::from_str(&id).map_err(|_| CedarRowError::MissingIdColumn(table.id_column))?;
+    let attrs: HashMap<String, RestrictedExpression> = table
+        .columns
+        .iter()
+        .zip(row.iter())
+        .filter_map(|(column, value)| Some((column.clone(), value_to_expression(value)?)))
+        .collect();
+    Ok(Entity::new(
+        EntityUid::from_type_name_and_id(type_name, id),
+        attrs,
+        HashSet::new(),
+    ))
+}
+
+// Tables must be registered (see register_table) before rows are read from them, rows of
+// unregistered tables or that cannot be turned into resources are denied to everyone.
+impl<C: CedarContext> SchemaPolicy for CedarPolicy<C> {
+    fn from_row(table_name: &str, row: &Vec<mysql::Value>) -> Self {
+        let entity = get_table(table_name)
+            .ok_or_else(|| CedarRowError::UnregisteredTable(String::from(table_name)))
+            .and_then(|table| entity_from_row(&table, row));
+        match entity {
+            Ok(entity) => CedarPolicy::new(entity),
+            Err(_) => CedarPolicy::deny_all(),
+        }
+    }
+}
@@ -0,0 +1,133 @@
+use std::collections::{HashMap, HashSet};
+use std::str::FromStr;
+use std::sync::Arc;
+
+use sesame::context::UnprotectedContext;
+use sesame::policy::{JoinAPI, Policy, Reason};
+use sesame_cedar::cedar::{Entity, EntityUid, PolicySet, RestrictedExpression};
+use sesame_cedar::{CedarContext, CedarPolicy};
+
+const POLICIES: &str = r#"
+    permit(principal, action == Action::"Response", resource)
+    when { resource has sender && resource.sender == principal.name };
+
+    permit(principal in Group::"admins", action, resource);
+
+    permit(principal, action == Action::"Render", resource)
+    when { context.target == "public" };
+"#;
+
+pub struct User {
+    name: &'static str,
+    admin: bool,
+}
+impl CedarContext for User {
+    fn principal(&self) -> Option<EntityUid> {
+        Some(EntityUid::from_str(&format!("User::\"{}\"", self.name)).unwrap())
+    }
+    fn entities(&self) -> Vec<Entity> {
+        let mut parents = HashSet::new();
+        if self.admin {
+            parents.insert(EntityUid::from_str("Group::\"admins\"").unwrap());
+        }
+        vec![
+            Entity::new(
+                self.principal().unwrap(),
+                HashMap::from([(
+                    String::from("name"),
+                    RestrictedExpression::new_string(String::from(self.name)),
+                )]),
+                parents,
+            ),
+            Entity::with_uid(EntityUid::from_str("Group::\"admins\"").unwrap()),
+        ]
+    }
+}
+
+fn user(name: &'static str, admin: bool) -> UnprotectedContext {
+    UnprotectedContext::test(User { name, admin })
+}
+
+fn chat(id: &str, sender: &str) -> Entity {
+    Entity::new(
+        EntityUid::from_str(&format!("Chat::\"{}\"", id)).unwrap(),
+        HashMap::from([(
+            String::from("sender"),
+            RestrictedExpression::new_string(String::from(sender)),
+        )]),
+        HashSet::new(),
+    )
+}
+
+#[test]
+fn cedar_policy_check() {
+    let policies = Arc::new(PolicySet::from_str(POLICIES).unwrap());
+    let policy = CedarPolicy::<User>::with_policy_set(policies, chat("1", "alice"));
+
+    assert!(policy.check(&user("alice", false), Reason::Response));
+    assert!(!policy.check(&user("bob", false), Reason::Response));
+    assert!(!policy.check(&user("alice", false), Reason::Cookie("alice")));
+    assert!(policy.check(&user("bob", true), Reason::Cookie("alice")));
+    assert!(policy.check(&user("bob", false), Reason::TemplateRender("public")));
+    assert!(!policy.check(&user("bob", false), Reason::TemplateRender("private")));
+}
+
+#[test]
+fn cedar_policy_join() {
+    let policies = Arc::new(PolicySet::from_str(POLICIES).unwrap());
+    let policy1 = CedarPolicy::<User>::with_policy_set(policies.clone(), chat("1", "alice"));
+    let policy2 = CedarPolicy::<User>::with_policy_set(policies.clone(), chat("2", "bob"));
+    let joined = policy1.join(policy2);
+
+    assert!(!joined.check(&user("alice", false), Reason::Response));
+    assert!(!joined.check(&user("bob", false), Reason::Response));
+    assert!(joined.check(&user("carl", true), Reason::Response));
+
+    // Joining reflexively does not stack policies.
+    let joined = joined.specialize_top::<CedarPolicy<User>>().unwrap();
+    assert_eq!(joined.resources().len(), 2);
+}
+
+#[cfg(feature = "mysql")]
+#[test]
+fn cedar_policy_from_row() {
+    use sesame_cedar::{load_policy_set, register_table, CedarTable};
+    use sesame_mysql::SchemaPolicy;
+
+    load_policy_set(POLICIES).unwrap();
+    register_table(
+        "chats",
+        CedarTable::new("Chat", 0, &["id", "sender", "content"]),
+    );
+
+    let row = vec![
+        mysql::Value::Int(5),
+        mysql::Value::from("alice"),
+        mysql::Value::NULL,
+    ];
+    let policy = CedarPolicy::<User>::from_row("chats", &row);
+    assert_eq!(
+        policy.resources(),
+        vec![EntityUid::from_str("Chat::\"5\"").unwrap()]
+    );
+    assert!(policy.check(&user("alice", false), Reason::Response));
+    assert!(!policy.check(&user("bob", false), Reason::Response));
+
+    // Ids with quotes are fine.
+    let row = vec![
+        mysql::Value::from("5\" || true"),
+        mysql::Value::from("alice"),
+        mysql::Value::NULL,
+    ];
+    let policy = CedarPolicy::<User>::from_row("chats", &row);
+    assert!(policy.check(&user("alice", false), Reason::Response));
+
+    // Unregistered tables and malformed rows are denied, even to admins.
+    let policy = CedarPolicy::<User>::from_row("unregistered", &row);
+    assert!(!policy.check(&user("alice", true), Reason::Response));
+    register_table("bad_chats", CedarTable::new("Bad Chat", 0, &["id"]));
+    let policy = CedarPolicy::<User>::from_row("bad_chats", &row);
+    assert!(!policy.check(&user("alice", true), Reason::Response));
+    let policy = CedarPolicy::<User>::from_row("chats", &Vec::new());
+    assert!(!policy.check(&user("alice", true), Reason::Response));
+}
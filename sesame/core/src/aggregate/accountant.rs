@@ -0,0 +1,88 @@
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+use crate::aggregate::check_privacy_parameters;
+use crate::error::{SesameError, SesameResult};
+
+// Tracks the privacy budget spent per key, where keys identify a dataset or a data subject.
+// Every release spends its epsilon (and delta) from all the keys it is about, and is refused if
+// any of them would exceed the limits (sequential composition).
+pub struct PrivacyAccountant {
+    max_epsilon: f64,
+    max_delta: f64,
+    spent: Mutex<HashMap<String, (f64, f64)>>,
+}
+
+impl PrivacyAccountant {
+    pub fn new(max_epsilon: f64, max_delta: f64) -> Self {
+        Self {
+            max_epsilon,
+            max_delta,
+            spent: Mutex::new(HashMap::new()),
+        }
+    }
+
+    // Spend budget from all keys, or none if any key does not have enough left or the
+    // parameters are invalid (e.g. negative or NaN epsilon).
+    // Releases must be charged to at least one key, otherwise they would be free.
+    pub fn spend<S: AsRef<str>>(&self, keys: &[S], epsilon: f64, delta: f64) -> SesameResult<()> {
+        check_privacy_parameters(epsilon, delta)?;
+        if keys.is_empty() {
+            return Err(SesameError::InvalidPrivacyParameters(String::from(
+                "no keys to charge",
+            )));
+        }
+        let mut spent = self.spent.lock().unwrap();
+        for key in keys {
+            let (e, d) = spent.get(key.as_ref()).cloned().unwrap_or((0.0, 0.0));
+            if e + epsilon > self.max_epsilon || d + delta > self.max_delta {
+                return Err(SesameError::PrivacyBudgetExhausted(String::from(
+                    key.as_ref(),
+                )));
+            }
+        }
+        for key in keys {
+            let entry = spent
+                .entry(String::from(key.as_ref()))
+                .or_insert((0.0, 0.0));
+            entry.0 += epsilon;
+            entry.1 += delta;
+        }
+        Ok(())
+    }
+
+    // The (epsilon, delta) left for key.
+    pub fn remaining(&self, key: &str) -> (f64, f64) {
+        let spent = self.spent.lock().unwrap();
+        let (e, d) = spent.get(key).cloned().unwrap_or((0.0, 0.0));
+        (self.max_epsilon - e, self.max_delta - d)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::aggregate::PrivacyAccountant;
+
+    #[test]
+    fn accountant_spends_all_or_nothing() {
+        let accountant = PrivacyAccountant::new(1.0, 0.0);
+        accountant.spend(&["alice"], 0.75, 0.0).unwrap();
+        assert!(accountant.spend(&["alice", "bob"], 0.5, 0.0).is_err());
+        assert_eq!(accountant.remaining("bob"), (1.0, 0.0));
+        accountant.spend(&["alice", "bob"], 0.25, 0.0).unwrap();
+        assert_eq!(accountant.remaining("alice"), (0.0, 0.0));
+        assert_eq!(accountant.remaining("bob"), (0.75, 0.0));
+        assert!(accountant.spend(&["bob"], 0.5, 1e-5).is_err());
+
+        // Invalid parameters cannot be used to refund budget or to bypass the limits.
+        assert!(accountant.spend(&["bob"], -0.5, 0.0).is_err());
+        assert!(accountant.spend(&["bob"], f64::NAN, 0.0).is_err());
+        assert!(accountant.spend(&["bob"], 0.0, 0.0).is_err());
+        assert!(accountant.spend(&["bob"], 0.1, 1.0).is_err());
+        assert_eq!(accountant.remaining("bob"), (0.75, 0.0));
+
+        // Releases cannot be free.
+        let empty: &[&str] = &[];
+        assert!(accountant.spend(empty, 0.1, 0.0).is_err());
+    }
+}
@@ -0,0 +1,187 @@
+use std::collections::{BTreeMap, BTreeSet};
+
+use crate::context::{Context, ContextData, UnprotectedContext};
+use crate::error::{SesameError, SesameResult};
+use crate::pcon::PCon;
+use crate::policy::{Policy, Reason};
+use crate::verified::VerifiedRegion;
+
+// Releases of group statistics over at least k distinct subjects are communicated to policies
+// via Reason::Custom(&GroupedRelease { k }).
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct GroupedRelease {
+    pub k: usize,
+}
+
+// Helper for policies that opt into grouped releases with at least min_k subjects per group.
+pub fn allows_grouped(reason: &Reason<'_>, min_k: usize) -> bool {
+    match reason {
+        Reason::Custom(custom) => match custom.downcast_ref::<GroupedRelease>() {
+            None => false,
+            Some(release) => release.k >= min_k,
+        },
+        _ => false,
+    }
+}
+
+// The released statistics of groups with at least k subjects, and how many groups were
+// suppressed for having fewer.
+#[derive(Clone, Debug, PartialEq)]
+pub struct GroupRelease<K: Ord, O> {
+    pub groups: BTreeMap<K, O>,
+    pub suppressed: usize,
+}
+
+// Groups the data by key, aggregates every group, and releases the aggregates of groups that
+// at least k distinct subjects (see Policy::subjects) contribute to. Every element must pass its
+// policy check with GroupedRelease { k }.
+// Works with data read from the DB (PCon<T, AnyPolicy>) as long as its schema policies expose
+// their subjects, elements whose policies know no subjects never make up a released group.
+pub fn group_by<T, P: Policy, D: ContextData, K: Ord, O, G: Fn(&T) -> K, A: Fn(Vec<T>) -> O>(
+    data: Vec<PCon<T, P>>,
+    context: Context<D>,
+    k: usize,
+    key: VerifiedRegion<G>,
+    aggregate: VerifiedRegion<A>,
+) -> SesameResult<GroupRelease<K, O>> {
+    let context = UnprotectedContext::from(context);
+    let release = GroupedRelease { k };
+    let key = key.get_functor();
+
+    let mut groups: BTreeMap<K, (Vec<T>, BTreeSet<String>)> = BTreeMap::new();
+    for pcon in data {
+        let (t, p) = pcon.consume();
+        if !p.check(&context, Reason::Custom(&release)) {
+            return Err(SesameError::PolicyCheckFailed(p.name()));
+        }
+        let group = groups.entry(key(&t)).or_default();
+        group.0.push(t);
+        group.1.extend(p.subjects());
+    }
+
+    let aggregate = aggregate.get_functor();
+    let mut result = GroupRelease {
+        groups: BTreeMap::new(),
+        suppressed: 0,
+    };
+    for (key, (values, subjects)) in groups {
+        if subjects.len() >= k {
+            result.groups.insert(key, aggregate(values));
+        } else {
+            result.suppressed += 1;
+        }
+    }
+    Ok(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::aggregate::{allows_grouped, group_by};
+    use crate::context::{Context, UnprotectedContext};
+    use crate::pcon::PCon;
+    use crate::policy::rbac::{RoleContext, RolePolicy};
+    use crate::policy::{AnyPolicy, PolicyAnd, Reason, SimplePolicy};
+    use crate::verified::VerifiedRegion;
+
+    // Grades can only be released in groups of at least 3 students.
+    #[derive(Clone)]
+    pub struct GradePolicy {
+        student: String,
+    }
+    impl SimplePolicy for GradePolicy {
+        fn simple_name(&self) -> String {
+            format!("GradePolicy({})", self.student)
+        }
+        fn simple_check(&self, _context: &UnprotectedContext, reason: Reason<'_>) -> bool {
+            allows_grouped(&reason, 3)
+        }
+        fn simple_join_direct(&mut self, _other: &mut Self) {}
+        fn simple_subjects(&self) -> Vec<String> {
+            vec![self.student.clone()]
+        }
+    }
+
+    pub struct Admin {}
+    impl RoleContext for Admin {
+        fn principal(&self) -> Option<&str> {
+            Some("admin")
+        }
+        fn roles(&self) -> Vec<&str> {
+            vec!["admin"]
+        }
+    }
+
+    fn grade(question: u64, grade: f64, owner: &str) -> PCon<(u64, f64), RolePolicy<Admin>> {
+        let policy = RolePolicy::with_roles(&["admin"]).allow_owner(owner);
+        PCon::new((question, grade), policy)
+    }
+
+    #[test]
+    fn group_by_suppresses_small_groups() {
+        let data = vec![
+            grade(1, 80.0, "alice"),
+            grade(1, 90.0, "bob"),
+            grade(1, 100.0, "carl"),
+            grade(2, 50.0, "alice"),
+            grade(2, 70.0, "alice"),
+            grade(2, 60.0, "bob"),
+        ];
+        let release = group_by(
+            data,
+            Context::test(Admin {}),
+            3,
+            VerifiedRegion::new(|(question, _): &(u64, f64)| *question),
+            VerifiedRegion::new(|grades: Vec<(u64, f64)>| {
+                grades.iter().map(|(_, g)| g).sum::<f64>() / grades.len() as f64
+            }),
+        )
+        .unwrap();
+        assert_eq!(release.groups.len(), 1);
+        assert_eq!(release.groups[&1], 90.0);
+        assert_eq!(release.suppressed, 1);
+
+        // Policies are checked.
+        let data = vec![grade(1, 80.0, "alice")];
+        let release = group_by(
+            data,
+            Context::test(()),
+            1,
+            VerifiedRegion::new(|(question, _): &(u64, f64)| *question),
+            VerifiedRegion::new(|grades: Vec<(u64, f64)>| grades.len()),
+        );
+        assert!(release.is_err());
+    }
+
+    #[test]
+    fn group_by_db_data() {
+        // Like data read from the DB, with policies behind AnyPolicy.
+        let grade = |question: u64, grade: f64, student: &str| {
+            let student: AnyPolicy = AnyPolicy::new(GradePolicy {
+                student: String::from(student),
+            });
+            let admin: AnyPolicy = AnyPolicy::new(RolePolicy::<Admin>::with_roles(&["admin"]));
+            let policy: AnyPolicy = AnyPolicy::new(PolicyAnd::new(student, admin));
+            PCon::new((question, grade), policy)
+        };
+        let data = || {
+            vec![
+                grade(1, 80.0, "alice"),
+                grade(1, 90.0, "bob"),
+                grade(1, 100.0, "carl"),
+                grade(2, 50.0, "alice"),
+                grade(2, 60.0, "bob"),
+            ]
+        };
+        let key = || VerifiedRegion::new(|(question, _): &(u64, f64)| *question);
+        let aggregate = || VerifiedRegion::new(|grades: Vec<(u64, f64)>| grades.len());
+
+        let release = group_by(data(), Context::test(Admin {}), 3, key(), aggregate()).unwrap();
+        assert_eq!(release.groups.len(), 1);
+        assert_eq!(release.groups[&1], 3);
+        assert_eq!(release.suppressed, 1);
+
+        // The policy does not allow releases over groups of fewer than 3 subjects.
+        let release = group_by(data(), Context::test(Admin {}), 2, key(), aggregate());
+        assert!(release.is_err());
+    }
+}
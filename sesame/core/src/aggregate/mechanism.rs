@@ -0,0 +1,107 @@
+use rand::Rng;
+
+use crate::error::{SesameError, SesameResult};
+
+// Whether epsilon is finite and positive, and delta is in [0, 1).
+pub fn valid_privacy_parameters(epsilon: f64, delta: f64) -> bool {
+    epsilon.is_finite() && epsilon > 0.0 && (0.0..1.0).contains(&delta)
+}
+pub(crate) fn check_privacy_parameters(epsilon: f64, delta: f64) -> SesameResult<()> {
+    if valid_privacy_parameters(epsilon, delta) {
+        Ok(())
+    } else {
+        Err(SesameError::InvalidPrivacyParameters(format!(
+            "epsilon {} must be finite and positive, delta {} must be in [0, 1)",
+            epsilon, delta
+        )))
+    }
+}
+
+// Noise mechanisms for differentially private releases.
+// Use the laplace and gaussian constructors to validate the parameters, releases also refuse
+// mechanisms with invalid parameters.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Mechanism {
+    // Pure epsilon-DP.
+    Laplace { epsilon: f64 },
+    // (epsilon, delta)-DP, for epsilon < 1.
+    Gaussian { epsilon: f64, delta: f64 },
+}
+
+impl Mechanism {
+    pub fn laplace(epsilon: f64) -> SesameResult<Self> {
+        let mechanism = Mechanism::Laplace { epsilon };
+        mechanism.validate()?;
+        Ok(mechanism)
+    }
+    pub fn gaussian(epsilon: f64, delta: f64) -> SesameResult<Self> {
+        let mechanism = Mechanism::Gaussian { epsilon, delta };
+        mechanism.validate()?;
+        Ok(mechanism)
+    }
+
+    // Laplace needs a valid epsilon. The Gaussian mechanism's calibration only holds for
+    // epsilon < 1 and needs a positive delta.
+    pub fn validate(&self) -> SesameResult<()> {
+        check_privacy_parameters(self.epsilon(), self.delta())?;
+        match self {
+            Mechanism::Laplace { .. } => Ok(()),
+            Mechanism::Gaussian { epsilon, delta } if *epsilon < 1.0 && *delta > 0.0 => Ok(()),
+            Mechanism::Gaussian { epsilon, delta } => {
+                Err(SesameError::InvalidPrivacyParameters(format!(
+                    "the gaussian mechanism needs epsilon {} < 1 and delta {} > 0",
+                    epsilon, delta
+                )))
+            }
+        }
+    }
+
+    pub fn epsilon(&self) -> f64 {
+        match self {
+            Mechanism::Laplace { epsilon } => *epsilon,
+            Mechanism::Gaussian { epsilon, .. } => *epsilon,
+        }
+    }
+    pub fn delta(&self) -> f64 {
+        match self {
+            Mechanism::Laplace { .. } => 0.0,
+            Mechanism::Gaussian { delta, .. } => *delta,
+        }
+    }
+
+    // The same mechanism with a fraction of the budget, used to split queries into parts.
+    pub(crate) fn split(&self, parts: f64) -> Self {
+        match self {
+            Mechanism::Laplace { epsilon } => Mechanism::Laplace {
+                epsilon: epsilon / parts,
+            },
+            Mechanism::Gaussian { epsilon, delta } => Mechanism::Gaussian {
+                epsilon: epsilon / parts,
+                delta: delta / parts,
+            },
+        }
+    }
+
+    // Noise calibrated to a query with the given sensitivity.
+    pub(crate) fn noise(&self, sensitivity: f64) -> f64 {
+        let mut rng = rand::thread_rng();
+        match self {
+            Mechanism::Laplace { epsilon } => {
+                let scale = sensitivity / epsilon;
+                // u = -0.5 would give ln(0), so sample from the open interval.
+                let mut u: f64 = rng.gen_range(-0.5..0.5);
+                while u == -0.5 {
+                    u = rng.gen_range(-0.5..0.5);
+                }
+                -scale * u.signum() * (1.0 - 2.0 * u.abs()).ln()
+            }
+            Mechanism::Gaussian { epsilon, delta } => {
+                let sigma = sensitivity * (2.0 * (1.25 / delta).ln()).sqrt() / epsilon;
+                // Box-Muller.
+                let u1: f64 = 1.0 - rng.gen::<f64>();
+                let u2: f64 = rng.gen();
+                sigma * (-2.0 * u1.ln()).sqrt() * (2.0 * std::f64::consts::PI * u2).cos()
+            }
+        }
+    }
+}
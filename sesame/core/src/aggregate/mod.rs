@@ -0,0 +1,11 @@
+mod accountant;
+mod group;
+mod mechanism;
+mod policy;
+mod release;
+
+pub use accountant::*;
+pub use group::*;
+pub use mechanism::*;
+pub use policy::*;
+pub use release::*;
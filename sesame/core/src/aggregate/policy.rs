@@ -0,0 +1,70 @@
+use crate::aggregate::valid_privacy_parameters;
+use crate::context::UnprotectedContext;
+use crate::policy::{Reason, SimplePolicy};
+
+// Differentially private releases are communicated to policies via
+// Reason::Custom(&AggregateRelease { .. }).
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct AggregateRelease {
+    pub epsilon: f64,
+    pub delta: f64,
+}
+
+// The aggregate release implied by a reason, if any.
+pub fn aggregate_release_of<'a>(reason: &'a Reason<'_>) -> Option<&'a AggregateRelease> {
+    match reason {
+        Reason::Custom(custom) => custom.downcast_ref::<AggregateRelease>(),
+        _ => None,
+    }
+}
+
+// Helper for policies that opt into aggregate releases with epsilon <= max_epsilon.
+// Releases with invalid parameters (see valid_privacy_parameters) are never allowed.
+pub fn allows_aggregate(reason: &Reason<'_>, max_epsilon: f64, max_delta: f64) -> bool {
+    match aggregate_release_of(reason) {
+        None => false,
+        Some(release) => {
+            valid_privacy_parameters(release.epsilon, release.delta)
+                && release.epsilon <= max_epsilon
+                && release.delta <= max_delta
+        }
+    }
+}
+
+// Data that can only be released as part of differentially private aggregates.
+#[derive(Clone, Debug, PartialEq)]
+pub struct AggregatePolicy {
+    max_epsilon: f64,
+    max_delta: f64,
+}
+impl AggregatePolicy {
+    pub fn new(max_epsilon: f64, max_delta: f64) -> Self {
+        Self {
+            max_epsilon,
+            max_delta,
+        }
+    }
+    pub fn max_epsilon(&self) -> f64 {
+        self.max_epsilon
+    }
+    pub fn max_delta(&self) -> f64 {
+        self.max_delta
+    }
+}
+
+impl SimplePolicy for AggregatePolicy {
+    fn simple_name(&self) -> String {
+        format!(
+            "AggregatePolicy(epsilon <= {}, delta <= {})",
+            self.max_epsilon, self.max_delta
+        )
+    }
+    fn simple_check(&self, _context: &UnprotectedContext, reason: Reason<'_>) -> bool {
+        allows_aggregate(&reason, self.max_epsilon, self.max_delta)
+    }
+    // The join allows the strictest bounds of both.
+    fn simple_join_direct(&mut self, other: &mut Self) {
+        self.max_epsilon = self.max_epsilon.min(other.max_epsilon);
+        self.max_delta = self.max_delta.min(other.max_delta);
+    }
+}
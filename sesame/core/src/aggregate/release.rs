@@ -0,0 +1,213 @@
+use std::collections::BTreeSet;
+
+use crate::aggregate::{AggregateRelease, Mechanism, PrivacyAccountant};
+use crate::context::{Context, ContextData, UnprotectedContext};
+use crate::error::{SesameError, SesameResult};
+use crate::fold::fold;
+use crate::policy::{Policy, Reason};
+use crate::SesameType;
+
+// Fold the data, check that its policy allows the release, and charge the budget of keys and of
+// every data subject the policy knows of (see Policy::subjects), so callers cannot leave them out.
+// The budget is only spent if the mechanism is valid and the policy check succeeds, and releases
+// that would be charged to no key at all are refused.
+fn release<S: SesameType<Out = Vec<f64>>, D: ContextData, K: AsRef<str>>(
+    data: S,
+    context: Context<D>,
+    mechanism: &Mechanism,
+    accountant: &PrivacyAccountant,
+    keys: &[K],
+) -> SesameResult<Vec<f64>> {
+    mechanism.validate()?;
+    let data = match fold(data) {
+        Ok(data) => data,
+        Err(_) => {
+            return Err(SesameError::SesameTypeFoldFailed(String::from(
+                "fold failed",
+            )))
+        }
+    };
+    let (values, policy) = data.consume();
+    let context = UnprotectedContext::from(context);
+    let release = AggregateRelease {
+        epsilon: mechanism.epsilon(),
+        delta: mechanism.delta(),
+    };
+    if !policy.check(&context, Reason::Custom(&release)) {
+        return Err(SesameError::PolicyCheckFailed(policy.name()));
+    }
+    let mut charged: BTreeSet<String> = keys.iter().map(|k| String::from(k.as_ref())).collect();
+    charged.extend(policy.subjects());
+    let charged: Vec<String> = charged.into_iter().collect();
+    accountant.spend(&charged, release.epsilon, release.delta)?;
+    Ok(values)
+}
+
+// Noisy number of elements.
+pub fn count<S: SesameType<Out = Vec<f64>>, D: ContextData, K: AsRef<str>>(
+    data: S,
+    context: Context<D>,
+    mechanism: Mechanism,
+    accountant: &PrivacyAccountant,
+    keys: &[K],
+) -> SesameResult<f64> {
+    let values = release(data, context, &mechanism, accountant, keys)?;
+    Ok(values.len() as f64 + mechanism.noise(1.0))
+}
+
+// Noisy sum of the elements, each clamped to bounds.
+pub fn sum<S: SesameType<Out = Vec<f64>>, D: ContextData, K: AsRef<str>>(
+    data: S,
+    context: Context<D>,
+    bounds: (f64, f64),
+    mechanism: Mechanism,
+    accountant: &PrivacyAccountant,
+    keys: &[K],
+) -> SesameResult<f64> {
+    let values = release(data, context, &mechanism, accountant, keys)?;
+    let (low, high) = bounds;
+    let sum: f64 = values.iter().map(|v| v.clamp(low, high)).sum();
+    Ok(sum + mechanism.noise(low.abs().max(high.abs())))
+}
+
+// Noisy mean of the elements, each clamped to bounds.
+// The budget is split evenly between a noisy sum and a noisy count.
+pub fn mean<S: SesameType<Out = Vec<f64>>, D: ContextData, K: AsRef<str>>(
+    data: S,
+    context: Context<D>,
+    bounds: (f64, f64),
+    mechanism: Mechanism,
+    accountant: &PrivacyAccountant,
+    keys: &[K],
+) -> SesameResult<f64> {
+    let values = release(data, context, &mechanism, accountant, keys)?;
+    let (low, high) = bounds;
+    let half = mechanism.split(2.0);
+    let sum: f64 = values.iter().map(|v| v.clamp(low, high)).sum();
+    let sum = sum + half.noise(low.abs().max(high.abs()));
+    let count = values.len() as f64 + half.noise(1.0);
+    Ok((sum / count.max(1.0)).clamp(low, high))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::aggregate::{count, mean, sum, AggregatePolicy, Mechanism, PrivacyAccountant};
+    use crate::context::{Context, UnprotectedContext};
+    use crate::pcon::PCon;
+    use crate::policy::{Reason, SimplePolicy};
+
+    // Data about a single subject, allowed to be released.
+    #[derive(Clone)]
+    pub struct SubjectPolicy {}
+    impl SimplePolicy for SubjectPolicy {
+        fn simple_name(&self) -> String {
+            String::from("SubjectPolicy")
+        }
+        fn simple_check(&self, _context: &UnprotectedContext, _reason: Reason<'_>) -> bool {
+            true
+        }
+        fn simple_join_direct(&mut self, _other: &mut Self) {}
+        fn simple_subjects(&self) -> Vec<String> {
+            vec![String::from("alice")]
+        }
+    }
+
+    fn data(max_epsilon: f64) -> Vec<PCon<f64, AggregatePolicy>> {
+        (1..=100)
+            .map(|i| PCon::new(i as f64, AggregatePolicy::new(max_epsilon, 0.0)))
+            .collect()
+    }
+
+    #[test]
+    fn aggregate_release() {
+        // Very large epsilon, so noise is negligible.
+        let accountant = PrivacyAccountant::new(1e7, 0.0);
+        let laplace = Mechanism::Laplace { epsilon: 1e6 };
+        let result = count(data(1e6), Context::test(()), laplace, &accountant, &["ds"]);
+        assert!((result.unwrap() - 100.0).abs() < 0.1);
+        let result = sum(
+            data(1e6),
+            Context::test(()),
+            (0.0, 50.0),
+            laplace,
+            &accountant,
+            &["ds"],
+        );
+        assert!((result.unwrap() - 3775.0).abs() < 0.1);
+        let result = mean(
+            data(1e6),
+            Context::test(()),
+            (0.0, 200.0),
+            laplace,
+            &accountant,
+            &["ds"],
+        );
+        assert!((result.unwrap() - 50.5).abs() < 0.1);
+        assert_eq!(accountant.remaining("ds").0, 1e7 - 3e6);
+    }
+
+    #[test]
+    fn aggregate_release_refused() {
+        let accountant = PrivacyAccountant::new(1.0, 0.0);
+        let laplace = Mechanism::Laplace { epsilon: 0.5 };
+
+        // Policy does not allow this epsilon.
+        let result = count(data(0.1), Context::test(()), laplace, &accountant, &["ds"]);
+        assert!(result.is_err());
+        assert_eq!(accountant.remaining("ds").0, 1.0);
+
+        // Budget runs out.
+        count(data(1.0), Context::test(()), laplace, &accountant, &["ds"]).unwrap();
+        count(data(1.0), Context::test(()), laplace, &accountant, &["ds"]).unwrap();
+        let result = count(data(1.0), Context::test(()), laplace, &accountant, &["ds"]);
+        assert!(result.is_err());
+
+        // No keys to charge.
+        let accountant = PrivacyAccountant::new(1.0, 0.0);
+        let empty: &[&str] = &[];
+        let result = count(data(1.0), Context::test(()), laplace, &accountant, empty);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn aggregate_release_charges_subjects() {
+        let accountant = PrivacyAccountant::new(1.0, 0.0);
+        let laplace = Mechanism::Laplace { epsilon: 0.5 };
+        let data = || -> Vec<PCon<f64, SubjectPolicy>> {
+            (1..=10)
+                .map(|i| PCon::new(i as f64, SubjectPolicy {}))
+                .collect()
+        };
+
+        // The subjects of the data are charged even if the caller does not name them.
+        let empty: &[&str] = &[];
+        count(data(), Context::test(()), laplace, &accountant, empty).unwrap();
+        count(data(), Context::test(()), laplace, &accountant, &["ds"]).unwrap();
+        assert_eq!(accountant.remaining("alice").0, 0.0);
+        assert_eq!(accountant.remaining("ds").0, 0.5);
+        let result = count(data(), Context::test(()), laplace, &accountant, empty);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn aggregate_release_invalid_mechanism() {
+        assert!(Mechanism::laplace(0.5).is_ok());
+        assert!(Mechanism::laplace(-1.0).is_err());
+        assert!(Mechanism::laplace(f64::INFINITY).is_err());
+        assert!(Mechanism::gaussian(0.5, 1e-5).is_ok());
+        assert!(Mechanism::gaussian(2.0, 1e-5).is_err());
+        assert!(Mechanism::gaussian(0.5, 0.0).is_err());
+        assert!(Mechanism::gaussian(0.5, 1.0).is_err());
+
+        // Invalid mechanisms are refused even by policies they would otherwise satisfy, and
+        // spend no budget.
+        let accountant = PrivacyAccountant::new(1.0, 0.0);
+        let negative = Mechanism::Laplace { epsilon: -0.5 };
+        let result = count(data(1.0), Context::test(()), negative, &accountant, &["ds"]);
+        assert!(result.is_err());
+        let nan = Mechanism::Laplace { epsilon: f64::NAN };
+        let result = count(data(1.0), Context::test(()), nan, &accountant, &["ds"]);
+        assert!(result.is_err());
+        assert_eq!(accountant.remaining("ds").0, 1.0);
+    }
+}
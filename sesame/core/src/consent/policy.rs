@@ -0,0 +1,229 @@
+use std::collections::BTreeSet;
+use std::fmt::{Debug, Formatter};
+use std::sync::Arc;
+
+use crate::consent::store::{get_consent_store, ConsentStore};
+use crate::context::UnprotectedContext;
+use crate::policy::{Reason, SimplePolicy};
+
+// Processing purposes are communicated to policies via Reason::Custom(&Purpose::new(..)).
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Purpose(String);
+impl Purpose {
+    pub fn new<S: ToString>(purpose: S) -> Self {
+        Purpose(purpose.to_string())
+    }
+    pub fn name(&self) -> &str {
+        &self.0
+    }
+}
+
+// The purpose implied by a reason, if any.
+// Built-in reasons (rendering, responding, etc) are part of providing the service and do not
+// imply a purpose that requires consent.
+pub fn purpose_of<'a>(reason: &'a Reason<'_>) -> Option<&'a str> {
+    match reason {
+        Reason::Custom(custom) => custom.downcast_ref::<Purpose>().map(Purpose::name),
+        _ => None,
+    }
+}
+
+// The store consent is recorded in, the global store (see set_consent_store) if None.
+type Store = Option<Arc<dyn ConsentStore>>;
+
+// Data about one or more subjects can only be processed for a purpose if all of them consented to
+// that purpose.
+// Data whose subject is unknown (e.g. rows of unregistered tables) gets a policy that denies
+// everything, see deny_all.
+pub struct ConsentPolicy {
+    subjects: BTreeSet<String>,
+    // The subjects grouped by the store their consent is recorded in.
+    stores: Vec<(BTreeSet<String>, Store)>,
+    deny: bool,
+}
+
+impl ConsentPolicy {
+    pub fn new<S: ToString>(subject: S) -> Self {
+        Self::from_store(subject, None)
+    }
+    pub fn with_store<S: ToString>(subject: S, store: Arc<dyn ConsentStore>) -> Self {
+        Self::from_store(subject, Some(store))
+    }
+    pub fn deny_all() -> Self {
+        Self {
+            subjects: BTreeSet::new(),
+            stores: Vec::new(),
+            deny: true,
+        }
+    }
+    pub fn subjects(&self) -> &BTreeSet<String> {
+        &self.subjects
+    }
+
+    fn from_store<S: ToString>(subject: S, store: Store) -> Self {
+        let subjects = BTreeSet::from([subject.to_string()]);
+        Self {
+            subjects: subjects.clone(),
+            stores: vec![(subjects, store)],
+            deny: false,
+        }
+    }
+}
+
+// Whether the two groups use the same store.
+fn same_store(a: &Store, b: &Store) -> bool {
+    match (a, b) {
+        (None, None) => true,
+        (Some(a), Some(b)) => {
+            std::ptr::eq(Arc::as_ptr(a).cast::<()>(), Arc::as_ptr(b).cast::<()>())
+        }
+        _ => false,
+    }
+}
+
+impl SimplePolicy for ConsentPolicy {
+    fn simple_name(&self) -> String {
+        format!("ConsentPolicy(subjects: {:?})", self.subjects)
+    }
+    fn simple_check(&self, _context: &UnprotectedContext, reason: Reason<'_>) -> bool {
+        if self.deny {
+            return false;
+        }
+        let purpose = match purpose_of(&reason) {
+            None => return true,
+            Some(purpose) => purpose,
+        };
+        self.stores.iter().all(
+            |(subjects, store)| match store.clone().or_else(get_consent_store) {
+                None => false,
+                Some(store) => subjects
+                    .iter()
+                    .all(|subject| store.has_consent(subject, purpose)),
+            },
+        )
+    }
+    // Each subject keeps being checked against its own store.
+    fn simple_join_direct(&mut self, other: &mut Self) {
+        self.subjects.append(&mut other.subjects);
+        for (mut subjects, store) in other.stores.drain(..) {
+            match self.stores.iter_mut().find(|(_, s)| same_store(s, &store)) {
+                None => self.stores.push((subjects, store)),
+                Some((existing, _)) => existing.append(&mut subjects),
+            }
+        }
+        self.deny = self.deny || other.deny;
+    }
+    fn simple_subjects(&self) -> Vec<String> {
+        self.subjects.iter().cloned().collect()
+    }
+}
+
+impl Clone for ConsentPolicy {
+    fn clone(&self) -> Self {
+        Self {
+            subjects: self.subjects.clone(),
+            stores: self.stores.clone(),
+            deny: self.deny,
+        }
+    }
+}
+impl Debug for ConsentPolicy {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ConsentPolicy")
+            .field("subjects", &self.subjects)
+            .field("deny", &self.deny)
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::consent::{ConsentPolicy, ConsentStore, InMemoryConsentStore, Purpose};
+    use crate::context::UnprotectedContext;
+    use crate::policy::{JoinAPI, Policy, Reason};
+    use std::sync::Arc;
+
+    #[test]
+    fn consent_store_history() {
+        let store = InMemoryConsentStore::new();
+        assert!(!store.has_consent("alice", "marketing"));
+        store.grant("alice", "marketing").unwrap();
+        store.grant("alice", "analytics").unwrap();
+        assert!(store.has_consent("alice", "marketing"));
+        assert!(!store.has_consent("bob", "marketing"));
+        store.revoke("alice", "marketing").unwrap();
+        assert!(!store.has_consent("alice", "marketing"));
+        assert!(store.has_consent("alice", "analytics"));
+
+        let history = store.history("alice").unwrap();
+        assert_eq!(history.len(), 3);
+        assert!(history[0].granted && !history[2].granted);
+    }
+
+    #[test]
+    fn consent_policy_check() {
+        let store = Arc::new(InMemoryConsentStore::new());
+        store.grant("alice", "marketing").unwrap();
+
+        let context = UnprotectedContext::test(());
+        let marketing = Purpose::new("marketing");
+        let analytics = Purpose::new("analytics");
+        let alice = ConsentPolicy::with_store("alice", store.clone());
+        assert!(alice.check(&context, Reason::Response));
+        assert!(alice.check(&context, Reason::Custom(&marketing)));
+        assert!(!alice.check(&context, Reason::Custom(&analytics)));
+
+        store.revoke("alice", "marketing").unwrap();
+        assert!(!alice.check(&context, Reason::Custom(&marketing)));
+    }
+
+    #[test]
+    fn consent_policy_join() {
+        let store = Arc::new(InMemoryConsentStore::new());
+        store.grant("alice", "marketing").unwrap();
+
+        let context = UnprotectedContext::test(());
+        let marketing = Purpose::new("marketing");
+        let alice = ConsentPolicy::with_store("alice", store.clone());
+        let bob = ConsentPolicy::with_store("bob", store.clone());
+        let joined = alice.join(bob);
+        assert!(!joined.check(&context, Reason::Custom(&marketing)));
+        store.grant("bob", "marketing").unwrap();
+        assert!(joined.check(&context, Reason::Custom(&marketing)));
+        assert!(joined.is::<ConsentPolicy>());
+    }
+
+    #[test]
+    fn consent_policy_join_stores() {
+        let store1 = Arc::new(InMemoryConsentStore::new());
+        let store2 = Arc::new(InMemoryConsentStore::new());
+        store1.grant("alice", "marketing").unwrap();
+        store2.grant("bob", "marketing").unwrap();
+
+        // Each subject is checked against its own store.
+        let context = UnprotectedContext::test(());
+        let marketing = Purpose::new("marketing");
+        let alice = ConsentPolicy::with_store("alice", store1.clone());
+        let bob = ConsentPolicy::with_store("bob", store2.clone());
+        let joined = alice.join(bob);
+        assert!(joined.check(&context, Reason::Custom(&marketing)));
+        store2.revoke("bob", "marketing").unwrap();
+        assert!(!joined.check(&context, Reason::Custom(&marketing)));
+    }
+
+    #[test]
+    fn consent_policy_deny_all() {
+        let store = Arc::new(InMemoryConsentStore::new());
+        store.grant("alice", "marketing").unwrap();
+
+        let context = UnprotectedContext::test(());
+        let marketing = Purpose::new("marketing");
+        let deny = ConsentPolicy::deny_all();
+        assert!(!deny.check(&context, Reason::Response));
+        assert!(!deny.check(&context, Reason::Custom(&marketing)));
+
+        let joined = ConsentPolicy::with_store("alice", store).join(deny);
+        assert!(!joined.check(&context, Reason::Response));
+        assert!(!joined.check(&context, Reason::Custom(&marketing)));
+    }
+}
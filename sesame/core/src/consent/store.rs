@@ -0,0 +1,89 @@
+use std::sync::{Arc, Mutex, RwLock};
+
+use chrono::{NaiveDateTime, Utc};
+
+use crate::policy::NotAPolicyContainer;
+
+// A single consent decision made by a data subject.
+// The history of all decisions is kept, the latest one for a purpose is in effect.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ConsentRecord {
+    pub subject: String,
+    pub purpose: String,
+    pub granted: bool,
+    pub time: NaiveDateTime,
+}
+impl ConsentRecord {
+    pub fn new(subject: &str, purpose: &str, granted: bool) -> Self {
+        Self {
+            subject: String::from(subject),
+            purpose: String::from(purpose),
+            granted,
+            time: Utc::now().naive_utc(),
+        }
+    }
+}
+
+// Where consent decisions are stored.
+pub trait ConsentStore: Send + Sync + NotAPolicyContainer {
+    // Append a decision to the history.
+    fn record(&self, record: ConsentRecord) -> Result<(), String>;
+    // All decisions made by the subject, oldest first.
+    fn history(&self, subject: &str) -> Result<Vec<ConsentRecord>, String>;
+
+    fn grant(&self, subject: &str, purpose: &str) -> Result<(), String> {
+        self.record(ConsentRecord::new(subject, purpose, true))
+    }
+    fn revoke(&self, subject: &str, purpose: &str) -> Result<(), String> {
+        self.record(ConsentRecord::new(subject, purpose, false))
+    }
+    // Whether the latest decision of the subject for this purpose is a grant.
+    // Errors are treated as no consent.
+    fn has_consent(&self, subject: &str, purpose: &str) -> bool {
+        match self.history(subject) {
+            Err(_) => false,
+            Ok(history) => history
+                .iter()
+                .rev()
+                .find(|record| record.purpose == purpose)
+                .map_or(false, |record| record.granted),
+        }
+    }
+}
+
+// Keeps the history in memory, useful for testing.
+#[derive(Default)]
+pub struct InMemoryConsentStore {
+    records: Mutex<Vec<ConsentRecord>>,
+}
+impl InMemoryConsentStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+impl ConsentStore for InMemoryConsentStore {
+    fn record(&self, record: ConsentRecord) -> Result<(), String> {
+        self.records.lock().unwrap().push(record);
+        Ok(())
+    }
+    fn history(&self, subject: &str) -> Result<Vec<ConsentRecord>, String> {
+        let records = self.records.lock().unwrap();
+        Ok(records
+            .iter()
+            .filter(|record| record.subject == subject)
+            .cloned()
+            .collect())
+    }
+}
+
+// Global static singleton, used by policies that are not given a store explicitly
+// (e.g. when constructed from DB rows).
+static CONSENT_STORE: RwLock<Option<Arc<dyn ConsentStore>>> = RwLock::new(None);
+
+// Should be called once at startup.
+pub fn set_consent_store<S: ConsentStore + 'static>(store: S) {
+    *CONSENT_STORE.write().unwrap() = Some(Arc::new(store));
+}
+pub fn get_consent_store() -> Option<Arc<dyn ConsentStore>> {
+    CONSENT_STORE.read().unwrap().clone()
+}
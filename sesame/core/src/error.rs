@@ -4,6 +4,10 @@ use std::fmt::{Debug, Display, Error as FmtError, Formatter};
 pub enum SesameError {
     PolicyCheckFailed(String),
     SesameTypeFoldFailed(String),
+    PrivacyBudgetExhausted(String),
+    InvalidPrivacyParameters(String),
+    PseudonymizationFailed(String),
+    DeclassificationFailed(String),
 }
 
 impl Display for SesameError {
@@ -12,6 +12,8 @@ extern crate sesame_derive;
 extern crate sesame_sandbox;
 
 // Export these
+pub mod aggregate;
+pub mod consent;
 pub mod context;
 pub mod critical;
 pub mod error;
@@ -20,7 +22,9 @@ pub mod fold;
 pub mod fold_in;
 pub mod pcon;
 pub mod policy;
+pub mod pseudonym;
 pub mod sandbox;
+pub mod session;
 pub mod testing;
 pub mod verified;
 
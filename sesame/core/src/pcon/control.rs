@@ -0,0 +1,109 @@
+use crate::pcon::PCon;
+use crate::policy::{AnyPolicy, AnyPolicyable, JoinAPI};
+
+// Protected branching.
+impl<T> PCon<T, AnyPolicy> {
+    // Choose a or b depending on cond.
+    // The result is protected by the join of the policies of cond and of *both* branches, so that
+    // the policy of the result does not reveal which branch was chosen.
+    pub fn select<PC: AnyPolicyable, PA: AnyPolicyable, PB: AnyPolicyable>(
+        cond: PCon<bool, PC>,
+        a: PCon<T, PA>,
+        b: PCon<T, PB>,
+    ) -> Self {
+        let (cond, pc) = cond.consume();
+        let (a, pa) = a.consume();
+        let (b, pb) = b.consume();
+        let policy = pc.join(pa).join(pb);
+        PCon::new(if cond { a } else { b }, policy)
+    }
+}
+
+// Syntax sugar for PCon::select: pcon_if!(cond => a, else => b).
+#[macro_export]
+macro_rules! pcon_if {
+    ($cond:expr => $a:expr, else => $b:expr $(,)?) => {
+        $crate::pcon::PCon::select($cond, $a, $b)
+    };
+}
+
+// Keep the elements for which predicate is true.
+// The predicate only sees protected elements and returns a protected boolean. Which elements are
+// kept depends on every predicate result, so the result is protected by the join of the policies
+// of all predicate results and of the kept elements.
+pub fn filter<T, P: AnyPolicyable, PP: AnyPolicyable, F: Fn(&PCon<T, P>) -> PCon<bool, PP>>(
+    data: Vec<PCon<T, P>>,
+    predicate: F,
+) -> PCon<Vec<T>, AnyPolicy> {
+    let mut result = Vec::new();
+    let mut policy: Option<AnyPolicy> = None;
+    let mut join = |p: AnyPolicy| {
+        policy = Some(match policy.take() {
+            None => p,
+            Some(policy) => policy.join(p),
+        });
+    };
+    for pcon in data {
+        let (keep, pp) = predicate(&pcon).consume();
+        join(AnyPolicy::new(pp));
+        if keep {
+            let (t, p) = pcon.consume();
+            join(AnyPolicy::new(p));
+            result.push(t);
+        }
+    }
+    PCon::new(result, policy.unwrap_or_default())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::context::UnprotectedContext;
+    use crate::pcon::{filter, PCon};
+    use crate::policy::rbac::{RoleContext, RolePolicy};
+    use crate::policy::{AnyPolicy, NoPolicy, Policy, Reason};
+
+    pub struct User(&'static str);
+    impl RoleContext for User {
+        fn principal(&self) -> Option<&str> {
+            Some(self.0)
+        }
+        fn roles(&self) -> Vec<&str> {
+            Vec::new()
+        }
+    }
+
+    fn owned(t: u64, owner: &str) -> PCon<u64, RolePolicy<User>> {
+        PCon::new(t, RolePolicy::with_owner(owner))
+    }
+
+    #[test]
+    fn pcon_select() {
+        let alice = UnprotectedContext::test(User("alice"));
+        let cond = PCon::new(true, RolePolicy::<User>::with_owner("alice"));
+        let result: PCon<u64, AnyPolicy> =
+            crate::pcon_if!(cond => owned(1, "alice"), else => PCon::new(2, NoPolicy {}));
+        assert!(result.policy().check(&alice, Reason::Response));
+        assert_eq!(result.consume().0, 1);
+
+        // Both branches' policies are joined.
+        let cond = PCon::new(false, NoPolicy {});
+        let result = PCon::select(cond, owned(1, "bob"), owned(2, "alice"));
+        assert!(!result.policy().check(&alice, Reason::Response));
+        assert_eq!(result.consume().0, 2);
+    }
+
+    #[test]
+    fn pcon_filter() {
+        let alice = UnprotectedContext::test(User("alice"));
+        let data = vec![owned(10, "alice"), owned(60, "alice"), owned(70, "alice")];
+        let result = filter(data, |x| x.clone().gt(50u64));
+        assert!(result.policy().check(&alice, Reason::Response));
+        assert_eq!(result.consume().0, vec![60, 70]);
+
+        // Predicates on other owners' data taint the result.
+        let data = vec![owned(10, "bob"), owned(60, "alice")];
+        let result = filter(data, |x| x.clone().gt(50u64));
+        assert!(!result.policy().check(&alice, Reason::Response));
+        assert_eq!(result.consume().0, vec![60]);
+    }
+}
@@ -1,4 +1,13 @@
+mod control;
 mod obfuscated_pointer;
+mod operators;
 mod pcon_type;
+mod string;
+mod vec;
+mod zip;
 
+pub use control::*;
+pub use operators::*;
 pub use pcon_type::*;
+pub use vec::*;
+pub use zip::*;
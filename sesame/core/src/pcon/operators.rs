@@ -0,0 +1,238 @@
+use std::ops::{Add, Mul, Not, Sub};
+use std::str::FromStr;
+
+use crate::pcon::PCon;
+use crate::policy::{AnyPolicy, AnyPolicyable, JoinAPI, Policy};
+
+// The right hand side of an operator on PCon<T, P>: either another PCon, whose policy is joined
+// with P, or a plain constant, which keeps P as is.
+pub trait PConOperand<P: Policy> {
+    type Data;
+    type Policy: Policy;
+    fn combine(self, policy: P) -> (Self::Data, Self::Policy);
+}
+
+impl<T, P: AnyPolicyable, P2: AnyPolicyable> PConOperand<P> for PCon<T, P2> {
+    type Data = T;
+    type Policy = AnyPolicy;
+    fn combine(self, policy: P) -> (T, AnyPolicy) {
+        let (t, p2) = self.consume();
+        (t, policy.join(p2))
+    }
+}
+
+macro_rules! pcon_operand_impl {
+  ($($T:ty,)+) => (
+    $(
+    impl<P: Policy> PConOperand<P> for $T {
+        type Data = $T;
+        type Policy = P;
+        fn combine(self, policy: P) -> ($T, P) {
+            (self, policy)
+        }
+    }
+    )+
+  );
+}
+pcon_operand_impl!(u8, u16, u32, u64, u128, usize,);
+pcon_operand_impl!(i8, i16, i32, i64, i128, isize,);
+pcon_operand_impl!(bool, f32, f64, char, String,);
+impl<'a, P: Policy> PConOperand<P> for &'a str {
+    type Data = &'a str;
+    type Policy = P;
+    fn combine(self, policy: P) -> (&'a str, P) {
+        (self, policy)
+    }
+}
+
+// Operators are only implemented for std types, so that they never run application code (e.g. a
+// custom PartialEq or Add impl) on protected data.
+mod private {
+    pub trait Sealed {}
+}
+// Types arithmetic operators can be applied to.
+pub trait PConNumeric: private::Sealed {}
+// Types comparisons can be applied to.
+pub trait PConPrimitive: private::Sealed {}
+// Types protected strings can be parsed into (see PCon::parse).
+pub trait PConParse: private::Sealed + FromStr {}
+// Strings that can be appended to PCon<String, _>.
+pub trait PConStr: private::Sealed {
+    fn pcon_str(&self) -> &str;
+}
+
+macro_rules! pcon_primitive_impl {
+  ($($T:ty,)+) => (
+    $(
+    impl private::Sealed for $T {}
+    impl PConPrimitive for $T {}
+    )+
+  );
+}
+macro_rules! pcon_numeric_impl {
+  ($($T:ty,)+) => (
+    pcon_primitive_impl!($($T,)+);
+    $(
+    impl PConNumeric for $T {}
+    impl PConParse for $T {}
+    )+
+  );
+}
+pcon_numeric_impl!(u8, u16, u32, u64, u128, usize,);
+pcon_numeric_impl!(i8, i16, i32, i64, i128, isize,);
+pcon_numeric_impl!(f32, f64,);
+pcon_primitive_impl!(bool, char, String,);
+impl PConParse for bool {}
+impl PConParse for char {}
+impl<'a> private::Sealed for &'a str {}
+impl<'a> PConPrimitive for &'a str {}
+
+impl PConStr for String {
+    fn pcon_str(&self) -> &str {
+        self.as_str()
+    }
+}
+impl<'a> PConStr for &'a str {
+    fn pcon_str(&self) -> &str {
+        self
+    }
+}
+
+// Arithmetic.
+macro_rules! pcon_operator_impl {
+  ($([$Op:ident, $op:ident],)+) => (
+    $(
+    impl<T, P: Policy, O: PConOperand<P>> $Op<O> for PCon<T, P>
+    where
+        T: PConNumeric + $Op<O::Data>,
+        O::Data: PConNumeric,
+    {
+        type Output = PCon<T::Output, O::Policy>;
+        fn $op(self, rhs: O) -> Self::Output {
+            let (t, p) = self.consume();
+            let (rhs, p) = rhs.combine(p);
+            PCon::new(t.$op(rhs), p)
+        }
+    }
+    )+
+  );
+}
+pcon_operator_impl!([Add, add], [Sub, sub], [Mul, mul],);
+
+impl<T: PConPrimitive + Not, P: Policy> Not for PCon<T, P> {
+    type Output = PCon<T::Output, P>;
+    fn not(self) -> Self::Output {
+        let (t, p) = self.consume();
+        PCon::new(!t, p)
+    }
+}
+
+// String concatenation.
+impl<P: Policy, O: PConOperand<P>> Add<O> for PCon<String, P>
+where
+    O::Data: PConStr,
+{
+    type Output = PCon<String, O::Policy>;
+    fn add(self, rhs: O) -> Self::Output {
+        self.concat(rhs)
+    }
+}
+
+// Comparisons produce protected booleans.
+macro_rules! pcon_comparison_impl {
+  ($([$Cmp:ident, $cmp:ident, $op:tt],)+) => (
+    $(
+    pub fn $cmp<O: PConOperand<P>>(self, other: O) -> PCon<bool, O::Policy>
+    where
+        T: $Cmp<O::Data>,
+        O::Data: PConPrimitive,
+    {
+        self.compare(other, |a, b| a $op b)
+    }
+    )+
+  );
+}
+impl<T: PConPrimitive, P: Policy> PCon<T, P> {
+    fn compare<O: PConOperand<P>, F: FnOnce(&T, &O::Data) -> bool>(
+        self,
+        other: O,
+        f: F,
+    ) -> PCon<bool, O::Policy> {
+        let (t, p) = self.consume();
+        let (other, p) = other.combine(p);
+        PCon::new(f(&t, &other), p)
+    }
+
+    pcon_comparison_impl!(
+        [PartialEq, eq, ==],
+        [PartialEq, ne, !=],
+        [PartialOrd, lt, <],
+        [PartialOrd, le, <=],
+        [PartialOrd, gt, >],
+        [PartialOrd, ge, >=],
+    );
+}
+
+// String concatenation.
+impl<P: Policy> PCon<String, P> {
+    pub fn concat<O: PConOperand<P>>(self, other: O) -> PCon<String, O::Policy>
+    where
+        O::Data: PConStr,
+    {
+        let (mut t, p) = self.consume();
+        let (other, p) = other.combine(p);
+        t.push_str(other.pcon_str());
+        PCon::new(t, p)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::context::UnprotectedContext;
+    use crate::pcon::PCon;
+    use crate::policy::{AnyPolicy, NoPolicy, Policy, Reason};
+    use crate::testing::TestPolicy;
+
+    #[test]
+    fn pcon_arithmetic() {
+        let a = PCon::new(10, TestPolicy::new(NoPolicy {}));
+        let b = PCon::new(5, NoPolicy {});
+
+        // Policies are kept with constants.
+        let c: PCon<i32, TestPolicy<NoPolicy>> = a.clone() * 2;
+        assert_eq!(c.consume().0, 20);
+
+        // Policies are joined with other PCons.
+        let d: PCon<i32, AnyPolicy> = (a.clone() + b.clone()) - 3;
+        let context = UnprotectedContext::test(());
+        assert!(d.policy().check(&context, Reason::Response));
+        assert_eq!(d.consume().0, 12);
+
+        let e = !(PCon::new(true, NoPolicy {}));
+        assert!(!e.consume().0);
+    }
+
+    #[test]
+    fn pcon_comparisons() {
+        let id = PCon::new(String::from("alice"), NoPolicy {});
+        assert!(id.clone().eq("alice").consume().0);
+        assert!(id.clone().ne(String::from("bob")).consume().0);
+
+        let grade = PCon::new(80, NoPolicy {});
+        let other = PCon::new(90, NoPolicy {});
+        let result = grade.clone().lt(other);
+        let context = UnprotectedContext::test(());
+        assert!(result.policy().check(&context, Reason::Response));
+        assert!(result.consume().0);
+        assert!(grade.clone().ge(80).consume().0);
+        assert!(!grade.gt(80).consume().0);
+    }
+
+    #[test]
+    fn pcon_concat() {
+        let first = PCon::new(String::from("Kinan"), NoPolicy {});
+        let last = PCon::new(String::from("Albab"), NoPolicy {});
+        let name = first.concat(" ").concat(last) + "!";
+        assert_eq!(name.consume().0, "Kinan Albab!");
+    }
+}
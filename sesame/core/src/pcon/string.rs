@@ -0,0 +1,138 @@
+use std::sync::OnceLock;
+
+use regex::Regex;
+use sha2::{Digest, Sha256};
+
+use crate::pcon::{PCon, PConParse};
+use crate::policy::Policy;
+
+// Vetted transformations on protected strings.
+// These live inside Sesame, so unlike verified/critical regions they need no reviewer signature.
+// Every transformation keeps the data in a PCon with the original policy.
+impl<P: Policy> PCon<String, P> {
+    // Parse into a std numeric, bool or char type, so no application FromStr runs on the data.
+    // Both success and failure stay protected by the same policy.
+    pub fn parse<T: PConParse>(self) -> Result<PCon<T, P>, PCon<T::Err, P>> {
+        let (t, p) = self.consume();
+        match t.parse::<T>() {
+            Ok(t) => Ok(PCon::new(t, p)),
+            Err(e) => Err(PCon::new(e, p)),
+        }
+    }
+
+    // Whitespace and case.
+    pub fn trim(self) -> PCon<String, P> {
+        self.map_string(|s| s.trim().to_string())
+    }
+    pub fn to_lowercase(self) -> PCon<String, P> {
+        self.map_string(|s| s.to_lowercase())
+    }
+    pub fn to_uppercase(self) -> PCon<String, P> {
+        self.map_string(|s| s.to_uppercase())
+    }
+
+    // The number of parts depends on the data, so the parts are returned inside a single PCon.
+    pub fn split(self, separator: &str) -> PCon<Vec<String>, P> {
+        let (t, p) = self.consume();
+        PCon::new(t.split(separator).map(String::from).collect(), p)
+    }
+
+    // Regex matching.
+    pub fn is_match(&self, regex: &Regex) -> PCon<bool, P>
+    where
+        P: Clone,
+    {
+        PCon::new(regex.is_match(self.data()), self.policy().clone())
+    }
+
+    // Hex-encoded SHA-256 digest.
+    // Hashes of low-entropy data (e.g. emails) can be reversed by brute force, so the digest keeps
+    // the policy of the input.
+    pub fn sha256(self) -> PCon<String, P> {
+        self.map_string(|s| hex::encode(Sha256::digest(s.as_bytes())))
+    }
+
+    // Validation.
+    pub fn is_email(&self) -> PCon<bool, P>
+    where
+        P: Clone,
+    {
+        static EMAIL: OnceLock<Regex> = OnceLock::new();
+        let regex = EMAIL.get_or_init(|| {
+            Regex::new(r"^[A-Za-z0-9.!#$%&'*+/=?^_`{|}~-]+@[A-Za-z0-9](?:[A-Za-z0-9-]{0,61}[A-Za-z0-9])?(?:\.[A-Za-z0-9](?:[A-Za-z0-9-]{0,61}[A-Za-z0-9])?)+$").unwrap()
+        });
+        self.is_match(regex)
+    }
+    pub fn is_url(&self) -> PCon<bool, P>
+    where
+        P: Clone,
+    {
+        static URL: OnceLock<Regex> = OnceLock::new();
+        let regex = URL.get_or_init(|| {
+            Regex::new(r"^https?://(?:[A-Za-z0-9](?:[A-Za-z0-9-]{0,61}[A-Za-z0-9])?\.)*[A-Za-z0-9](?:[A-Za-z0-9-]{0,61}[A-Za-z0-9])?(?::[0-9]{1,5})?(?:[/?#][^\s]*)?$").unwrap()
+        });
+        self.is_match(regex)
+    }
+
+    fn map_string<F: FnOnce(String) -> String>(self, f: F) -> PCon<String, P> {
+        let (t, p) = self.consume();
+        PCon::new(f(t), p)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::num::ParseIntError;
+
+    use regex::Regex;
+
+    use crate::pcon::PCon;
+    use crate::policy::NoPolicy;
+
+    fn pcon(s: &str) -> PCon<String, NoPolicy> {
+        PCon::new(String::from(s), NoPolicy {})
+    }
+
+    #[test]
+    fn pcon_parse() {
+        let result: Result<PCon<u64, NoPolicy>, PCon<ParseIntError, NoPolicy>> = pcon("42").parse();
+        assert_eq!(result.unwrap().consume().0, 42);
+
+        let result = pcon("abc").parse::<u64>();
+        assert!(result.is_err());
+
+        assert!(pcon("true").parse::<bool>().unwrap().consume().0);
+        assert_eq!(pcon("x").parse::<char>().unwrap().consume().0, 'x');
+    }
+
+    #[test]
+    fn pcon_transformations() {
+        assert_eq!(pcon("  Hello ").trim().consume().0, "Hello");
+        assert_eq!(pcon("Hello").to_lowercase().consume().0, "hello");
+        assert_eq!(pcon("Hello").to_uppercase().consume().0, "HELLO");
+        assert_eq!(
+            pcon("a,b,c").split(",").consume().0,
+            vec!["a".to_string(), "b".to_string(), "c".to_string()]
+        );
+        assert_eq!(
+            pcon("abc").sha256().consume().0,
+            "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"
+        );
+    }
+
+    #[test]
+    fn pcon_validation() {
+        let regex = Regex::new(r"^[0-9]+$").unwrap();
+        assert!(pcon("123").is_match(&regex).consume().0);
+        assert!(!pcon("12a").is_match(&regex).consume().0);
+
+        assert!(pcon("user@example.com").is_email().consume().0);
+        assert!(!pcon("user@").is_email().consume().0);
+        assert!(!pcon("user example.com").is_email().consume().0);
+
+        assert!(pcon("https://example.com/path?q=1").is_url().consume().0);
+        assert!(pcon("http://localhost:8000").is_url().consume().0);
+        assert!(!pcon("javascript:alert(1)").is_url().consume().0);
+        assert!(!pcon("https://exa mple.com").is_url().consume().0);
+    }
+}
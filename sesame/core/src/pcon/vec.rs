@@ -0,0 +1,409 @@
+use std::any::Any;
+use std::collections::BTreeMap;
+use std::iter::FromIterator;
+
+use crate::fold::Foldable;
+use crate::pcon::obfuscated_pointer::ObPtr;
+use crate::pcon::PCon;
+use crate::policy::{join_dyn, AnyPolicy, AnyPolicyable, PolicyDyn, PolicyDynRelation, RefPolicy};
+use crate::sesame_type::r#enum::SesameTypeEnum;
+use crate::sesame_type::r#type::{SesameType, SesameTypeOut};
+use crate::sesame_type_dyns::{SesameDyn, SesameDynRelation};
+use crate::verified::VerifiedRegion;
+
+// A collection of protected values that keeps a policy per element, unlike folding which joins
+// all of them into one.
+// Bulk operations run verified (privacy pure) functions over all elements at once.
+// The length of the collection is protected by the join of the policies of all its elements.
+// After filter_by, sort_by_key or group_by, the membership and order of elements depend on the
+// data of elements (including ones that were filtered out), so element-wise access (policies, get,
+// iter and into_vec) is no longer available, and the length and folded values are also protected by
+// the policies of all the elements that decided them.
+pub struct PConVec<T, P: AnyPolicyable> {
+    values: ObPtr<Vec<T>>,
+    policies: Vec<P>,
+    // Policies of the elements whose data decided membership or order, None if nothing did.
+    shape: Option<Vec<P>>,
+}
+
+// Join the given policies, or the default policy if there are none.
+fn join_all<P: AnyPolicyable, PDyn: PolicyDyn + ?Sized + PolicyDynRelation<P>>(
+    policies: impl IntoIterator<Item = P>,
+) -> AnyPolicy<PDyn> {
+    policies
+        .into_iter()
+        .map(AnyPolicy::<PDyn>::new)
+        .reduce(join_dyn)
+        .unwrap_or_default()
+}
+
+impl<T, P: AnyPolicyable> PConVec<T, P> {
+    pub fn new() -> Self {
+        Self {
+            values: ObPtr::new(Vec::new()),
+            policies: Vec::new(),
+            shape: None,
+        }
+    }
+
+    fn from_parts(values: Vec<T>, policies: Vec<P>, shape: Option<Vec<P>>) -> Self {
+        Self {
+            values: ObPtr::new(values),
+            policies,
+            shape,
+        }
+    }
+    fn into_parts(self) -> (Vec<T>, Vec<P>, Option<Vec<P>>) {
+        (self.values.mov(), self.policies, self.shape)
+    }
+
+    // Whether membership or order of elements depend on their data.
+    pub fn is_data_dependent(&self) -> bool {
+        self.shape.is_some()
+    }
+
+    // None once membership or order depend on data.
+    pub fn policies(&self) -> Option<&Vec<P>> {
+        match self.shape {
+            None => Some(&self.policies),
+            Some(_) => None,
+        }
+    }
+
+    pub fn push(&mut self, pcon: PCon<T, P>) {
+        let (t, p) = pcon.consume();
+        self.values.get_mut().push(t);
+        self.policies.push(p);
+    }
+    // None once membership or order depend on data.
+    pub fn get(&self, index: usize) -> Option<PCon<&T, RefPolicy<'_, P>>> {
+        if self.shape.is_some() {
+            return None;
+        }
+        let t = self.values.get().get(index)?;
+        Some(PCon::new(t, RefPolicy::new(&self.policies[index])))
+    }
+    // None once membership or order depend on data.
+    pub fn iter(&self) -> Option<impl Iterator<Item = PCon<&T, RefPolicy<'_, P>>>> {
+        if self.shape.is_some() {
+            return None;
+        }
+        Some(
+            self.values
+                .get()
+                .iter()
+                .zip(self.policies.iter())
+                .map(|(t, p)| PCon::new(t, RefPolicy::new(p))),
+        )
+    }
+    // Err(self) once membership or order depend on data, use into_folded instead.
+    pub fn into_vec(self) -> Result<Vec<PCon<T, P>>, Self> {
+        if self.shape.is_some() {
+            return Err(self);
+        }
+        let (values, policies, _) = self.into_parts();
+        Ok(values
+            .into_iter()
+            .zip(policies)
+            .map(|(t, p)| PCon::new(t, p))
+            .collect())
+    }
+
+    // Apply f to every element, keeping its policy.
+    pub fn map<O, F: Fn(&T) -> O>(self, f: VerifiedRegion<F>) -> PConVec<O, P> {
+        let f = f.get_functor();
+        let (values, policies, shape) = self.into_parts();
+        PConVec::from_parts(values.iter().map(f).collect(), policies, shape)
+    }
+
+    // The elements in [offset, offset + limit), for pagination.
+    pub fn page(self, offset: usize, limit: usize) -> Self {
+        let (values, policies, shape) = self.into_parts();
+        let (values, policies) = values
+            .into_iter()
+            .zip(policies)
+            .skip(offset)
+            .take(limit)
+            .unzip();
+        Self::from_parts(values, policies, shape)
+    }
+
+    // Fold into a single PCon protected by the join of all the policies, including the ones that
+    // decided membership or order.
+    pub fn into_folded(self) -> PCon<Vec<T>, AnyPolicy> {
+        let (values, policies, shape) = self.into_parts();
+        let policy = join_all(policies.into_iter().chain(shape.into_iter().flatten()));
+        PCon::new(values, policy)
+    }
+}
+
+impl<T, P: AnyPolicyable + Clone> PConVec<T, P> {
+    // The policies that protect the shape (length and order) of this collection.
+    fn shape_policies(&self) -> impl Iterator<Item = P> + '_ {
+        self.policies
+            .iter()
+            .chain(self.shape.iter().flatten())
+            .cloned()
+    }
+    // The policies that decide membership or order after a data dependent operation.
+    fn new_shape(&self) -> Option<Vec<P>> {
+        Some(self.shape_policies().collect())
+    }
+
+    pub fn len(&self) -> PCon<usize, AnyPolicy> {
+        PCon::new(self.policies.len(), join_all(self.shape_policies()))
+    }
+    pub fn is_empty(&self) -> PCon<bool, AnyPolicy> {
+        PCon::new(self.policies.is_empty(), join_all(self.shape_policies()))
+    }
+
+    // Keep the elements for which f is true.
+    pub fn filter_by<F: Fn(&T) -> bool>(self, f: VerifiedRegion<F>) -> Self {
+        let f = f.get_functor();
+        let shape = self.new_shape();
+        let (values, policies, _) = self.into_parts();
+        let (values, policies) = values
+            .into_iter()
+            .zip(policies)
+            .filter(|(t, _)| f(t))
+            .unzip();
+        Self::from_parts(values, policies, shape)
+    }
+
+    // Stable sort by the key computed by f, keys never leave the verified region.
+    pub fn sort_by_key<K: Ord, F: Fn(&T) -> K>(self, f: VerifiedRegion<F>) -> Self {
+        let f = f.get_functor();
+        let shape = self.new_shape();
+        let (values, policies, _) = self.into_parts();
+        let mut elements: Vec<(T, P)> = values.into_iter().zip(policies).collect();
+        elements.sort_by_key(|(t, _)| f(t));
+        let (values, policies) = elements.into_iter().unzip();
+        Self::from_parts(values, policies, shape)
+    }
+
+    // Group elements by the key computed by f.
+    // Each key is protected by the join of the policies of the elements in its group, the size
+    // of each group (its len) by the policies of all the elements.
+    pub fn group_by<K: Ord, F: Fn(&T) -> K>(
+        self,
+        f: VerifiedRegion<F>,
+    ) -> Vec<(PCon<K, AnyPolicy>, PConVec<T, P>)> {
+        let f = f.get_functor();
+        let shape = self.new_shape();
+        let (values, policies, _) = self.into_parts();
+        let mut groups: BTreeMap<K, (Vec<T>, Vec<P>)> = BTreeMap::new();
+        for (t, p) in values.into_iter().zip(policies) {
+            let group = groups
+                .entry(f(&t))
+                .or_insert_with(|| (Vec::new(), Vec::new()));
+            group.0.push(t);
+            group.1.push(p);
+        }
+        groups
+            .into_iter()
+            .map(|(k, (values, policies))| {
+                let key = PCon::new(k, join_all(policies.iter().cloned()));
+                (key, PConVec::from_parts(values, policies, shape.clone()))
+            })
+            .collect()
+    }
+}
+
+impl<T, P: AnyPolicyable> Default for PConVec<T, P> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+impl<T: Clone, P: AnyPolicyable + Clone> Clone for PConVec<T, P> {
+    fn clone(&self) -> Self {
+        Self {
+            values: self.values.clone(),
+            policies: self.policies.clone(),
+            shape: self.shape.clone(),
+        }
+    }
+}
+impl<T, P: AnyPolicyable> From<Vec<PCon<T, P>>> for PConVec<T, P> {
+    fn from(vec: Vec<PCon<T, P>>) -> Self {
+        let (values, policies) = vec.into_iter().map(PCon::consume).unzip();
+        Self::from_parts(values, policies, None)
+    }
+}
+impl<T, P: AnyPolicyable> FromIterator<PCon<T, P>> for PConVec<T, P> {
+    fn from_iter<I: IntoIterator<Item = PCon<T, P>>>(iter: I) -> Self {
+        let (values, policies) = iter.into_iter().map(PCon::consume).unzip();
+        Self::from_parts(values, policies, None)
+    }
+}
+
+// Implement SesameType for PConVec<T, P> like Vec<PCon<T, P>>.
+// The policies that decided membership or order are joined into the policy of the first element,
+// so folding a data dependent PConVec that is empty and nested in another SesameType cannot
+// protect its emptiness, fold the PConVec itself (or use into_folded) instead.
+#[doc = "Library implementation of SesameTypeOut. Do not copy this docstring!"]
+impl<T: Any, P: AnyPolicyable> SesameTypeOut for PConVec<T, P> {
+    type Out = Vec<T>;
+}
+#[doc = "Library implementation of SesameType. Do not copy this docstring!"]
+impl<
+        T: Any,
+        DT: SesameDyn + ?Sized + SesameDynRelation<T> + Any,
+        P: AnyPolicyable,
+        PT: PolicyDyn + ?Sized + PolicyDynRelation<P>,
+    > SesameType<DT, PT> for PConVec<T, P>
+{
+    fn to_enum(self) -> SesameTypeEnum<DT, PT> {
+        let (values, policies, shape) = self.into_parts();
+        let mut shape = shape.map(join_all::<P, PT>);
+        let elements = values
+            .into_iter()
+            .zip(policies)
+            .map(|(t, p)| {
+                let p = match shape.take() {
+                    None => AnyPolicy::new(p),
+                    Some(shape) => join_dyn(AnyPolicy::new(p), shape),
+                };
+                SesameTypeEnum::PCon(PCon::new(DT::boxed_dyn(t), p))
+            })
+            .collect();
+        SesameTypeEnum::Vec(elements)
+    }
+    fn from_enum(e: SesameTypeEnum<DT, PT>) -> Result<Self, ()> {
+        Ok(Self::from(Vec::<PCon<T, P>>::from_enum(e)?))
+    }
+    fn out_from_enum(e: SesameTypeEnum<DT, PT>) -> Result<Self::Out, ()> {
+        Vec::<PCon<T, P>>::out_from_enum(e)
+    }
+}
+
+// Folding a PConVec directly also protects the result with the policies that decided membership
+// or order.
+impl<T: Any, P: AnyPolicyable, PDyn: PolicyDyn + ?Sized + PolicyDynRelation<P>> Foldable<PDyn>
+    for PConVec<T, P>
+{
+    fn unsafe_fold(self) -> Result<(Self::Out, AnyPolicy<PDyn>), ()> {
+        let (values, policies, shape) = self.into_parts();
+        let policy = join_all(policies.into_iter().chain(shape.into_iter().flatten()));
+        Ok((values, policy))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::context::UnprotectedContext;
+    use crate::fold::fold;
+    use crate::pcon::{PCon, PConVec};
+    use crate::policy::rbac::{RoleContext, RolePolicy};
+    use crate::policy::{AnyPolicy, Policy, Reason};
+    use crate::verified::VerifiedRegion;
+
+    pub struct User(&'static str);
+    impl RoleContext for User {
+        fn principal(&self) -> Option<&str> {
+            Some(self.0)
+        }
+        fn roles(&self) -> Vec<&str> {
+            Vec::new()
+        }
+    }
+
+    // (time, sender, text) chats owned by their sender.
+    fn chats() -> PConVec<(u64, String, String), RolePolicy<User>> {
+        vec![(3, "alice", "c"), (1, "bob", "a"), (2, "alice", "b")]
+            .into_iter()
+            .map(|(time, sender, text)| {
+                PCon::new(
+                    (time, String::from(sender), String::from(text)),
+                    RolePolicy::with_owner(sender),
+                )
+            })
+            .collect()
+    }
+
+    #[test]
+    fn pconvec_keeps_policies() {
+        let alice = UnprotectedContext::test(User("alice"));
+        let chats = chats().map(VerifiedRegion::new(|chat: &(u64, String, String)| {
+            chat.2.clone()
+        }));
+        assert_eq!(chats.policies().unwrap().len(), 3);
+        assert!(!chats.len().policy().check(&alice, Reason::Response));
+        assert!(chats
+            .get(0)
+            .unwrap()
+            .policy()
+            .check(&alice, Reason::Response));
+        assert!(!chats
+            .get(1)
+            .unwrap()
+            .policy()
+            .check(&alice, Reason::Response));
+
+        let page = chats.page(2, 1);
+        assert!(page
+            .iter()
+            .unwrap()
+            .all(|c| c.policy().check(&alice, Reason::Response)));
+        assert!(page.len().policy().check(&alice, Reason::Response));
+        let page = page.into_folded();
+        assert!(page.policy().check(&alice, Reason::Response));
+        assert_eq!(page.consume().0, vec!["b"]);
+    }
+
+    #[test]
+    fn pconvec_sort_hides_order() {
+        let alice = UnprotectedContext::test(User("alice"));
+        let chats = chats()
+            .sort_by_key(VerifiedRegion::new(|chat: &(u64, String, String)| chat.0))
+            .map(VerifiedRegion::new(|chat: &(u64, String, String)| {
+                chat.2.clone()
+            }));
+        assert!(chats.is_data_dependent());
+        assert!(chats.policies().is_none());
+        assert!(chats.get(0).is_none());
+        assert!(chats.iter().is_none());
+
+        // Bob's chat decided the position of alice's chats.
+        let page = chats.page(1, 2);
+        assert!(!page.len().policy().check(&alice, Reason::Response));
+        let page = page.into_folded();
+        assert!(!page.policy().check(&alice, Reason::Response));
+        assert_eq!(page.consume().0, vec!["b", "c"]);
+    }
+
+    #[test]
+    fn pconvec_filter_and_group() {
+        let alice = UnprotectedContext::test(User("alice"));
+        let filtered = chats().filter_by(VerifiedRegion::new(|chat: &(u64, String, String)| {
+            chat.1 == "alice"
+        }));
+        let len = filtered.len();
+        assert!(!len.policy().check(&alice, Reason::Response));
+        assert_eq!(len.consume().0, 2);
+        let filtered = match filtered.into_vec() {
+            Ok(_) => panic!("filtered PConVec must not be split into elements"),
+            Err(filtered) => filtered,
+        };
+        let folded: PCon<_, AnyPolicy> = fold(filtered).unwrap();
+        assert!(!folded.policy().check(&alice, Reason::Response));
+
+        // Filtering everything out still protects the empty result.
+        let empty = chats().filter_by(VerifiedRegion::new(|chat: &(u64, String, String)| {
+            chat.0 > 3
+        }));
+        assert!(!empty.is_empty().policy().check(&alice, Reason::Response));
+        let folded: PCon<_, AnyPolicy> = fold(empty).unwrap();
+        assert!(!folded.policy().check(&alice, Reason::Response));
+
+        let groups = chats().group_by(VerifiedRegion::new(|chat: &(u64, String, String)| {
+            chat.1.clone()
+        }));
+        assert_eq!(groups.len(), 2);
+        assert!(groups[0].0.policy().check(&alice, Reason::Response));
+        assert!(!groups[1].0.policy().check(&alice, Reason::Response));
+        assert!(groups[0].1.policies().is_none());
+        let size = groups[0].1.len();
+        assert!(!size.policy().check(&alice, Reason::Response));
+        assert_eq!(size.consume().0, 2);
+    }
+}
@@ -0,0 +1,158 @@
+use crate::pcon::PCon;
+use crate::policy::{Policy, PolicyAnd, RefPolicy};
+
+// Combine PCons into a PCon of a tuple, keeping the concrete type of all the policies.
+impl<A, P1: Policy> PCon<A, P1> {
+    pub fn zip<B, P2: Policy>(self, other: PCon<B, P2>) -> PCon<(A, B), PolicyAnd<P1, P2>> {
+        let (a, p1) = self.consume();
+        let (b, p2) = other.consume();
+        PCon::new((a, b), PolicyAnd::new(p1, p2))
+    }
+}
+
+// zip3 to zip8 nest their policies to the left, e.g. PolicyAnd<PolicyAnd<P1, P2>, P3>.
+macro_rules! zip_impl {
+  ($name:ident, $Policy:ty, [$A0:ident, $P0:ident, $a0:ident], $([$A:ident, $P:ident, $a:ident]),+) => (
+    #[allow(clippy::too_many_arguments)]
+    pub fn $name<$A0, $P0: Policy, $($A, $P: Policy,)+>(
+        $a0: PCon<$A0, $P0>,
+        $($a: PCon<$A, $P>,)+
+    ) -> PCon<($A0, $($A,)+), $Policy> {
+        let ($a0, policy) = $a0.consume();
+        $(
+        let ($a, p) = $a.consume();
+        let policy = PolicyAnd::new(policy, p);
+        )+
+        PCon::new(($a0, $($a,)+), policy)
+    }
+  );
+}
+zip_impl!(
+    zip3,
+    PolicyAnd<PolicyAnd<P1, P2>, P3>,
+    [A1, P1, a1],
+    [A2, P2, a2],
+    [A3, P3, a3]
+);
+zip_impl!(
+    zip4,
+    PolicyAnd<PolicyAnd<PolicyAnd<P1, P2>, P3>, P4>,
+    [A1, P1, a1],
+    [A2, P2, a2],
+    [A3, P3, a3],
+    [A4, P4, a4]
+);
+zip_impl!(
+    zip5,
+    PolicyAnd<PolicyAnd<PolicyAnd<PolicyAnd<P1, P2>, P3>, P4>, P5>,
+    [A1, P1, a1],
+    [A2, P2, a2],
+    [A3, P3, a3],
+    [A4, P4, a4],
+    [A5, P5, a5]
+);
+zip_impl!(
+    zip6,
+    PolicyAnd<PolicyAnd<PolicyAnd<PolicyAnd<PolicyAnd<P1, P2>, P3>, P4>, P5>, P6>,
+    [A1, P1, a1],
+    [A2, P2, a2],
+    [A3, P3, a3],
+    [A4, P4, a4],
+    [A5, P5, a5],
+    [A6, P6, a6]
+);
+zip_impl!(
+    zip7,
+    PolicyAnd<PolicyAnd<PolicyAnd<PolicyAnd<PolicyAnd<PolicyAnd<P1, P2>, P3>, P4>, P5>, P6>, P7>,
+    [A1, P1, a1],
+    [A2, P2, a2],
+    [A3, P3, a3],
+    [A4, P4, a4],
+    [A5, P5, a5],
+    [A6, P6, a6],
+    [A7, P7, a7]
+);
+zip_impl!(
+    zip8,
+    PolicyAnd<
+        PolicyAnd<
+            PolicyAnd<PolicyAnd<PolicyAnd<PolicyAnd<PolicyAnd<P1, P2>, P3>, P4>, P5>, P6>,
+            P7,
+        >,
+        P8,
+    >,
+    [A1, P1, a1],
+    [A2, P2, a2],
+    [A3, P3, a3],
+    [A4, P4, a4],
+    [A5, P5, a5],
+    [A6, P6, a6],
+    [A7, P7, a7],
+    [A8, P8, a8]
+);
+
+// Splitting a PCon of a tuple gives every part the policy of the whole tuple, which is only
+// possible without cloning for references to policies.
+type RefPCon<'a, T, P> = PCon<T, RefPolicy<'a, P>>;
+
+impl<'a, A, B, P: Policy> PCon<(A, B), RefPolicy<'a, P>> {
+    pub fn unzip(self) -> (RefPCon<'a, A, P>, RefPCon<'a, B, P>) {
+        let ((a, b), p) = self.consume();
+        let p = p.policy();
+        (
+            PCon::new(a, RefPolicy::new(p)),
+            PCon::new(b, RefPolicy::new(p)),
+        )
+    }
+}
+impl<'a, 'r, A, B, P: Policy> PCon<&'r (A, B), RefPolicy<'a, P>> {
+    pub fn unzip(self) -> (RefPCon<'a, &'r A, P>, RefPCon<'a, &'r B, P>) {
+        let ((a, b), p) = self.consume();
+        let p = p.policy();
+        (
+            PCon::new(a, RefPolicy::new(p)),
+            PCon::new(b, RefPolicy::new(p)),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::context::UnprotectedContext;
+    use crate::pcon::{zip3, PCon};
+    use crate::policy::{NoPolicy, Policy, PolicyAnd, Reason};
+    use crate::testing::TestPolicy;
+    use crate::verified::VerifiedRegion;
+
+    #[test]
+    fn pcon_zip() {
+        let name = PCon::new(String::from("kinan"), TestPolicy::new(NoPolicy {}));
+        let age = PCon::new(30u64, NoPolicy {});
+        let zipped: PCon<(String, u64), PolicyAnd<TestPolicy<NoPolicy>, NoPolicy>> = name.zip(age);
+
+        let description = zipped.verified(VerifiedRegion::new(|(name, age): &(String, u64)| {
+            format!("{} ({})", name, age)
+        }));
+        let context = UnprotectedContext::test(());
+        assert!(description.policy().check(&context, Reason::Response));
+        assert_eq!(description.consume().0, "kinan (30)");
+
+        let (name, age) = zipped.as_ref().unzip();
+        assert_eq!(name.consume().0, "kinan");
+        assert_eq!(*age.consume().0, 30);
+    }
+
+    #[test]
+    fn pcon_zip3() {
+        let zipped = zip3(
+            PCon::new(1, NoPolicy {}),
+            PCon::new("a", NoPolicy {}),
+            PCon::new(true, TestPolicy::new(NoPolicy {})),
+        );
+        let _: &PolicyAnd<PolicyAnd<NoPolicy, NoPolicy>, TestPolicy<NoPolicy>> = zipped.policy();
+        let sum = zipped.into_verified(VerifiedRegion::new(|(a, b, c): (i32, &str, bool)| {
+            a + b.len() as i32 + c as i32
+        }));
+        assert_eq!(sum.consume().0, 3);
+    }
+}
@@ -0,0 +1,257 @@
+use std::any::Any;
+use std::collections::BTreeSet;
+use std::fmt::{Debug, Formatter};
+use std::marker::PhantomData;
+
+use serde::{Deserialize, Serialize};
+
+use crate::context::UnprotectedContext;
+use crate::critical::CriticalRegion;
+use crate::error::{SesameError, SesameResult};
+use crate::pcon::PCon;
+use crate::policy::{Reason, SimplePolicy};
+
+// Confidentiality levels, ordered from least to most sensitive.
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Debug, Serialize, Deserialize)]
+pub enum Level {
+    Public,
+    Internal,
+    Confidential,
+    Restricted,
+}
+
+// A point in the security lattice: a level and a set of compartments.
+// Labels are used both to label data and as the clearance of a principal.
+#[derive(Clone, PartialEq, Eq, Hash, Debug, Serialize, Deserialize)]
+pub struct Label {
+    level: Level,
+    compartments: BTreeSet<String>,
+}
+impl Label {
+    pub fn new(level: Level) -> Self {
+        Self {
+            level,
+            compartments: BTreeSet::new(),
+        }
+    }
+    pub fn with_compartment<S: ToString>(mut self, compartment: S) -> Self {
+        self.compartments.insert(compartment.to_string());
+        self
+    }
+
+    pub fn level(&self) -> Level {
+        self.level
+    }
+    pub fn compartments(&self) -> &BTreeSet<String> {
+        &self.compartments
+    }
+
+    // Whether self is at least as restrictive as other (i.e. other <= self in the lattice).
+    pub fn dominates(&self, other: &Label) -> bool {
+        self.level >= other.level && self.compartments.is_superset(&other.compartments)
+    }
+
+    // Least upper bound.
+    pub fn lub(&self, other: &Label) -> Label {
+        Label {
+            level: std::cmp::max(self.level, other.level),
+            compartments: self
+                .compartments
+                .union(&other.compartments)
+                .cloned()
+                .collect(),
+        }
+    }
+}
+
+// Implemented by the (folded) context data type of the application to expose the clearance of
+// the principal making the request.
+pub trait ClearanceContext: Any {
+    fn clearance(&self) -> Option<&Label>;
+}
+
+// Data labeled with a Label can only be accessed by principals whose clearance dominates it.
+// C is the type of the context data (implementing ClearanceContext) that the policy checks against.
+pub struct LatticePolicy<C: ClearanceContext> {
+    label: Label,
+    _c: PhantomData<fn() -> C>,
+}
+
+impl<C: ClearanceContext> LatticePolicy<C> {
+    pub fn new(label: Label) -> Self {
+        Self {
+            label,
+            _c: PhantomData,
+        }
+    }
+    pub fn label(&self) -> &Label {
+        &self.label
+    }
+}
+
+impl<C: ClearanceContext> SimplePolicy for LatticePolicy<C> {
+    fn simple_name(&self) -> String {
+        format!("LatticePolicy(label: {:?})", self.label)
+    }
+    fn simple_check(&self, context: &UnprotectedContext, _reason: Reason<'_>) -> bool {
+        match context.downcast_ref::<C>().and_then(C::clearance) {
+            None => self.label.level == Level::Public && self.label.compartments.is_empty(),
+            Some(clearance) => clearance.dominates(&self.label),
+        }
+    }
+    // The join is the least upper bound, so joining never stacks policies.
+    fn simple_join_direct(&mut self, other: &mut Self) {
+        self.label = self.label.lub(&other.label);
+    }
+}
+
+impl<C: ClearanceContext> Clone for LatticePolicy<C> {
+    fn clone(&self) -> Self {
+        Self::new(self.label.clone())
+    }
+}
+impl<C: ClearanceContext> PartialEq for LatticePolicy<C> {
+    fn eq(&self, other: &Self) -> bool {
+        self.label == other.label
+    }
+}
+impl<C: ClearanceContext> Eq for LatticePolicy<C> {}
+impl<C: ClearanceContext> Debug for LatticePolicy<C> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("LatticePolicy")
+            .field("label", &self.label)
+            .finish()
+    }
+}
+
+// Declassification changes the label of the data, and thus must be signed by a reviewer.
+// The critical region is given the current label and returns the new one, which must be lower
+// in the lattice (dominated by the current label): declassification can never raise a label or
+// move the data to unrelated compartments.
+impl<T, C: ClearanceContext> PCon<T, LatticePolicy<C>> {
+    pub fn declassify<F: FnOnce(&Label) -> Label>(
+        self,
+        functor: CriticalRegion<F>,
+    ) -> SesameResult<PCon<T, LatticePolicy<C>>> {
+        let (t, p) = self.consume();
+        let label = functor.get_functor()(&p.label);
+        if !p.label.dominates(&label) {
+            return Err(SesameError::DeclassificationFailed(format!(
+                "{:?} is not lower than {:?}",
+                label, p.label
+            )));
+        }
+        Ok(PCon::new(t, LatticePolicy::new(label)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::context::UnprotectedContext;
+    use crate::critical::{CriticalRegion, Signature};
+    use crate::pcon::PCon;
+    use crate::policy::lattice::{ClearanceContext, Label, LatticePolicy, Level};
+    use crate::policy::{JoinAPI, Policy, Reason};
+
+    pub struct User {
+        clearance: Option<Label>,
+    }
+    impl ClearanceContext for User {
+        fn clearance(&self) -> Option<&Label> {
+            self.clearance.as_ref()
+        }
+    }
+    fn user(clearance: Option<Label>) -> UnprotectedContext {
+        UnprotectedContext::test(User { clearance })
+    }
+
+    #[test]
+    fn lattice_policy_check() {
+        let policy =
+            LatticePolicy::<User>::new(Label::new(Level::Confidential).with_compartment("hr"));
+
+        let anonymous = user(None);
+        let internal = user(Some(Label::new(Level::Internal).with_compartment("hr")));
+        let restricted = user(Some(Label::new(Level::Restricted)));
+        let hr = user(Some(Label::new(Level::Restricted).with_compartment("hr")));
+        assert!(!policy.check(&anonymous, Reason::Response));
+        assert!(!policy.check(&internal, Reason::Response));
+        assert!(!policy.check(&restricted, Reason::Response));
+        assert!(policy.check(&hr, Reason::Response));
+
+        let public = LatticePolicy::<User>::new(Label::new(Level::Public));
+        assert!(public.check(&anonymous, Reason::Response));
+    }
+
+    #[test]
+    fn lattice_policy_join() {
+        let policy1 =
+            LatticePolicy::<User>::new(Label::new(Level::Internal).with_compartment("hr"));
+        let policy2 =
+            LatticePolicy::<User>::new(Label::new(Level::Confidential).with_compartment("legal"));
+        let joined = policy1.join(policy2);
+
+        // Joining reflexively does not stack policies.
+        let joined = joined.specialize_top_ref::<LatticePolicy<User>>().unwrap();
+        let expected = Label::new(Level::Confidential)
+            .with_compartment("hr")
+            .with_compartment("legal");
+        assert_eq!(joined.label(), &expected);
+    }
+
+    #[test]
+    fn lattice_policy_declassify() {
+        let pcon = PCon::new(
+            10,
+            LatticePolicy::<User>::new(Label::new(Level::Restricted).with_compartment("hr")),
+        );
+        let pcon = pcon
+            .declassify(CriticalRegion::new(
+                |label: &Label| {
+                    assert_eq!(label.level(), Level::Restricted);
+                    Label::new(Level::Internal)
+                },
+                Signature {
+                    username: "test",
+                    signature: "test",
+                },
+            ))
+            .unwrap();
+
+        let internal = user(Some(Label::new(Level::Internal)));
+        assert!(pcon.policy().check(&internal, Reason::Response));
+    }
+
+    #[test]
+    fn lattice_policy_declassify_only_lowers() {
+        let signature = || Signature {
+            username: "test",
+            signature: "test",
+        };
+        let label = Label::new(Level::Confidential).with_compartment("hr");
+
+        // Raising the level.
+        let pcon = PCon::new(10, LatticePolicy::<User>::new(label.clone()));
+        let result = pcon.declassify(CriticalRegion::new(
+            |_: &Label| Label::new(Level::Restricted).with_compartment("hr"),
+            signature(),
+        ));
+        assert!(result.is_err());
+
+        // Moving to an unrelated compartment.
+        let pcon = PCon::new(10, LatticePolicy::<User>::new(label.clone()));
+        let result = pcon.declassify(CriticalRegion::new(
+            |_: &Label| Label::new(Level::Internal).with_compartment("legal"),
+            signature(),
+        ));
+        assert!(result.is_err());
+
+        // Keeping the same label is allowed.
+        let pcon = PCon::new(10, LatticePolicy::<User>::new(label.clone()));
+        let result = pcon.declassify(CriticalRegion::new(
+            |label: &Label| label.clone(),
+            signature(),
+        ));
+        assert_eq!(result.unwrap().policy().label(), &label);
+    }
+}
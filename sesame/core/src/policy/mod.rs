@@ -4,6 +4,9 @@ mod policy;
 mod reflection;
 mod specialization;
 
+pub mod lattice;
+pub mod rbac;
+
 pub use conjunction::*;
 pub use policies::*;
 pub use policy::*;
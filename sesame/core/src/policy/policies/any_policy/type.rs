@@ -99,6 +99,9 @@ impl<P: PolicyDyn + ?Sized> Policy for AnyPolicy<P> {
     fn check(&self, context: &UnprotectedContext, reason: Reason) -> bool {
         self.policy.upcast_pref().check(context, reason)
     }
+    fn subjects(&self) -> Vec<String> {
+        self.policy.upcast_pref().subjects()
+    }
 }
 
 // AnyPolicyDyn is Clone if it obligates trait object to be Clone as well.
@@ -23,4 +23,10 @@ impl<P: Policy> Policy for OptionPolicy<P> {
             Self::Policy(p) => p.check(context, reason),
         }
     }
+    fn subjects(&self) -> Vec<String> {
+        match self {
+            Self::NoPolicy => Vec::new(),
+            Self::Policy(p) => p.subjects(),
+        }
+    }
 }
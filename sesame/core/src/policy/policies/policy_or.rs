@@ -38,4 +38,9 @@ impl<P1: Policy, P2: Policy> Policy for PolicyOr<P1, P2> {
     fn check(&self, context: &UnprotectedContext, reason: Reason) -> bool {
         self.p1.check(context, reason.clone()) || self.p2.check(context, reason)
     }
+    fn subjects(&self) -> Vec<String> {
+        let mut subjects = self.p1.subjects();
+        subjects.extend(self.p2.subjects());
+        subjects
+    }
 }
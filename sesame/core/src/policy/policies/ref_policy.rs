@@ -30,6 +30,9 @@ impl<'a, P: Policy + ?Sized> Policy for RefPolicy<'a, P> {
     fn check(&self, context: &UnprotectedContext, reason: Reason) -> bool {
         self.policy.check(context, reason)
     }
+    fn subjects(&self) -> Vec<String> {
+        self.policy.subjects()
+    }
 }
 
 // Upcast to a ref object.
@@ -11,6 +11,9 @@ pub enum Reason<'i> {
     TemplateRender(&'i str),                          // Template name/path.
     Cookie(&'i str),                                  // Cookie name.
     Redirect(&'i str),                                // Redirect path (before substitution).
+    Session(&'i str),                                 // Session cookie name.
+    Stream(&'i str),                                  // Streamed message (e.g. WebSocket), path.
+    FileWrite(&'i str),                               // File path written to.
     Response,                                         // Returning a response.
     Custom(&'i dyn Any),                              // Custom operation (via unbox(..)).
 }
@@ -20,6 +23,12 @@ pub trait Policy: Send + Sync + Reflective + UpgradableToAny + Join {
     fn name(&self) -> String;
     // Policy check function!
     fn check(&self, context: &UnprotectedContext, reason: Reason<'_>) -> bool;
+    // The data subjects (e.g. user ids) the protected data is about, if the policy knows them.
+    // Used to find all the data of a subject (e.g. for subject access and erasure requests), and
+    // to count the distinct subjects contributing to grouped releases.
+    fn subjects(&self) -> Vec<String> {
+        Vec::new()
+    }
 }
 
 // Simplified policy interface that application code can implement.
@@ -29,6 +38,9 @@ pub trait SimplePolicy: Send + Sync + Any + NotAPolicyContainer {
     fn simple_name(&self) -> String;
     fn simple_check(&self, context: &UnprotectedContext, reason: Reason<'_>) -> bool;
     fn simple_join_direct(&mut self, other: &mut Self);
+    fn simple_subjects(&self) -> Vec<String> {
+        Vec::new()
+    }
 }
 
 // Every SimplePolicy is automatically a Policy that can be joined with instances of the same
@@ -40,6 +52,9 @@ impl<P: SimplePolicy> Policy for P {
     fn check(&self, context: &UnprotectedContext, reason: Reason<'_>) -> bool {
         self.simple_check(context, reason)
     }
+    fn subjects(&self) -> Vec<String> {
+        self.simple_subjects()
+    }
 }
 
 #[cfg(test)]
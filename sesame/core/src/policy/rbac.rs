@@ -0,0 +1,181 @@
+use std::any::Any;
+use std::collections::HashSet;
+use std::fmt::{Debug, Formatter};
+use std::marker::PhantomData;
+
+use crate::context::UnprotectedContext;
+use crate::policy::{Reason, SimplePolicy};
+
+// Implemented by the (folded) context data type of the application to expose who is making the
+// request and which roles they have.
+// Note that policies see the folded (Out) type of the ContextData, which is where this trait
+// should be implemented.
+pub trait RoleContext: Any {
+    // The ID of the principal making the request (e.g. user id), if any.
+    fn principal(&self) -> Option<&str>;
+    // The roles the principal has.
+    fn roles(&self) -> Vec<&str>;
+}
+
+// Allows access to principals that either have one of the allowed roles, or are one of the owners.
+// C is the type of the context data (implementing RoleContext) that the policy checks against.
+pub struct RolePolicy<C: RoleContext> {
+    roles: HashSet<String>,
+    owners: HashSet<String>,
+    _c: PhantomData<fn() -> C>,
+}
+
+impl<C: RoleContext> RolePolicy<C> {
+    pub fn new(roles: HashSet<String>, owners: HashSet<String>) -> Self {
+        Self {
+            roles,
+            owners,
+            _c: PhantomData,
+        }
+    }
+    // Only principals with one of the given roles are allowed.
+    pub fn with_roles<S: ToString>(roles: &[S]) -> Self {
+        Self::new(roles.iter().map(S::to_string).collect(), HashSet::new())
+    }
+    // Only the given owner is allowed.
+    pub fn with_owner<S: ToString>(owner: S) -> Self {
+        Self::new(HashSet::new(), HashSet::from([owner.to_string()]))
+    }
+    // Builder-style additions.
+    pub fn allow_role<S: ToString>(mut self, role: S) -> Self {
+        self.roles.insert(role.to_string());
+        self
+    }
+    pub fn allow_owner<S: ToString>(mut self, owner: S) -> Self {
+        self.owners.insert(owner.to_string());
+        self
+    }
+
+    pub fn roles(&self) -> &HashSet<String> {
+        &self.roles
+    }
+    pub fn owners(&self) -> &HashSet<String> {
+        &self.owners
+    }
+
+    // Check directly against a RoleContext.
+    pub fn allows(&self, context: &C) -> bool {
+        if let Some(principal) = context.principal() {
+            if self.owners.contains(principal) {
+                return true;
+            }
+        }
+        context
+            .roles()
+            .iter()
+            .any(|role| self.roles.contains(*role))
+    }
+}
+
+impl<C: RoleContext> SimplePolicy for RolePolicy<C> {
+    fn simple_name(&self) -> String {
+        format!(
+            "RolePolicy(roles: {:?}, owners: {:?})",
+            self.roles, self.owners
+        )
+    }
+    fn simple_check(&self, context: &UnprotectedContext, _reason: Reason<'_>) -> bool {
+        match context.downcast_ref::<C>() {
+            None => false,
+            Some(context) => self.allows(context),
+        }
+    }
+    // Joined data is only accessible by roles allowed by both policies, and by the owner only
+    // if both policies agree on it.
+    fn simple_join_direct(&mut self, other: &mut Self) {
+        self.roles = self.roles.intersection(&other.roles).cloned().collect();
+        self.owners = self.owners.intersection(&other.owners).cloned().collect();
+    }
+    fn simple_subjects(&self) -> Vec<String> {
+        self.owners.iter().cloned().collect()
+    }
+}
+
+impl<C: RoleContext> Clone for RolePolicy<C> {
+    fn clone(&self) -> Self {
+        Self::new(self.roles.clone(), self.owners.clone())
+    }
+}
+impl<C: RoleContext> PartialEq for RolePolicy<C> {
+    fn eq(&self, other: &Self) -> bool {
+        self.roles == other.roles && self.owners == other.owners
+    }
+}
+impl<C: RoleContext> Eq for RolePolicy<C> {}
+impl<C: RoleContext> Debug for RolePolicy<C> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("RolePolicy")
+            .field("roles", &self.roles)
+            .field("owners", &self.owners)
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::context::UnprotectedContext;
+    use crate::policy::rbac::{RoleContext, RolePolicy};
+    use crate::policy::{JoinAPI, Policy, Reason};
+
+    pub struct User {
+        id: String,
+        roles: Vec<String>,
+    }
+    impl User {
+        fn new(id: &str, roles: &[&str]) -> Self {
+            Self {
+                id: String::from(id),
+                roles: roles.iter().map(|r| String::from(*r)).collect(),
+            }
+        }
+    }
+    impl RoleContext for User {
+        fn principal(&self) -> Option<&str> {
+            Some(&self.id)
+        }
+        fn roles(&self) -> Vec<&str> {
+            self.roles.iter().map(String::as_str).collect()
+        }
+    }
+
+    #[test]
+    fn role_policy_check() {
+        let policy = RolePolicy::<User>::with_roles(&["admin", "staff"]).allow_owner("alice");
+
+        let alice = UnprotectedContext::test(User::new("alice", &[]));
+        let bob = UnprotectedContext::test(User::new("bob", &["student"]));
+        let admin = UnprotectedContext::test(User::new("carl", &["student", "admin"]));
+        assert!(policy.check(&alice, Reason::Response));
+        assert!(!policy.check(&bob, Reason::Response));
+        assert!(policy.check(&admin, Reason::Response));
+
+        // Wrong context type.
+        let other = UnprotectedContext::test(String::from("alice"));
+        assert!(!policy.check(&other, Reason::Response));
+    }
+
+    #[test]
+    fn role_policy_join() {
+        let policy1 = RolePolicy::<User>::with_roles(&["admin", "staff"]).allow_owner("alice");
+        let policy2 = RolePolicy::<User>::with_roles(&["admin"]).allow_owner("bob");
+        let joined = policy1.join(policy2);
+
+        let alice = UnprotectedContext::test(User::new("alice", &[]));
+        let bob = UnprotectedContext::test(User::new("bob", &[]));
+        let staff = UnprotectedContext::test(User::new("carl", &["staff"]));
+        let admin = UnprotectedContext::test(User::new("dan", &["admin"]));
+        assert!(!joined.check(&alice, Reason::Response));
+        assert!(!joined.check(&bob, Reason::Response));
+        assert!(!joined.check(&staff, Reason::Response));
+        assert!(joined.check(&admin, Reason::Response));
+
+        // Joining reflexively does not stack policies.
+        let specialized = joined.specialize_top_ref::<RolePolicy<User>>();
+        assert!(specialized.is_ok());
+    }
+}
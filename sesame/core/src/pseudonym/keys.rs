@@ -0,0 +1,67 @@
+use std::collections::BTreeMap;
+use std::sync::RwLock;
+
+use crate::error::{SesameError, SesameResult};
+
+// Source of the secret keys used for keyed hashing.
+// Keys are identified by an id, so that old pseudonyms can still be recomputed after rotation.
+pub trait PseudonymKeys: Send + Sync {
+    // The key new pseudonyms are computed with.
+    fn current_key(&self) -> SesameResult<(u32, Vec<u8>)>;
+    // Look up an older key by its id.
+    fn key(&self, id: u32) -> SesameResult<Vec<u8>>;
+}
+
+// In-memory key ring, the most recently added key is the current one.
+pub struct KeyRing {
+    keys: RwLock<BTreeMap<u32, Vec<u8>>>,
+}
+
+impl KeyRing {
+    pub fn new(key: Vec<u8>) -> Self {
+        let mut keys = BTreeMap::new();
+        keys.insert(0, key);
+        Self {
+            keys: RwLock::new(keys),
+        }
+    }
+
+    // Read a hex encoded key from the given environment variable.
+    pub fn from_env(variable: &str) -> SesameResult<Self> {
+        let key = std::env::var(variable).map_err(|_| {
+            SesameError::PseudonymizationFailed(format!("missing key variable {}", variable))
+        })?;
+        let key = hex::decode(key.trim()).map_err(|_| {
+            SesameError::PseudonymizationFailed(format!("bad key in variable {}", variable))
+        })?;
+        Ok(Self::new(key))
+    }
+
+    // Add key and make it the current key, returns its id.
+    pub fn rotate(&self, key: Vec<u8>) -> u32 {
+        let mut keys = self.keys.write().unwrap();
+        let id = keys.keys().next_back().map_or(0, |id| id + 1);
+        keys.insert(id, key);
+        id
+    }
+}
+
+impl PseudonymKeys for KeyRing {
+    fn current_key(&self) -> SesameResult<(u32, Vec<u8>)> {
+        let keys = self.keys.read().unwrap();
+        match keys.iter().next_back() {
+            Some((id, key)) => Ok((*id, key.clone())),
+            None => Err(SesameError::PseudonymizationFailed(String::from("no keys"))),
+        }
+    }
+    fn key(&self, id: u32) -> SesameResult<Vec<u8>> {
+        let keys = self.keys.read().unwrap();
+        match keys.get(&id) {
+            Some(key) => Ok(key.clone()),
+            None => Err(SesameError::PseudonymizationFailed(format!(
+                "unknown key {}",
+                id
+            ))),
+        }
+    }
+}
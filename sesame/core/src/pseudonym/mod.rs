@@ -0,0 +1,9 @@
+mod keys;
+mod policy;
+mod pseudonymizer;
+mod vault;
+
+pub use keys::*;
+pub use policy::*;
+pub use pseudonymizer::*;
+pub use vault::*;
@@ -0,0 +1,46 @@
+use crate::context::UnprotectedContext;
+use crate::policy::{Reason, SimplePolicy};
+
+// Pseudonymization is communicated to the policy of the original data via
+// Reason::Custom(&Pseudonymization { .. }).
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Pseudonymization {
+    pub key_id: u32,
+}
+
+// Revealing a tokenized value from a TokenVault is communicated to the policy of the original
+// data via Reason::Custom(&Detokenization).
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Detokenization;
+
+// Helper for policies that opt into having their data pseudonymized.
+pub fn allows_pseudonymization(reason: &Reason<'_>) -> bool {
+    match reason {
+        Reason::Custom(custom) => custom.is::<Pseudonymization>(),
+        _ => false,
+    }
+}
+
+// Helper for policies that opt into having their data revealed from a TokenVault.
+pub fn allows_detokenization(reason: &Reason<'_>) -> bool {
+    match reason {
+        Reason::Custom(custom) => custom.is::<Detokenization>(),
+        _ => false,
+    }
+}
+
+// Policy of pseudonyms and tokens.
+// A pseudonym cannot be linked back to the original data without the key (or the vault), so it
+// can be used anywhere, e.g. as a stable identifier in logs, analytics, or API keys.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PseudonymPolicy {}
+
+impl SimplePolicy for PseudonymPolicy {
+    fn simple_name(&self) -> String {
+        String::from("PseudonymPolicy")
+    }
+    fn simple_check(&self, _context: &UnprotectedContext, _reason: Reason<'_>) -> bool {
+        true
+    }
+    fn simple_join_direct(&mut self, _other: &mut Self) {}
+}
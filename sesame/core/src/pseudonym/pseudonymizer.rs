@@ -0,0 +1,152 @@
+use hmac::{Hmac, Mac};
+use sha2::Sha256;
+
+use crate::context::{Context, ContextData, UnprotectedContext};
+use crate::error::{SesameError, SesameResult};
+use crate::pcon::PCon;
+use crate::policy::{Policy, Reason};
+use crate::pseudonym::{PseudonymKeys, PseudonymPolicy, Pseudonymization};
+
+// HMAC-SHA256 of data under key, separated by domain so pseudonyms and tokens never collide.
+pub(crate) fn keyed_hash(key: &[u8], domain: &str, data: &str) -> String {
+    let mut mac = Hmac::<Sha256>::new_from_slice(key).unwrap();
+    mac.update(domain.as_bytes());
+    mac.update(&[0]);
+    mac.update(data.as_bytes());
+    hex::encode(mac.finalize().into_bytes())
+}
+
+// Computes stable pseudonyms of protected strings using a keyed hash.
+// The same input and key always give the same pseudonym, so pseudonyms can be joined and counted,
+// but they cannot be reversed without the key.
+pub struct Pseudonymizer {
+    keys: Box<dyn PseudonymKeys>,
+}
+
+impl Pseudonymizer {
+    pub fn new<K: PseudonymKeys + 'static>(keys: K) -> Self {
+        Self {
+            keys: Box::new(keys),
+        }
+    }
+
+    // Pseudonymize with the current key.
+    pub fn pseudonymize<D: ContextData, P: Policy>(
+        &self,
+        data: PCon<String, P>,
+        context: Context<D>,
+    ) -> SesameResult<PCon<String, PseudonymPolicy>> {
+        let (id, key) = self.keys.current_key()?;
+        self.apply(data, context, id, &key)
+    }
+
+    // Pseudonymize with an older key, e.g. to look up pseudonyms computed before a rotation.
+    pub fn pseudonymize_with_key<D: ContextData, P: Policy>(
+        &self,
+        data: PCon<String, P>,
+        context: Context<D>,
+        key_id: u32,
+    ) -> SesameResult<PCon<String, PseudonymPolicy>> {
+        let key = self.keys.key(key_id)?;
+        self.apply(data, context, key_id, &key)
+    }
+
+    // The policy of the data must allow pseudonymization, after which the pseudonym is only
+    // protected by the weaker PseudonymPolicy.
+    fn apply<D: ContextData, P: Policy>(
+        &self,
+        data: PCon<String, P>,
+        context: Context<D>,
+        key_id: u32,
+        key: &[u8],
+    ) -> SesameResult<PCon<String, PseudonymPolicy>> {
+        let (t, p) = data.consume();
+        let context = UnprotectedContext::from(context);
+        if !p.check(&context, Reason::Custom(&Pseudonymization { key_id })) {
+            return Err(SesameError::PolicyCheckFailed(p.name()));
+        }
+        Ok(PCon::new(
+            keyed_hash(key, "pseudonym", &t),
+            PseudonymPolicy {},
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::context::{Context, UnprotectedContext};
+    use crate::pcon::PCon;
+    use crate::policy::{NoPolicy, Reason, SimplePolicy};
+    use crate::pseudonym::{allows_pseudonymization, KeyRing, Pseudonymizer};
+
+    #[derive(Clone)]
+    struct EmailPolicy {}
+    impl SimplePolicy for EmailPolicy {
+        fn simple_name(&self) -> String {
+            String::from("EmailPolicy")
+        }
+        fn simple_check(&self, _context: &UnprotectedContext, reason: Reason<'_>) -> bool {
+            allows_pseudonymization(&reason)
+        }
+        fn simple_join_direct(&mut self, _other: &mut Self) {}
+    }
+
+    fn email() -> PCon<String, EmailPolicy> {
+        PCon::new(String::from("alice@example.com"), EmailPolicy {})
+    }
+
+    #[test]
+    fn pseudonyms_are_stable() {
+        let pseudonymizer = Pseudonymizer::new(KeyRing::new(b"secret".to_vec()));
+        let a = pseudonymizer
+            .pseudonymize(email(), Context::test(()))
+            .unwrap();
+        let b = pseudonymizer
+            .pseudonymize(email(), Context::test(()))
+            .unwrap();
+        let (a, _) = a.consume();
+        assert_eq!(a, b.consume().0);
+        assert_eq!(a.len(), 64);
+        assert!(!a.contains("alice"));
+
+        // Rotating changes pseudonyms, but old ones can be recomputed.
+        let other = PCon::new(String::from("bob@example.com"), NoPolicy {});
+        let other = pseudonymizer
+            .pseudonymize(other, Context::test(()))
+            .unwrap();
+        assert_ne!(a, other.consume().0);
+
+        let keys = KeyRing::new(b"secret".to_vec());
+        assert_eq!(keys.rotate(b"new secret".to_vec()), 1);
+        let pseudonymizer = Pseudonymizer::new(keys);
+        let c = pseudonymizer
+            .pseudonymize(email(), Context::test(()))
+            .unwrap();
+        assert_ne!(a, c.consume().0);
+        let d = pseudonymizer
+            .pseudonymize_with_key(email(), Context::test(()), 0)
+            .unwrap();
+        assert_eq!(a, d.consume().0);
+        let e = pseudonymizer.pseudonymize_with_key(email(), Context::test(()), 5);
+        assert!(e.is_err());
+    }
+
+    #[test]
+    fn pseudonymization_is_checked() {
+        #[derive(Clone)]
+        struct SecretPolicy {}
+        impl SimplePolicy for SecretPolicy {
+            fn simple_name(&self) -> String {
+                String::from("SecretPolicy")
+            }
+            fn simple_check(&self, _context: &UnprotectedContext, _reason: Reason<'_>) -> bool {
+                false
+            }
+            fn simple_join_direct(&mut self, _other: &mut Self) {}
+        }
+
+        let pseudonymizer = Pseudonymizer::new(KeyRing::new(b"secret".to_vec()));
+        let data = PCon::new(String::from("secret"), SecretPolicy {});
+        assert!(pseudonymizer.pseudonymize(data, Context::test(())).is_err());
+    }
+}
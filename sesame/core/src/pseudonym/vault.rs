@@ -0,0 +1,155 @@
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+use crate::context::{Context, ContextData, UnprotectedContext};
+use crate::critical::CriticalRegion;
+use crate::error::{SesameError, SesameResult};
+use crate::pcon::PCon;
+use crate::policy::{AnyPolicy, AnyPolicyable, JoinAPI, Policy, Reason};
+use crate::pseudonym::pseudonymizer::keyed_hash;
+use crate::pseudonym::{Detokenization, PseudonymKeys, PseudonymPolicy, Pseudonymization};
+
+// Deterministic tokenization with a reversible vault.
+// Tokens are keyed hashes, so the same value always gets the same token. The vault remembers the
+// original value and its policy, and only reveals it through a signed critical region that the
+// original policy allows.
+pub struct TokenVault {
+    keys: Box<dyn PseudonymKeys>,
+    entries: Mutex<HashMap<String, (String, AnyPolicy)>>,
+}
+
+impl TokenVault {
+    pub fn new<K: PseudonymKeys + 'static>(keys: K) -> Self {
+        Self {
+            keys: Box::new(keys),
+            entries: Mutex::new(HashMap::new()),
+        }
+    }
+
+    // Replace data with a token, the policy of the data must allow pseudonymization.
+    pub fn tokenize<D: ContextData, P: AnyPolicyable>(
+        &self,
+        data: PCon<String, P>,
+        context: Context<D>,
+    ) -> SesameResult<PCon<String, PseudonymPolicy>> {
+        let (key_id, key) = self.keys.current_key()?;
+        let (t, p) = data.consume();
+        let context = UnprotectedContext::from(context);
+        if !p.check(&context, Reason::Custom(&Pseudonymization { key_id })) {
+            return Err(SesameError::PolicyCheckFailed(p.name()));
+        }
+
+        let token = format!("tok_{}_{}", key_id, &keyed_hash(&key, "token", &t)[..32]);
+        let mut entries = self.entries.lock().unwrap();
+        // The same value may have been tokenized under different policies, revealing it must
+        // satisfy all of them.
+        let policy = match entries.remove(&token) {
+            None => AnyPolicy::new(p),
+            Some((_, old)) => old.join(p),
+        };
+        entries.insert(token.clone(), (t, policy));
+        Ok(PCon::new(token, PseudonymPolicy {}))
+    }
+
+    // Reveal the value behind token to functor, if the policy of the value allows it.
+    pub fn reveal<D: ContextData, PT: Policy, O, F: FnOnce(String) -> O>(
+        &self,
+        token: &PCon<String, PT>,
+        context: Context<D>,
+        functor: CriticalRegion<F>,
+    ) -> SesameResult<O> {
+        let entries = self.entries.lock().unwrap();
+        let (value, policy) = match entries.get(token.data()) {
+            Some(entry) => entry,
+            None => {
+                return Err(SesameError::PseudonymizationFailed(String::from(
+                    "unknown token",
+                )))
+            }
+        };
+        let context = UnprotectedContext::from(context);
+        if !policy.check(&context, Reason::Custom(&Detokenization)) {
+            return Err(SesameError::PolicyCheckFailed(policy.name()));
+        }
+        let value = value.clone();
+        drop(entries);
+        let functor = functor.get_functor();
+        Ok(functor(value))
+    }
+
+    // Remove the value behind token from the vault, making the token irreversible.
+    pub fn forget<PT: Policy>(&self, token: &PCon<String, PT>) -> bool {
+        self.entries.lock().unwrap().remove(token.data()).is_some()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::context::{Context, UnprotectedContext};
+    use crate::critical::{CriticalRegion, Signature};
+    use crate::pcon::PCon;
+    use crate::policy::{Reason, SimplePolicy};
+    use crate::pseudonym::{allows_detokenization, allows_pseudonymization, KeyRing, TokenVault};
+
+    #[derive(Clone)]
+    struct CardPolicy {
+        revealable: bool,
+    }
+    impl SimplePolicy for CardPolicy {
+        fn simple_name(&self) -> String {
+            String::from("CardPolicy")
+        }
+        fn simple_check(&self, _context: &UnprotectedContext, reason: Reason<'_>) -> bool {
+            allows_pseudonymization(&reason) || (self.revealable && allows_detokenization(&reason))
+        }
+        fn simple_join_direct(&mut self, other: &mut Self) {
+            self.revealable = self.revealable && other.revealable;
+        }
+    }
+
+    fn card(revealable: bool) -> PCon<String, CardPolicy> {
+        PCon::new(String::from("4111111111111111"), CardPolicy { revealable })
+    }
+
+    fn region() -> CriticalRegion<fn(String) -> String> {
+        CriticalRegion::new(
+            |card: String| format!("**** {}", &card[12..]),
+            Signature {
+                username: "test",
+                signature: "",
+            },
+        )
+    }
+
+    #[test]
+    fn tokenize_and_reveal() {
+        let vault = TokenVault::new(KeyRing::new(b"secret".to_vec()));
+        let token = vault.tokenize(card(true), Context::test(())).unwrap();
+        assert!(token.data().starts_with("tok_0_"));
+        assert!(!token.data().contains("4111"));
+
+        // Deterministic.
+        let again = vault.tokenize(card(true), Context::test(())).unwrap();
+        assert_eq!(token.data(), again.data());
+
+        let revealed = vault.reveal(&token, Context::test(()), region()).unwrap();
+        assert_eq!(revealed, "**** 1111");
+
+        // Forgotten tokens cannot be revealed.
+        assert!(vault.forget(&token));
+        assert!(vault.reveal(&token, Context::test(()), region()).is_err());
+    }
+
+    #[test]
+    fn reveal_is_checked() {
+        let vault = TokenVault::new(KeyRing::new(b"secret".to_vec()));
+        let token = vault.tokenize(card(false), Context::test(())).unwrap();
+        assert!(vault.reveal(&token, Context::test(()), region()).is_err());
+
+        // Tokenizing the same value under a stricter policy also restricts earlier tokens.
+        let vault = TokenVault::new(KeyRing::new(b"secret".to_vec()));
+        let token = vault.tokenize(card(true), Context::test(())).unwrap();
+        vault.tokenize(card(false), Context::test(())).unwrap();
+        assert!(vault.reveal(&token, Context::test(()), region()).is_err());
+    }
+}
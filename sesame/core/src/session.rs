@@ -0,0 +1,83 @@
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+use chrono::{NaiveDateTime, Utc};
+
+use crate::policy::NotAPolicyContainer;
+
+// Server side session data, serialized after its policy allowed storing it in the session
+// (see Reason::Session).
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SessionRecord {
+    pub data: String,
+    pub expires: NaiveDateTime,
+}
+impl SessionRecord {
+    pub fn new(data: String, expires: NaiveDateTime) -> Self {
+        Self { data, expires }
+    }
+    pub fn is_expired(&self) -> bool {
+        self.expires <= Utc::now().naive_utc()
+    }
+}
+
+// Where sessions are stored, keyed by session id.
+pub trait SessionStore: Send + Sync + NotAPolicyContainer {
+    fn load(&self, id: &str) -> Result<Option<SessionRecord>, String>;
+    // Insert or replace the session.
+    fn save(&self, id: &str, record: SessionRecord) -> Result<(), String>;
+    fn delete(&self, id: &str) -> Result<(), String>;
+}
+
+// Keeps sessions in memory, they are lost on restart.
+#[derive(Default)]
+pub struct InMemorySessionStore {
+    sessions: Mutex<HashMap<String, SessionRecord>>,
+}
+impl InMemorySessionStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+impl SessionStore for InMemorySessionStore {
+    fn load(&self, id: &str) -> Result<Option<SessionRecord>, String> {
+        Ok(self.sessions.lock().unwrap().get(id).cloned())
+    }
+    fn save(&self, id: &str, record: SessionRecord) -> Result<(), String> {
+        self.sessions
+            .lock()
+            .unwrap()
+            .insert(String::from(id), record);
+        Ok(())
+    }
+    fn delete(&self, id: &str) -> Result<(), String> {
+        self.sessions.lock().unwrap().remove(id);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{Duration, Utc};
+
+    use crate::session::{InMemorySessionStore, SessionRecord, SessionStore};
+
+    #[test]
+    fn in_memory_session_store() {
+        let store = InMemorySessionStore::new();
+        let expires = Utc::now().naive_utc() + Duration::hours(1);
+        store
+            .save("a", SessionRecord::new(String::from("1"), expires))
+            .unwrap();
+        let record = store.load("a").unwrap().unwrap();
+        assert_eq!(record.data, "1");
+        assert!(!record.is_expired());
+        assert_eq!(store.load("b").unwrap(), None);
+
+        store.delete("a").unwrap();
+        assert_eq!(store.load("a").unwrap(), None);
+
+        let expired = SessionRecord::new(String::new(), Utc::now().naive_utc());
+        assert!(expired.is_expired());
+    }
+}
@@ -34,6 +34,9 @@ impl<P: Policy> Policy for TestPolicy<P> {
     fn check(&self, context: &UnprotectedContext, reason: Reason) -> bool {
         self.p.check(context, reason)
     }
+    fn subjects(&self) -> Vec<String> {
+        self.p.subjects()
+    }
 }
 
 impl<P: Policy> From<P> for TestPolicy<P> {
@@ -0,0 +1,31 @@
+extern crate proc_macro2;
+extern crate syn;
+
+use proc_macro2::Span;
+use syn::spanned::Spanned;
+use syn::{parse_quote, Attribute, LitStr, Path};
+
+pub type Error = (Span, &'static str);
+
+// Generated code refers to the frontend crate by this path, ::sesame_rocket by default.
+// Set it with #[sesame(crate = "::sesame_axum")] (e.g. to derive the JSON traits for sesame_axum,
+// or if the dependency is renamed).
+pub fn crate_path(attrs: &[Attribute]) -> Result<Path, Error> {
+    let mut path = parse_quote!(::sesame_rocket);
+    for attr in attrs.iter().filter(|attr| attr.path().is_ident("sesame")) {
+        let result = attr.parse_nested_meta(|meta| {
+            if !meta.path.is_ident("crate") {
+                return Err(meta.error("unsupported sesame attribute"));
+            }
+            path = meta.value()?.parse::<LitStr>()?.parse()?;
+            Ok(())
+        });
+        if result.is_err() {
+            return Err((
+                attr.span(),
+                "expected #[sesame(crate = \"path::to::crate\")]",
+            ));
+        }
+    }
+    Ok(path)
+}
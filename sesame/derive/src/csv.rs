@@ -0,0 +1,70 @@
+extern crate proc_macro2;
+extern crate quote;
+extern crate syn;
+
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{Data, DeriveInput, Fields};
+
+use crate::crate_path::{crate_path, Error};
+
+pub fn response_impl(input: DeriveInput) -> Result<TokenStream, Error> {
+    let krate = crate_path(&input.attrs)?;
+    let fields = match input.data {
+        Data::Struct(data) => match data.fields {
+            Fields::Named(fields) => fields.named,
+            _ => {
+                return Err((
+                    input.ident.span(),
+                    "derive(ResponsePConCsv) only works on structs with named fields",
+                ))
+            }
+        },
+        _ => {
+            return Err((
+                input.ident.span(),
+                "derive(ResponsePConCsv) only works on structs",
+            ))
+        }
+    };
+
+    // The generics of the input type.
+    let input_ident = input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    // List all fields and their types.
+    let idents: Vec<_> = fields
+        .iter()
+        .map(|field| field.ident.as_ref().unwrap())
+        .collect();
+    let names: Vec<_> = idents.iter().map(|ident| ident.to_string()).collect();
+    let types: Vec<_> = fields.iter().map(|field| &field.ty).collect();
+
+    // Generate implementation.
+    // Columns of nested fields are flattened and prefixed with the field name, e.g. "user.name".
+    Ok(quote! {
+        #[automatically_derived]
+        impl #impl_generics #krate::rocket::ResponsePConCsv for #input_ident #ty_generics #where_clause {
+            fn csv_columns() -> ::std::vec::Vec<::std::string::String> {
+                let mut __columns = ::std::vec::Vec::new();
+                #(
+                    for __column in <#types as #krate::rocket::ResponsePConCsv>::csv_columns() {
+                        if __column.is_empty() {
+                            __columns.push(::std::string::String::from(#names));
+                        } else {
+                            __columns.push(::std::format!("{}.{}", #names, __column));
+                        }
+                    }
+                )*
+                __columns
+            }
+            fn to_csv_cells(self) -> ::std::vec::Vec<#krate::rocket::OutputPConValue> {
+                let mut __cells = ::std::vec::Vec::new();
+                #(
+                    __cells.extend(#krate::rocket::ResponsePConCsv::to_csv_cells(self.#idents));
+                )*
+                __cells
+            }
+        }
+    })
+}
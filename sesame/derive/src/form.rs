@@ -3,14 +3,16 @@ extern crate quote;
 extern crate syn;
 
 use proc_macro2::{Ident, Span, TokenStream};
-use quote::quote;
+use quote::{quote, quote_spanned};
 use syn::punctuated::Punctuated;
 use syn::token::Comma;
 use syn::{
     Data, DataStruct, DeriveInput, Field, Fields, GenericParam, Generics, Lifetime, LifetimeParam,
-    Token, Type,
+    Path, Token, Type,
 };
 
+use crate::crate_path::crate_path;
+
 pub fn context_generics(mut generics: Generics) -> Generics {
     let mut r_bound = Punctuated::new();
     r_bound.push(Lifetime::new("'__a", Span::call_site()));
@@ -42,13 +44,13 @@ pub fn context_generics(mut generics: Generics) -> Generics {
     generics
 }
 
-pub fn cast_field_types(fields: &Punctuated<Field, Comma>) -> Vec<TokenStream> {
+pub fn cast_field_types(fields: &Punctuated<Field, Comma>, krate: &Path) -> Vec<TokenStream> {
     fields
         .iter()
         .map(|field| {
             let field_type = &field.ty;
             quote! {
-              <#field_type as ::sesame_rocket::rocket::FromPConForm<'__a, '__r>>
+              <#field_type as #krate::rocket::FromPConForm<'__a, '__r>>
             }
         })
         .collect()
@@ -156,6 +158,12 @@ pub fn generate_finalize_cases(fields: &Vec<Ident>, types: &Vec<TokenStream>) ->
 }
 
 pub fn derive_from_pcon_form_impl(input: DeriveInput) -> TokenStream {
+    // path of the frontend crate.
+    let krate = match crate_path(&input.attrs) {
+        Ok(krate) => krate,
+        Err((span, err)) => return quote_spanned!(span => compile_error!(#err)),
+    };
+
     // struct name we are deriving for.
     let input_name = input.ident;
 
@@ -173,7 +181,7 @@ pub fn derive_from_pcon_form_impl(input: DeriveInput) -> TokenStream {
         .iter()
         .map(|field| field.ident.clone().unwrap())
         .collect();
-    let casted_fields_types = cast_field_types(&fields);
+    let casted_fields_types = cast_field_types(&fields, &krate);
 
     // The context struct type def.
     let context_generics = context_generics(input.generics.clone());
@@ -196,7 +204,7 @@ pub fn derive_from_pcon_form_impl(input: DeriveInput) -> TokenStream {
         #context
 
         #[automatically_derived]
-        impl #impl_generics ::sesame_rocket::rocket::FromPConForm<'__a, '__r> for #input_name #ty_generics #where_clause {
+        impl #impl_generics #krate::rocket::FromPConForm<'__a, '__r> for #input_name #ty_generics #where_clause {
           type PConContext = FromPConFormGeneratedContext #ctx_ty_generics;
 
           // Required methods
@@ -211,7 +219,7 @@ pub fn derive_from_pcon_form_impl(input: DeriveInput) -> TokenStream {
           }
 
           // Push data for url_encoded bodies.
-          fn pcon_push_value(ctxt: &mut Self::PConContext, field: ::sesame_rocket::rocket::PConValueField<'__a>, request: ::sesame_rocket::rocket::PConRequest<'__a, '__r>) {
+          fn pcon_push_value(ctxt: &mut Self::PConContext, field: #krate::rocket::PConValueField<'__a>, request: #krate::rocket::PConRequest<'__a, '__r>) {
             ctxt.__parent = field.name.parent();
             match field.name.key_lossy().as_str() {
               #(#push_value_cases)*
@@ -227,8 +235,8 @@ pub fn derive_from_pcon_form_impl(input: DeriveInput) -> TokenStream {
           // Push data for multipart bodies.
           fn pcon_push_data<'life0, 'async_trait>(
             ctxt: &'life0 mut Self::PConContext,
-            field: ::sesame_rocket::rocket::PConDataField<'__a, '__r>,
-            request: ::sesame_rocket::rocket::PConRequest<'__a, '__r>,
+            field: #krate::rocket::PConDataField<'__a, '__r>,
+            request: #krate::rocket::PConRequest<'__a, '__r>,
           ) -> ::core::pin::Pin<Box<dyn ::core::future::Future<Output = ()> + ::core::marker::Send + 'async_trait>>
           where
             '__a: 'async_trait,
@@ -250,7 +258,7 @@ pub fn derive_from_pcon_form_impl(input: DeriveInput) -> TokenStream {
           }
 
           // Finalize.
-          fn pcon_finalize(ctxt: Self::PConContext) -> ::sesame_rocket::rocket::PConFormResult<'__a, Self> {
+          fn pcon_finalize(ctxt: Self::PConContext) -> #krate::rocket::PConFormResult<'__a, Self> {
             let mut errors = ctxt.__errors;
             let parent = ctxt.__parent;
             let opts = ctxt.__opts;
@@ -3,11 +3,11 @@ extern crate quote;
 extern crate syn;
 
 use attribute_derive::FromAttr;
-use proc_macro2::{Ident, Span, TokenStream};
+use proc_macro2::{Ident, TokenStream};
 use quote::quote;
 use syn::{Data, DeriveInput, ItemStruct};
 
-pub type Error = (Span, &'static str);
+use crate::crate_path::{crate_path, Error};
 
 // Attributes that developers can provide to customize our derive macro.
 #[derive(FromAttr)]
@@ -49,6 +49,9 @@ fn parse_derive_input_struct(input: DeriveInput) -> Result<ItemStruct, Error> {
 }
 
 pub fn request_impl(input: DeriveInput) -> Result<TokenStream, Error> {
+    // Path of the frontend crate.
+    let krate = crate_path(&input.attrs)?;
+
     // Parse the input struct.
     let input = parse_derive_input_struct(input)?;
 
@@ -66,10 +69,10 @@ pub fn request_impl(input: DeriveInput) -> Result<TokenStream, Error> {
 
     // Generate implementation.
     Ok(quote! {
-        impl #impl_generics ::sesame_rocket::rocket::RequestPConJson for #input_ident #ty_generics #where_clause {
+        impl #impl_generics #krate::frontend::RequestPConJson for #input_ident #ty_generics #where_clause {
             fn from_json(
-                mut __value: ::sesame_rocket::rocket::InputPConValue,
-                __request: ::sesame_rocket::rocket::PConRequest<'_, '_>,
+                mut __value: #krate::web::json::InputPConValue,
+                __request: #krate::frontend::PConRequest,
             ) -> Result<Self, &'static str> {
                 Ok(Self {
                     #(#fields: __value.get(#fields_strings)?.into_json(__request)?),*
@@ -82,6 +85,7 @@ pub fn request_impl(input: DeriveInput) -> Result<TokenStream, Error> {
 pub fn response_impl(input: DeriveInput) -> Result<TokenStream, Error> {
     // Parse the provided input attributes.
     let attrs = ResponsePConJsonArgs::from_attributes(&input.attrs).unwrap();
+    let krate = crate_path(&input.attrs)?;
 
     // Parse the input struct.
     let input = parse_derive_input_struct(input)?;
@@ -111,13 +115,13 @@ pub fn response_impl(input: DeriveInput) -> Result<TokenStream, Error> {
 
     // Generate implementation.
     Ok(quote! {
-        impl #impl_generics ::sesame_rocket::rocket::ResponsePConJson for #input_ident #ty_generics #where_clause {
-            fn to_json(self) -> ::sesame_rocket::rocket::OutputPConValue {
-                ::sesame_rocket::rocket::OutputPConValue::Object(HashMap::from([
+        impl #impl_generics #krate::web::json::ResponsePConJson for #input_ident #ty_generics #where_clause {
+            fn to_json(self) -> #krate::web::json::OutputPConValue {
+                #krate::web::json::OutputPConValue::Object(::std::collections::HashMap::from([
                     #((String::from(#fields_strings), self.#fields.to_json()),)*
                     #((
                         String::from(#as_is_strings),
-                        ::sesame_rocket::rocket::OutputPConValue::Value(serde_json::to_value(self.#as_is).unwrap()),
+                        #krate::web::json::OutputPConValue::Value(serde_json::to_value(self.#as_is).unwrap()),
                     )),*
                 ]))
             }
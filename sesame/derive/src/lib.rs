@@ -8,6 +8,8 @@ use syn::punctuated::Punctuated;
 use syn::token::Comma;
 use syn::{parse_macro_input, DeriveInput, Expr, ItemFn, ItemStruct};
 
+mod crate_path;
+mod csv;
 mod form;
 mod json;
 mod no_fold_in;
@@ -17,7 +19,7 @@ mod route;
 mod sandbox;
 mod sesame_type;
 
-#[proc_macro_derive(PConRender)]
+#[proc_macro_derive(PConRender, attributes(sesame))]
 pub fn derive_boxed_serialize(input: TokenStream) -> TokenStream {
     let input = parse_macro_input!(input as DeriveInput);
     render::derive_boxed_serialize_impl(input).into()
@@ -33,7 +35,7 @@ pub fn schema_policy(args: TokenStream, input: TokenStream) -> TokenStream {
     result
 }
 
-#[proc_macro_derive(FromPConForm)]
+#[proc_macro_derive(FromPConForm, attributes(sesame))]
 pub fn derive_from_pcon_form(input: TokenStream) -> TokenStream {
     let input = parse_macro_input!(input as DeriveInput);
     form::derive_from_pcon_form_impl(input).into()
@@ -109,7 +111,7 @@ pub fn derive_sandboxable(input: TokenStream) -> TokenStream {
     }
 }
 
-#[proc_macro_derive(RequestPConJson)]
+#[proc_macro_derive(RequestPConJson, attributes(sesame))]
 pub fn derive_request_pcon_json(input: TokenStream) -> TokenStream {
     let input = parse_macro_input!(input as DeriveInput);
     match json::request_impl(input) {
@@ -118,7 +120,7 @@ pub fn derive_request_pcon_json(input: TokenStream) -> TokenStream {
     }
 }
 
-#[proc_macro_derive(ResponsePConJson, attributes(response_pcon_json))]
+#[proc_macro_derive(ResponsePConJson, attributes(response_pcon_json, sesame))]
 pub fn dervie_response_pcon_json(input: TokenStream) -> TokenStream {
     let input = parse_macro_input!(input as DeriveInput);
     match json::response_impl(input) {
@@ -127,6 +129,15 @@ pub fn dervie_response_pcon_json(input: TokenStream) -> TokenStream {
     }
 }
 
+#[proc_macro_derive(ResponsePConCsv, attributes(sesame))]
+pub fn derive_response_pcon_csv(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    match csv::response_impl(input) {
+        Ok(tokens) => tokens.into(),
+        Err((span, err)) => quote_spanned!(span => compile_error!(#err)).into(),
+    }
+}
+
 #[proc_macro_derive(NoFoldIn)]
 pub fn derive_no_fold_in(input: TokenStream) -> TokenStream {
     let input = parse_macro_input!(input as DeriveInput);
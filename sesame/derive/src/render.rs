@@ -3,65 +3,74 @@ extern crate quote;
 extern crate syn;
 
 use proc_macro2::TokenStream;
-use quote::quote;
-use syn::{Data, DataEnum, DataStruct, DeriveInput, Fields, Variant};
+use quote::{quote, quote_spanned};
+use syn::{Data, DataEnum, DataStruct, DeriveInput, Fields, Path, Variant};
+
+use crate::crate_path::crate_path;
 
 pub fn derive_boxed_serialize_impl(input: DeriveInput) -> TokenStream {
+    let krate = match crate_path(&input.attrs) {
+        Ok(krate) => krate,
+        Err((span, err)) => return quote_spanned!(span => compile_error!(#err)),
+    };
     let input_name = input.ident;
     let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
 
     let body = match input.data {
-        Data::Struct(data) => derive_struct(data),
-        Data::Enum(data) => derive_enum(data),
+        Data::Struct(data) => derive_struct(data, &krate),
+        Data::Enum(data) => derive_enum(data, &krate),
         _ => panic!("PConRender can only be derived for structs and enums"),
     };
 
     quote! {
         #[automatically_derived]
-        impl #impl_generics ::sesame_rocket::render::PConRender for #input_name #ty_generics #where_clause {
-            fn render<'__impl_pcon_render>(&'__impl_pcon_render self) -> ::sesame_rocket::render::Renderable<'__impl_pcon_render> {
-                use ::sesame_rocket::render::SerializeFieldFallback as _;
+        impl #impl_generics #krate::render::PConRender for #input_name #ty_generics #where_clause {
+            fn render<'__impl_pcon_render>(&'__impl_pcon_render self) -> #krate::render::Renderable<'__impl_pcon_render> {
+                use #krate::render::SerializeFieldFallback as _;
                 #body
             }
         }
     }
 }
 
-fn derive_struct(data: DataStruct) -> TokenStream {
+fn derive_struct(data: DataStruct, krate: &Path) -> TokenStream {
     match data.fields {
         Fields::Named(fields) => {
             let puts = fields.named.into_iter().map(|field| {
                 let ident = field.ident.unwrap();
                 let name = ident.to_string();
                 quote! {
-                    map.insert(::std::string::String::from(#name), ::sesame_rocket::render::RenderFieldHelper(&self.#ident).render_field());
+                    map.insert(::std::string::String::from(#name), #krate::render::RenderFieldHelper(&self.#ident).render_field());
                 }
             });
             quote! {
-                let mut map: ::std::collections::BTreeMap<::std::string::String, ::sesame_rocket::render::Renderable<'__impl_pcon_render>> = ::std::collections::BTreeMap::new();
+                let mut map: ::std::collections::BTreeMap<::std::string::String, #krate::render::Renderable<'__impl_pcon_render>> = ::std::collections::BTreeMap::new();
                 #(#puts)*
-                ::sesame_rocket::render::Renderable::Dict(map)
+                #krate::render::Renderable::Dict(map)
             }
         }
         Fields::Unnamed(fields) => {
             let puts = fields.unnamed.into_iter().enumerate().map(|(i, _)| {
                 let index = syn::Index::from(i);
-                quote! { ::sesame_rocket::render::RenderFieldHelper(&self.#index).render_field() }
+                quote! { #krate::render::RenderFieldHelper(&self.#index).render_field() }
             });
             quote! {
-                ::sesame_rocket::render::Renderable::Array(vec![#(#puts),*])
+                #krate::render::Renderable::Array(vec![#(#puts),*])
             }
         }
         Fields::Unit => {
             quote! {
-                ::sesame_rocket::render::Renderable::Dict(::std::collections::BTreeMap::new())
+                #krate::render::Renderable::Dict(::std::collections::BTreeMap::new())
             }
         }
     }
 }
 
-fn derive_enum(data: DataEnum) -> TokenStream {
-    let arms = data.variants.into_iter().map(derive_variant);
+fn derive_enum(data: DataEnum, krate: &Path) -> TokenStream {
+    let arms = data
+        .variants
+        .into_iter()
+        .map(|variant| derive_variant(variant, krate));
     quote! {
         match self {
             #(#arms)*
@@ -69,14 +78,14 @@ fn derive_enum(data: DataEnum) -> TokenStream {
     }
 }
 
-fn derive_variant(variant: Variant) -> TokenStream {
+fn derive_variant(variant: Variant, krate: &Path) -> TokenStream {
     let variant_ident = &variant.ident;
     let variant_name = variant_ident.to_string();
 
     match variant.fields {
         Fields::Unit => {
             quote! {
-                Self::#variant_ident => ::sesame_rocket::render::Renderable::Serialize(&#variant_name),
+                Self::#variant_ident => #krate::render::Renderable::Serialize(&#variant_name),
             }
         }
         Fields::Named(fields) => {
@@ -88,39 +97,37 @@ fn derive_variant(variant: Variant) -> TokenStream {
             let names: Vec<String> = idents.iter().map(|i| i.to_string()).collect();
             quote! {
                 Self::#variant_ident { #(#idents),* } => {
-                    let mut inner: ::std::collections::BTreeMap<::std::string::String, ::sesame_rocket::render::Renderable<'__impl_pcon_render>> = ::std::collections::BTreeMap::new();
-                    #( inner.insert(::std::string::String::from(#names), ::sesame_rocket::render::RenderFieldHelper(#idents).render_field()); )*
-                    let mut outer: ::std::collections::BTreeMap<::std::string::String, ::sesame_rocket::render::Renderable<'__impl_pcon_render>> = ::std::collections::BTreeMap::new();
-                    outer.insert(::std::string::String::from(#variant_name), ::sesame_rocket::render::Renderable::Dict(inner));
-                    ::sesame_rocket::render::Renderable::Dict(outer)
+                    let mut inner: ::std::collections::BTreeMap<::std::string::String, #krate::render::Renderable<'__impl_pcon_render>> = ::std::collections::BTreeMap::new();
+                    #( inner.insert(::std::string::String::from(#names), #krate::render::RenderFieldHelper(#idents).render_field()); )*
+                    let mut outer: ::std::collections::BTreeMap<::std::string::String, #krate::render::Renderable<'__impl_pcon_render>> = ::std::collections::BTreeMap::new();
+                    outer.insert(::std::string::String::from(#variant_name), #krate::render::Renderable::Dict(inner));
+                    #krate::render::Renderable::Dict(outer)
                 },
             }
         }
         Fields::Unnamed(fields) => {
             let count = fields.unnamed.len();
             let bindings: Vec<_> = (0..count)
-                .map(|i| {
-                    syn::Ident::new(&format!("f{}", i), proc_macro2::Span::call_site())
-                })
+                .map(|i| syn::Ident::new(&format!("f{}", i), proc_macro2::Span::call_site()))
                 .collect();
 
             if count == 1 {
                 quote! {
                     Self::#variant_ident(#(#bindings),*) => {
-                        let mut outer: ::std::collections::BTreeMap<::std::string::String, ::sesame_rocket::render::Renderable<'__impl_pcon_render>> = ::std::collections::BTreeMap::new();
-                        outer.insert(::std::string::String::from(#variant_name), ::sesame_rocket::render::RenderFieldHelper(f0).render_field());
-                        ::sesame_rocket::render::Renderable::Dict(outer)
+                        let mut outer: ::std::collections::BTreeMap<::std::string::String, #krate::render::Renderable<'__impl_pcon_render>> = ::std::collections::BTreeMap::new();
+                        outer.insert(::std::string::String::from(#variant_name), #krate::render::RenderFieldHelper(f0).render_field());
+                        #krate::render::Renderable::Dict(outer)
                     },
                 }
             } else {
                 quote! {
                     Self::#variant_ident(#(#bindings),*) => {
-                        let mut outer: ::std::collections::BTreeMap<::std::string::String, ::sesame_rocket::render::Renderable<'__impl_pcon_render>> = ::std::collections::BTreeMap::new();
+                        let mut outer: ::std::collections::BTreeMap<::std::string::String, #krate::render::Renderable<'__impl_pcon_render>> = ::std::collections::BTreeMap::new();
                         outer.insert(
                             ::std::string::String::from(#variant_name),
-                            ::sesame_rocket::render::Renderable::Array(vec![#(::sesame_rocket::render::RenderFieldHelper(#bindings).render_field()),*]),
+                            #krate::render::Renderable::Array(vec![#(#krate::render::RenderFieldHelper(#bindings).render_field()),*]),
                         );
-                        ::sesame_rocket::render::Renderable::Dict(outer)
+                        #krate::render::Renderable::Dict(outer)
                     },
                 }
             }
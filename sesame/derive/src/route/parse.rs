@@ -7,7 +7,7 @@ use std::result::Result;
 
 use proc_macro2::{Ident, Span};
 use syn::parse::{Parse, ParseStream};
-use syn::{parse_str, Lit, Token};
+use syn::{parse_str, Lit, Path, Token};
 
 use rocket_http::uri::Origin;
 
@@ -234,6 +234,121 @@ impl Parse for WithDataArg {
     }
 }
 
+// require = Policy.
+struct RequireArg {
+    pub policy: Path,
+}
+impl Parse for RequireArg {
+    fn parse(input: ParseStream) -> Result<Self, syn::Error> {
+        // Parse require ident.
+        let fork = input.fork();
+        let ident: Ident = fork.parse()?;
+        if ident != "require" {
+            return Err(input.error("Expected require ="));
+        }
+        input.parse::<Ident>()?;
+
+        // Parse = token.
+        let _token: Token![=] = input.parse()?;
+
+        // Parse the policy type.
+        let policy: Path = input.parse()?;
+        Ok(RequireArg { policy })
+    }
+}
+
+// purpose = "purpose".
+struct PurposeArg {
+    pub purpose: String,
+}
+impl Parse for PurposeArg {
+    fn parse(input: ParseStream) -> Result<Self, syn::Error> {
+        // Parse purpose ident.
+        let fork = input.fork();
+        let ident: Ident = fork.parse()?;
+        if ident != "purpose" {
+            return Err(input.error("Expected purpose ="));
+        }
+        input.parse::<Ident>()?;
+
+        // Parse = token.
+        let _token: Token![=] = input.parse()?;
+
+        // Parse str literal.
+        let fork = input.fork();
+        let lit: Lit = fork.parse()?;
+        let purpose = match lit {
+            Lit::Str(lit) => lit.value(),
+            _ => {
+                return Err(input.error("Expected a literal str for purpose"));
+            }
+        };
+
+        // Done.
+        input.parse::<Lit>()?;
+        Ok(PurposeArg { purpose })
+    }
+}
+
+// status = 401.
+struct StatusArg {
+    pub status: u16,
+}
+impl Parse for StatusArg {
+    fn parse(input: ParseStream) -> Result<Self, syn::Error> {
+        // Parse status ident.
+        let fork = input.fork();
+        let ident: Ident = fork.parse()?;
+        if ident != "status" {
+            return Err(input.error("Expected status ="));
+        }
+        input.parse::<Ident>()?;
+
+        // Parse = token.
+        let _token: Token![=] = input.parse()?;
+
+        // Parse int literal.
+        let fork = input.fork();
+        let lit: Lit = fork.parse()?;
+        let status = match lit {
+            Lit::Int(lit) => lit.base10_parse::<u16>()?,
+            _ => {
+                return Err(input.error("Expected a literal integer for status"));
+            }
+        };
+        if !(100..600).contains(&status) {
+            return Err(input.error("Expected an HTTP status code"));
+        }
+
+        // Done.
+        input.parse::<Lit>()?;
+        Ok(StatusArg { status })
+    }
+}
+
+// crate = "::path::to::frontend".
+struct CrateArg {
+    pub krate: Path,
+}
+impl Parse for CrateArg {
+    fn parse(input: ParseStream) -> Result<Self, syn::Error> {
+        // Parse crate keyword.
+        let _crate: Token![crate] = input.parse()?;
+
+        // Parse = token.
+        let _token: Token![=] = input.parse()?;
+
+        // Parse the path of the frontend crate.
+        let lit: Lit = input.parse()?;
+        match lit {
+            Lit::Str(lit) => Ok(CrateArg {
+                krate: lit.parse()?,
+            }),
+            _ => Err(input.error("Expected crate = \"path\"")),
+        }
+    }
+}
+
 // Used to tell parser whether we know the method ahead of time or not.
 pub trait RouteType {
     const TYPE: &'static str;
@@ -260,6 +375,10 @@ pub struct RouteArgs<T: RouteType> {
     pub path_params: Vec<(String, usize)>,
     pub data: Option<String>,
     pub with_data: Option<String>,
+    pub require: Option<Path>,
+    pub purpose: Option<String>,
+    pub status: Option<u16>,
+    pub krate: Option<Path>,
     _t: PhantomData<T>,
 }
 impl<T: RouteType> Parse for RouteArgs<T> {
@@ -277,27 +396,47 @@ impl<T: RouteType> Parse for RouteArgs<T> {
         // Parse URI.
         let uri: RouteURI = input.parse()?;
 
-        // Parse data.
-        let data: Option<String> = if !input.is_empty() {
+        // Parse the remaining named arguments, in any order.
+        let mut data: Option<String> = Option::None;
+        let mut with_data: Option<String> = Option::None;
+        let mut require: Option<Path> = Option::None;
+        let mut purpose: Option<String> = Option::None;
+        let mut status: Option<u16> = Option::None;
+        let mut krate: Option<Path> = Option::None;
+        while !input.is_empty() {
             let _comma: Token![,] = input.parse()?;
-            let data: DataArg = input.parse()?;
-            Option::Some(data.parameter)
-        } else {
-            Option::None
-        };
-
-        // Parse with_data.
-        let with_data: Option<String> = if !input.is_empty() {
-            let _comma: Token![,] = input.parse()?;
-            let data: WithDataArg = input.parse()?;
-            Option::Some(data.parameter)
-        } else {
-            Option::None
-        };
+            // crate is a keyword, not an ident.
+            if input.peek(Token![crate]) {
+                if krate.replace(input.parse::<CrateArg>()?.krate).is_some() {
+                    return Err(input.error("Duplicate argument crate"));
+                }
+                continue;
+            }
+            let ident: Ident = input.fork().parse()?;
+            let duplicate = match ident.to_string().as_str() {
+                "data" => data.replace(input.parse::<DataArg>()?.parameter).is_some(),
+                "with_data" => with_data
+                    .replace(input.parse::<WithDataArg>()?.parameter)
+                    .is_some(),
+                "require" => require
+                    .replace(input.parse::<RequireArg>()?.policy)
+                    .is_some(),
+                "purpose" => purpose
+                    .replace(input.parse::<PurposeArg>()?.purpose)
+                    .is_some(),
+                "status" => status.replace(input.parse::<StatusArg>()?.status).is_some(),
+                _ => {
+                    return Err(input.error(format!("Unknown argument {}", ident)));
+                }
+            };
+            if duplicate {
+                return Err(input.error(format!("Duplicate argument {}", ident)));
+            }
+        }
 
-        // Should be empty here.
-        if !input.is_empty() {
-            return Err(input.error("Expected stream to be empty"));
+        // purpose and status only make sense with a required policy.
+        if require.is_none() && (purpose.is_some() || status.is_some()) {
+            return Err(input.error("purpose and status can only be used with require = <Policy>"));
         }
 
         // Return the parsed args.
@@ -308,6 +447,10 @@ impl<T: RouteType> Parse for RouteArgs<T> {
             path_params: uri.path_params,
             data,
             with_data,
+            require,
+            purpose,
+            status,
+            krate,
             _t: PhantomData,
         })
     }
@@ -7,7 +7,7 @@ use std::option::Option;
 
 use proc_macro2::{Ident, Span, TokenStream};
 use quote::quote;
-use syn::{FnArg, ItemFn, Pat, Type};
+use syn::{parse_quote, FnArg, ItemFn, Pat, Path, Type};
 
 use crate::route::{RouteArgs, RouteType};
 
@@ -65,6 +65,14 @@ struct RouteAttribute {
     pub types: HashMap<Parameter, Type>,
     // the handler's arguments in order.
     pub args: Vec<Parameter>,
+    // the policy the route requires (checked against the context before the handler runs).
+    pub require: Option<Path>,
+    // the purpose the policy is checked for.
+    pub purpose: Option<String>,
+    // the status to respond with if the check fails.
+    pub status: u16,
+    // path of the frontend crate (::sesame_rocket by default).
+    pub krate: Path,
 }
 impl RouteAttribute {
     pub fn new<T: RouteType>(args: RouteArgs<T>) -> Self {
@@ -83,6 +91,10 @@ impl RouteAttribute {
             uri: args.uri,
             types: HashMap::new(),
             args: Vec::new(),
+            require: args.require,
+            purpose: args.purpose,
+            status: args.status.unwrap_or(403),
+            krate: args.krate.unwrap_or_else(|| parse_quote!(::sesame_rocket)),
         }
     }
 
@@ -170,6 +182,7 @@ pub fn route_impl<T: RouteType>(args: RouteArgs<T>, input: ItemFn) -> TokenStrea
     let fn_name = args.func_name.as_ref().unwrap();
     let uri = &args.uri;
     let fn_call = args.call_function();
+    let krate = &args.krate;
 
     // Do Path parameters first.
     let path_params = args.path_params.iter().map(|(param, idx)| {
@@ -179,11 +192,11 @@ pub fn route_impl<T: RouteType>(args: RouteArgs<T>, input: ItemFn) -> TokenStrea
             ::std::option::Option::Some(_d) => match _d {
               ::std::result::Result::Ok(d) => d,
               ::std::result::Result::Err(_) => {
-                return ::sesame_rocket::rocket::PConResponseOutcome::Forward(_data);
+                return #krate::rocket::PConResponseOutcome::Forward(_data);
               },
             },
             ::std::option::Option::None => {
-              return ::sesame_rocket::rocket::PConResponseOutcome::Forward(_data);
+              return #krate::rocket::PConResponseOutcome::Forward(_data);
             },
           };
         }
@@ -195,13 +208,13 @@ pub fn route_impl<T: RouteType>(args: RouteArgs<T>, input: ItemFn) -> TokenStrea
       let ty = args.types.get(param).unwrap();
 
       quote! {
-        let #ident = match <#ty as ::sesame_rocket::rocket::FromPConRequest>::from_pcon_request(_request).await {
-          ::sesame_rocket::rocket::PConRequestOutcome::Success(_d) => _d,
-          ::sesame_rocket::rocket::PConRequestOutcome::Failure((_s, _e)) => {
-            return ::sesame_rocket::rocket::PConResponseOutcome::Failure(_s);
+        let #ident = match <#ty as #krate::rocket::FromPConRequest>::from_pcon_request(_request).await {
+          #krate::rocket::PConRequestOutcome::Success(_d) => _d,
+          #krate::rocket::PConRequestOutcome::Failure((_s, _e)) => {
+            return #krate::rocket::PConResponseOutcome::Failure(_s);
           },
-          ::sesame_rocket::rocket::PConRequestOutcome::Forward(_) => {
-            return ::sesame_rocket::rocket::PConResponseOutcome::Forward(_data);
+          #krate::rocket::PConRequestOutcome::Forward(_) => {
+            return #krate::rocket::PConResponseOutcome::Forward(_data);
           },
         };
       }
@@ -215,13 +228,13 @@ pub fn route_impl<T: RouteType>(args: RouteArgs<T>, input: ItemFn) -> TokenStrea
             let ident = data.to_ident();
             let data_ty = args.types.get(data).unwrap();
             let post_data = quote! {
-              let #ident = match <#data_ty as ::sesame_rocket::rocket::FromPConData>::from_data(_request, _data).await {
-                ::sesame_rocket::rocket::PConDataOutcome::Success(_d) => _d,
-                ::sesame_rocket::rocket::PConDataOutcome::Failure((_s, _e)) => {
-                  return ::sesame_rocket::rocket::PConResponseOutcome::Failure(_s);
+              let #ident = match <#data_ty as #krate::rocket::FromPConData>::from_data(_request, _data).await {
+                #krate::rocket::PConDataOutcome::Success(_d) => _d,
+                #krate::rocket::PConDataOutcome::Failure((_s, _e)) => {
+                  return #krate::rocket::PConResponseOutcome::Failure(_s);
                 },
-                ::sesame_rocket::rocket::PConDataOutcome::Forward(_f) => {
-                  return ::sesame_rocket::rocket::PConResponseOutcome::Forward(_f);
+                #krate::rocket::PConDataOutcome::Forward(_f) => {
+                  return #krate::rocket::PConResponseOutcome::Forward(_f);
                 },
               };
             };
@@ -233,12 +246,12 @@ pub fn route_impl<T: RouteType>(args: RouteArgs<T>, input: ItemFn) -> TokenStrea
                     let with_data_ident = with_data.to_ident();
                     let ty = args.types.get(with_data).unwrap();
                     quote! {
-                      let #with_data_ident = match <#ty as ::sesame_rocket::rocket::FromPConRequestAndData<#data_ty>>::from_pcon_request_and_data(_request, &#ident).await {
-                        ::sesame_rocket::rocket::PConRequestOutcome::Success(_d) => _d,
-                        ::sesame_rocket::rocket::PConRequestOutcome::Failure((_s, _e)) => {
-                          return ::sesame_rocket::rocket::PConResponseOutcome::Failure(_s);
+                      let #with_data_ident = match <#ty as #krate::rocket::FromPConRequestAndData<#data_ty>>::from_pcon_request_and_data(_request, &#ident).await {
+                        #krate::rocket::PConRequestOutcome::Success(_d) => _d,
+                        #krate::rocket::PConRequestOutcome::Failure((_s, _e)) => {
+                          return #krate::rocket::PConResponseOutcome::Failure(_s);
                         },
-                        ::sesame_rocket::rocket::PConRequestOutcome::Forward(_f) => {
+                        #krate::rocket::PConRequestOutcome::Forward(_f) => {
                           panic!("With_data member forwarded but data is consumed");
                         },
                       };
@@ -267,7 +280,7 @@ pub fn route_impl<T: RouteType>(args: RouteArgs<T>, input: ItemFn) -> TokenStrea
         .map(|param| {
             let ty = args.types.get(param).unwrap();
             quote! {
-              <#ty as ::sesame_rocket::rocket::FromPConForm>
+              <#ty as #krate::rocket::FromPConForm>
             }
         })
         .collect::<Vec<_>>();
@@ -298,7 +311,7 @@ pub fn route_impl<T: RouteType>(args: RouteArgs<T>, input: ItemFn) -> TokenStrea
 
       // handle any errors.
       if !_errors.is_empty() {
-        return ::sesame_rocket::rocket::PConResponseOutcome::Forward(_data);
+        return #krate::rocket::PConResponseOutcome::Forward(_data);
       }
       #(let #query_idents = #query_idents.unwrap();)*
     };
@@ -312,11 +325,28 @@ pub fn route_impl<T: RouteType>(args: RouteArgs<T>, input: ItemFn) -> TokenStrea
         quote! {}
     };
 
+    // Check the required policy (if any) before anything else.
+    let requirement = match args.require.as_ref() {
+        None => quote! { ::std::option::Option::None },
+        Some(policy) => {
+            let purpose = match args.purpose.as_ref() {
+                None => quote! { ::std::option::Option::None },
+                Some(purpose) => quote! { ::std::option::Option::Some(#purpose) },
+            };
+            let status = args.status;
+            quote! {
+              ::std::option::Option::Some(|request| {
+                ::std::boxed::Box::pin(#krate::rocket::check_route_policy::<#policy>(request, #purpose, #status))
+              })
+            }
+        }
+    };
+
     quote! {
       #[allow(non_camel_case_types)]
       pub struct #fn_name {}
       impl #fn_name {
-        pub async fn lambda<'a, 'r>(_request: ::sesame_rocket::rocket::PConRequest<'a, 'r>, _data: ::sesame_rocket::rocket::PConData<'a>) -> ::sesame_rocket::rocket::PConResponseOutcome<'a> {
+        pub async fn lambda<'a, 'r>(_request: #krate::rocket::PConRequest<'a, 'r>, _data: #krate::rocket::PConData<'a>) -> #krate::rocket::PConResponseOutcome<'a> {
           // Path parameters.
           #(#path_params)*
 
@@ -339,16 +369,17 @@ pub fn route_impl<T: RouteType>(args: RouteArgs<T>, input: ItemFn) -> TokenStrea
           #res_await
 
           // done!
-          ::sesame_rocket::rocket::PConResponseOutcome::from(_request, _res)
+          #krate::rocket::PConResponseOutcome::from(_request, _res)
         }
 
-        pub fn info() -> ::sesame_rocket::rocket::SesameRouteInfo {
-          ::sesame_rocket::rocket::SesameRouteInfo {
+        pub fn info() -> #krate::rocket::SesameRouteInfo {
+          #krate::rocket::SesameRouteInfo {
             method: ::rocket::http::Method::#method,
             uri: #uri,
             handler: |request, data| {
               ::std::boxed::Box::pin(Self::lambda(request, data))
             },
+            requirement: #requirement,
           }
         }
       }
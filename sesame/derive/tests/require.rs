@@ -0,0 +1,114 @@
+use sesame::consent::purpose_of;
+use sesame::context::UnprotectedContext;
+use sesame::pcon::PCon;
+use sesame::policy::{NoPolicy, Reason, SimplePolicy};
+
+use sesame_derive::{get, routes, SesameType};
+use sesame_rocket::policy::FrontendPolicy;
+use sesame_rocket::rocket::{
+    FromPConRequest, PConRequest, PConRequestOutcome, RouteAccess, RoutePolicy,
+};
+
+use rocket::http::{Cookie, Status};
+
+// Context data identifying who is making the request.
+#[derive(SesameType)]
+pub struct Viewer {
+    pub user: PCon<String, NoPolicy>,
+}
+
+#[rocket::async_trait]
+impl<'a, 'r> FromPConRequest<'a, 'r> for Viewer {
+    type PConError = ();
+    async fn from_pcon_request(
+        request: PConRequest<'a, 'r>,
+    ) -> PConRequestOutcome<Self, Self::PConError> {
+        let user = match request.cookies().get("user") {
+            Some(cookie) => cookie.value().to_owned(),
+            None => PCon::new(String::new(), NoPolicy {}),
+        };
+        PConRequestOutcome::Success(Viewer { user })
+    }
+}
+
+// Only the admin can access admin routes, or grade.
+pub struct AdminPolicy {}
+impl SimplePolicy for AdminPolicy {
+    fn simple_name(&self) -> String {
+        String::from("AdminPolicy")
+    }
+    fn simple_check(&self, context: &UnprotectedContext, reason: Reason<'_>) -> bool {
+        let viewer: &ViewerOut = context.downcast_ref().unwrap();
+        let allowed = match &reason {
+            Reason::Custom(custom) => match custom.downcast_ref::<RouteAccess>() {
+                Some(access) => access.uri.starts_with("/admin"),
+                None => purpose_of(&reason) == Some("grading"),
+            },
+            _ => false,
+        };
+        allowed && viewer.user == "admin"
+    }
+    fn simple_join_direct(&mut self, _other: &mut Self) {}
+}
+impl FrontendPolicy for AdminPolicy {
+    fn from_request(_request: &rocket::Request<'_>) -> Self {
+        AdminPolicy {}
+    }
+    fn from_cookie<'a, 'r>(
+        _name: &str,
+        _cookie: &'a Cookie<'static>,
+        _request: &'a rocket::Request<'r>,
+    ) -> Self {
+        AdminPolicy {}
+    }
+}
+impl RoutePolicy for AdminPolicy {
+    type Data = Viewer;
+}
+
+#[get("/admin", require = AdminPolicy)]
+fn admin() -> String {
+    String::from("admin page")
+}
+
+#[get("/grades/<lec>", require = AdminPolicy, purpose = "grading", status = 401)]
+fn grades(lec: PCon<u8, NoPolicy>) -> String {
+    format!("grades of {}", lec.discard_box())
+}
+
+fn client() -> sesame_rocket::testing::SesameClient {
+    let rocket = sesame_rocket::rocket::SesameRocket::<::rocket::Build>::build()
+        .mount("/", routes![admin, grades]);
+    sesame_rocket::testing::SesameClient::tracked(rocket).expect("valid `Rocket`")
+}
+
+#[test]
+fn require_allowed_test() {
+    let client = client();
+    let response = client
+        .get("/admin")
+        .cookie(Cookie::new("user", "admin"))
+        .dispatch();
+    assert_eq!(response.status(), Status::new(200));
+    assert_eq!(response.into_string().unwrap(), "admin page");
+
+    let response = client
+        .get("/grades/3")
+        .cookie(Cookie::new("user", "admin"))
+        .dispatch();
+    assert_eq!(response.status(), Status::new(200));
+    assert_eq!(response.into_string().unwrap(), "grades of 3");
+}
+
+#[test]
+fn require_denied_test() {
+    let client = client();
+    let response = client
+        .get("/admin")
+        .cookie(Cookie::new("user", "student"))
+        .dispatch();
+    assert_eq!(response.status(), Status::Forbidden);
+
+    let response = client.get("/grades/3").dispatch();
+    assert_eq!(response.status(), Status::Unauthorized);
+}
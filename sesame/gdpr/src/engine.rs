@@ -0,0 +1,279 @@
+use std::collections::{BTreeSet, HashMap};
+
+use serde_json::{json, Map, Value};
+
+use sesame::context::{Context, ContextData};
+use sesame::extensions::{ExtensionContext, SesameExtension};
+use sesame::policy::{AnyPolicy, Reason};
+use sesame_mysql::{get_schema_policy_columns, PConValue, SesameConn};
+
+use crate::error::{GdprError, GdprResult};
+use crate::json::value_to_json;
+
+// How the data of a subject is erased from a table.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Erasure {
+    // Delete the entire row. Rows that other subjects also own data in are anonymized instead.
+    Delete,
+    // Set the columns owned by the subject to NULL, keeping the rest of the row.
+    Anonymize,
+}
+
+#[derive(Clone, Debug)]
+struct TableConfig {
+    key_column: usize,
+    erasure: Erasure,
+    subject_column: Option<String>,
+}
+impl Default for TableConfig {
+    fn default() -> Self {
+        TableConfig {
+            key_column: 0,
+            erasure: Erasure::Delete,
+            subject_column: None,
+        }
+    }
+}
+
+// A row containing data owned by a subject.
+pub struct SubjectRow {
+    pub table: String,
+    // The name and value of the key column, used to identify the row when erasing it.
+    pub key_name: String,
+    pub key: PConValue,
+    // The (name, value) of every column owned by the subject.
+    pub columns: Vec<(String, PConValue)>,
+    // The names of the owned columns that no other subject owns.
+    pub exclusive: Vec<String>,
+    // Whether other subjects own data in this row too (in any column), such rows are never
+    // deleted, only their exclusive columns are anonymized.
+    pub co_owned: bool,
+}
+
+// Check the policy then return the value for export.
+struct ExportPolicyCheck {}
+impl SesameExtension<mysql::Value, AnyPolicy, mysql::Value> for ExportPolicyCheck {
+    fn apply(&mut self, data: mysql::Value, _policy: AnyPolicy) -> mysql::Value {
+        data
+    }
+}
+
+// Finds, exports, and erases the data of a subject across all tables with schema policies.
+// A column is owned by a subject if one of its schema policies lists the subject
+// (see SchemaPolicy::subjects).
+// Rows are identified by their first column and erased by deleting them unless configured
+// otherwise. Unless a table is configured with the column its subjects are stored in, finding
+// the data of a subject scans the entire table.
+#[derive(Clone, Debug, Default)]
+pub struct GdprEngine {
+    tables: HashMap<String, TableConfig>,
+}
+
+impl GdprEngine {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    // Configure the key column and erasure mode of a table.
+    pub fn table(mut self, table: &str, key_column: usize, erasure: Erasure) -> Self {
+        let config = self.tables.entry(String::from(table)).or_default();
+        config.key_column = key_column;
+        config.erasure = erasure;
+        self
+    }
+    // Configure the column that stores the subjects of a table (e.g. a user id column), so only
+    // rows that have the subject in that column are read when looking for their data.
+    pub fn subject_column(mut self, table: &str, column: &str) -> Self {
+        let config = self.tables.entry(String::from(table)).or_default();
+        config.subject_column = Some(String::from(column));
+        self
+    }
+
+    fn config(&self, table: &str) -> TableConfig {
+        self.tables.get(table).cloned().unwrap_or_default()
+    }
+
+    // The tables with schema policies and the columns that have them.
+    fn schema(&self) -> Vec<(String, Vec<usize>)> {
+        let mut schema: Vec<(String, Vec<usize>)> = Vec::new();
+        for (table, column) in get_schema_policy_columns() {
+            match schema.last_mut() {
+                Some((last, columns)) if *last == table => columns.push(column),
+                _ => schema.push((table, vec![column])),
+            }
+        }
+        schema
+    }
+
+    // Find every row with data owned by subject.
+    pub fn find(&self, conn: &mut SesameConn, subject: &str) -> GdprResult<Vec<SubjectRow>> {
+        self.find_rows(conn, subject, false)
+    }
+
+    // Schema policies are constructed from entire rows, so entire rows are read. If lock is true,
+    // the rows are locked until the end of the current transaction.
+    fn find_rows(
+        &self,
+        conn: &mut SesameConn,
+        subject: &str,
+        lock: bool,
+    ) -> GdprResult<Vec<SubjectRow>> {
+        let mut result = Vec::new();
+        for (table, columns) in self.schema() {
+            let config = self.config(&table);
+            let mut query = format!("SELECT * FROM {}", table);
+            let mut params = Vec::new();
+            if let Some(column) = &config.subject_column {
+                query = format!("{} WHERE {} = ?", query, column);
+                params.push(subject);
+            }
+            if lock {
+                query = format!("{} FOR UPDATE", query);
+            }
+
+            // The subject is not protected, so the empty context is enough.
+            for row in conn.prep_exec_iter(&query, params, Context::<()>::empty())? {
+                let row = row?;
+                let names = row.column_names();
+                let mut owned = Vec::new();
+                let mut exclusive = Vec::new();
+                let mut co_owned = false;
+                for column in &columns {
+                    let subjects: BTreeSet<String> = row.subjects(*column).into_iter().collect();
+                    if subjects.iter().any(|s| s != subject) {
+                        co_owned = true;
+                    }
+                    if subjects.contains(subject) {
+                        owned.push(*column);
+                        if subjects.len() == 1 {
+                            exclusive.push(*column);
+                        }
+                    }
+                }
+                if owned.is_empty() {
+                    continue;
+                }
+
+                let column = |index: usize| -> GdprResult<(String, PConValue)> {
+                    match (names.get(index), row.get(index)) {
+                        (Some(name), Some(value)) => Ok((name.clone(), value)),
+                        _ => Err(GdprError::ConfigError(format!(
+                            "table {} has no column {}",
+                            table, index
+                        ))),
+                    }
+                };
+                let (key_name, key) = column(config.key_column)?;
+                result.push(SubjectRow {
+                    table: table.clone(),
+                    key_name,
+                    key,
+                    columns: owned.into_iter().map(column).collect::<GdprResult<_>>()?,
+                    exclusive: exclusive
+                        .into_iter()
+                        .map(|index| Ok(column(index)?.0))
+                        .collect::<GdprResult<_>>()?,
+                    co_owned,
+                });
+            }
+        }
+        Ok(result)
+    }
+
+    // Export all the data owned by subject as JSON, grouped by table.
+    // Every exported value is checked against its policies with Reason::Response: the export
+    // fails if any check fails.
+    pub fn export<D: ContextData>(
+        &self,
+        conn: &mut SesameConn,
+        subject: &str,
+        context: Context<D>,
+    ) -> GdprResult<Value> {
+        let rows = self.find(conn, subject)?;
+        let context = ExtensionContext::new(context);
+        let mut tables: Map<String, Value> = Map::new();
+        for row in rows {
+            let mut object = Map::new();
+            for (name, value) in row.columns {
+                let mut ext = ExportPolicyCheck {};
+                let value = value.checked_extension(&mut ext, &context, Reason::Response)?;
+                object.insert(name, value_to_json(value));
+            }
+            match tables.entry(row.table).or_insert_with(|| json!([])) {
+                Value::Array(vec) => vec.push(Value::Object(object)),
+                _ => unreachable!(),
+            }
+        }
+        Ok(json!({ "subject": subject, "tables": tables }))
+    }
+
+    // Erase all the data owned by subject in a single transaction, deleting or anonymizing
+    // rows according to the configuration of their tables. Rows co-owned with other subjects are
+    // never deleted: only the columns owned exclusively by subject are anonymized, if any.
+    // Keys are passed as parameters, and so their policies must allow the erasure queries.
+    // Returns the number of erased rows.
+    pub fn erase<D: ContextData + Clone>(
+        &self,
+        conn: &mut SesameConn,
+        subject: &str,
+        context: Context<D>,
+    ) -> GdprResult<usize> {
+        conn.query_drop("START TRANSACTION")?;
+        let erased = self
+            .find_rows(conn, subject, true)
+            .and_then(|rows| self.erase_rows(conn, rows, context));
+        match erased {
+            Ok(count) => {
+                conn.query_drop("COMMIT")?;
+                Ok(count)
+            }
+            Err(e) => {
+                // Report the original error. If the rollback fails too, MySQL still rolls back
+                // the transaction when the connection is closed.
+                let _ = conn.query_drop("ROLLBACK");
+                Err(e)
+            }
+        }
+    }
+
+    fn erase_rows<D: ContextData + Clone>(
+        &self,
+        conn: &mut SesameConn,
+        rows: Vec<SubjectRow>,
+        context: Context<D>,
+    ) -> GdprResult<usize> {
+        let mut count = 0;
+        for row in rows {
+            let config = self.config(&row.table);
+            let key = row.key_name;
+            let query = match (config.erasure, row.co_owned) {
+                (Erasure::Delete, false) => format!("DELETE FROM {} WHERE {} = ?", row.table, key),
+                (Erasure::Anonymize, _) | (Erasure::Delete, true) => {
+                    // The key is needed to identify the row and is never anonymized.
+                    let columns: Vec<String> = if row.co_owned {
+                        row.exclusive
+                    } else {
+                        row.columns.into_iter().map(|(name, _)| name).collect()
+                    };
+                    let columns: Vec<String> = columns
+                        .into_iter()
+                        .filter(|name| *name != key)
+                        .map(|name| format!("{} = NULL", name))
+                        .collect();
+                    if columns.is_empty() {
+                        continue;
+                    }
+                    format!(
+                        "UPDATE {} SET {} WHERE {} = ?",
+                        row.table,
+                        columns.join(", "),
+                        key
+                    )
+                }
+            };
+            conn.prep_exec_drop(&query, (row.key,), context.clone())?;
+            count += 1;
+        }
+        Ok(count)
+    }
+}
@@ -0,0 +1,37 @@
+use sesame::error::SesameError;
+use sesame_mysql::SesameMySqlError;
+use std::fmt::{Debug, Display, Error as FmtError, Formatter};
+
+#[derive(Debug)]
+pub enum GdprError {
+    MySqlError(SesameMySqlError),
+    // The engine's configuration does not match the DB, e.g. a key column that does not exist.
+    ConfigError(String),
+}
+
+impl Display for GdprError {
+    fn fmt(&self, f: &mut Formatter) -> Result<(), FmtError> {
+        Debug::fmt(self, f)
+    }
+}
+impl std::error::Error for GdprError {}
+
+// Conversion.
+impl From<SesameMySqlError> for GdprError {
+    fn from(error: SesameMySqlError) -> Self {
+        GdprError::MySqlError(error)
+    }
+}
+impl From<SesameError> for GdprError {
+    fn from(error: SesameError) -> Self {
+        GdprError::MySqlError(SesameMySqlError::from(error))
+    }
+}
+impl From<mysql::Error> for GdprError {
+    fn from(error: mysql::Error) -> Self {
+        GdprError::MySqlError(SesameMySqlError::from(error))
+    }
+}
+
+// Result type.
+pub type GdprResult<T> = Result<T, GdprError>;
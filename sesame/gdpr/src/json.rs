@@ -0,0 +1,14 @@
+use serde_json::{Number, Value};
+
+// Convert a DB value to JSON for export.
+pub(crate) fn value_to_json(value: mysql::Value) -> Value {
+    match value {
+        mysql::Value::NULL => Value::Null,
+        mysql::Value::Bytes(bytes) => Value::String(String::from_utf8_lossy(&bytes).into_owned()),
+        mysql::Value::Int(i) => Value::from(i),
+        mysql::Value::UInt(u) => Value::from(u),
+        mysql::Value::Float(f) => Number::from_f64(f as f64).map_or(Value::Null, Value::Number),
+        mysql::Value::Double(d) => Number::from_f64(d).map_or(Value::Null, Value::Number),
+        value => Value::String(value.as_sql(true).trim_matches('\'').to_string()),
+    }
+}
@@ -0,0 +1,9 @@
+extern crate mysql;
+extern crate serde_json;
+
+mod engine;
+mod error;
+mod json;
+
+pub use engine::*;
+pub use error::*;
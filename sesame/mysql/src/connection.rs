@@ -7,6 +7,7 @@ use sesame::{SesameType, SesameTypeEnum};
 use mysql::prelude::Queryable;
 pub use mysql::Opts as PConOpts;
 
+use crate::encryption::{decrypt, encrypt, get_key_provider, key_id};
 use crate::{PConParams, PConQueryResult, PConResult};
 
 // PCon DB connection
@@ -74,6 +75,38 @@ impl SesameConn {
         Ok(self.conn.exec_drop(statement, params)?)
     }
 
+    // Re-encrypt the values of an encrypted column with the current key after key rotation, so
+    // that retired keys can be deleted. Rows are identified by key_column.
+    // Values are never revealed, and so this is not subject to any policy.
+    // Returns the number of re-encrypted values.
+    pub fn reencrypt_column(
+        &mut self,
+        table: &str,
+        key_column: &str,
+        column: &str,
+    ) -> PConResult<u64> {
+        let (current, _) = get_key_provider()?.current_key()?;
+        let rows: Vec<(mysql::Value, mysql::Value)> = self
+            .conn
+            .query(format!("SELECT {}, {} FROM {}", key_column, column, table))?;
+        let update = format!(
+            "UPDATE {} SET {} = ? WHERE {} = ?",
+            table, column, key_column
+        );
+        let mut count = 0;
+        for (key, value) in rows {
+            match key_id(&value)? {
+                Some(id) if id != current => {
+                    let value = encrypt(table, column, decrypt(table, column, value)?)?;
+                    self.conn.exec_drop(&update, (value, key))?;
+                    count += 1;
+                }
+                _ => {}
+            }
+        }
+        Ok(count)
+    }
+
     pub fn query_iter<T: AsRef<str>>(
         &mut self,
         query: T,
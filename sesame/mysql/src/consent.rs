@@ -0,0 +1,168 @@
+use std::collections::HashMap;
+use std::sync::RwLock;
+
+use chrono::{Datelike, NaiveDate, NaiveDateTime, Timelike};
+use mysql::prelude::Queryable;
+
+use sesame::consent::{ConsentPolicy, ConsentRecord, ConsentStore};
+use sesame::policy::NotAPolicyContainer;
+
+use crate::{PConOpts, PConResult, SchemaPolicy};
+
+// Consent history stored in a DB table, which is created if it does not exist.
+pub struct MySqlConsentStore {
+    pool: mysql::Pool,
+    table: String,
+}
+
+// The pool holds no policies (its options contain closures, which opt out of auto traits).
+impl NotAPolicyContainer for MySqlConsentStore {}
+
+impl MySqlConsentStore {
+    pub fn new<T: Into<PConOpts>>(opts: T, table: &str) -> PConResult<Self> {
+        let pool = mysql::Pool::new(opts)?;
+        pool.get_conn()?.query_drop(format!(
+            "CREATE TABLE IF NOT EXISTS {} (\
+               id BIGINT AUTO_INCREMENT PRIMARY KEY, \
+               subject VARCHAR(255) NOT NULL, \
+               purpose VARCHAR(255) NOT NULL, \
+               granted BOOLEAN NOT NULL, \
+               time DATETIME(6) NOT NULL, \
+               INDEX (subject))",
+            table
+        ))?;
+        Ok(Self {
+            pool,
+            table: String::from(table),
+        })
+    }
+}
+
+pub(crate) fn time_to_value(time: &NaiveDateTime) -> mysql::Value {
+    mysql::Value::Date(
+        time.year() as u16,
+        time.month() as u8,
+        time.day() as u8,
+        time.hour() as u8,
+        time.minute() as u8,
+        time.second() as u8,
+        time.nanosecond() / 1000,
+    )
+}
+pub(crate) fn value_to_time(value: mysql::Value) -> Result<NaiveDateTime, String> {
+    match value {
+        mysql::Value::Date(y, m, d, h, mi, s, us) => {
+            NaiveDate::from_ymd_opt(y as i32, m as u32, d as u32)
+                .and_then(|date| date.and_hms_micro_opt(h as u32, mi as u32, s as u32, us))
+                .ok_or_else(|| String::from("bad time"))
+        }
+        _ => Err(String::from("bad time")),
+    }
+}
+
+impl ConsentStore for MySqlConsentStore {
+    fn record(&self, record: ConsentRecord) -> Result<(), String> {
+        let mut conn = self.pool.get_conn().map_err(|e| e.to_string())?;
+        conn.exec_drop(
+            format!(
+                "INSERT INTO {} (subject, purpose, granted, time) VALUES (?, ?, ?, ?)",
+                self.table
+            ),
+            (
+                record.subject,
+                record.purpose,
+                record.granted,
+                time_to_value(&record.time),
+            ),
+        )
+        .map_err(|e| e.to_string())
+    }
+    fn history(&self, subject: &str) -> Result<Vec<ConsentRecord>, String> {
+        let mut conn = self.pool.get_conn().map_err(|e| e.to_string())?;
+        let rows: Vec<(String, String, bool, mysql::Value)> = conn
+            .exec(
+                format!(
+                    "SELECT subject, purpose, granted, time FROM {} WHERE subject = ? ORDER BY id",
+                    self.table
+                ),
+                (subject,),
+            )
+            .map_err(|e| e.to_string())?;
+        rows.into_iter()
+            .map(|(subject, purpose, granted, time)| {
+                Ok(ConsentRecord {
+                    subject,
+                    purpose,
+                    granted,
+                    time: value_to_time(time)?,
+                })
+            })
+            .collect()
+    }
+}
+
+// Which column holds the data subject of each table with ConsentPolicy schema policies.
+lazy_static! {
+    static ref CONSENT_SUBJECTS: RwLock<HashMap<String, usize>> = RwLock::new(HashMap::new());
+}
+pub fn register_consent_subject(table_name: &str, column: usize) {
+    CONSENT_SUBJECTS
+        .write()
+        .unwrap()
+        .insert(String::from(table_name), column);
+}
+
+// Tables must be registered (see register_consent_subject) before rows are read from them.
+// Rows of unregistered tables, or whose subject is missing or NULL, are denied to everyone.
+impl SchemaPolicy for ConsentPolicy {
+    fn from_row(table_name: &str, row: &Vec<mysql::Value>) -> Self {
+        let column = match CONSENT_SUBJECTS.read().unwrap().get(table_name) {
+            None => return ConsentPolicy::deny_all(),
+            Some(column) => *column,
+        };
+        match row.get(column) {
+            None | Some(mysql::Value::NULL) => ConsentPolicy::deny_all(),
+            Some(mysql::Value::Bytes(bytes)) => ConsentPolicy::new(String::from_utf8_lossy(bytes)),
+            Some(mysql::Value::Int(i)) => ConsentPolicy::new(i),
+            Some(mysql::Value::UInt(u)) => ConsentPolicy::new(u),
+            Some(value) => ConsentPolicy::new(value.as_sql(true)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use sesame::consent::ConsentPolicy;
+    use sesame::context::UnprotectedContext;
+    use sesame::policy::{Policy, Reason};
+
+    use crate::consent::register_consent_subject;
+    use crate::SchemaPolicy;
+
+    #[test]
+    fn consent_policy_from_row() {
+        register_consent_subject("consent_test", 1);
+        let context = UnprotectedContext::test(());
+
+        let row = vec![mysql::Value::Int(0), mysql::Value::from("alice")];
+        let policy = ConsentPolicy::from_row("consent_test", &row);
+        assert_eq!(policy.subjects().len(), 1);
+        assert!(policy.subjects().contains("alice"));
+        assert!(policy.check(&context, Reason::Response));
+
+        // Unregistered tables, missing and NULL subjects are denied.
+        let rows = vec![
+            ("unregistered", row),
+            ("consent_test", vec![mysql::Value::Int(0)]),
+            (
+                "consent_test",
+                vec![mysql::Value::Int(0), mysql::Value::NULL],
+            ),
+        ];
+        for (table, row) in rows {
+            let policy = ConsentPolicy::from_row(table, &row);
+            assert!(policy.subjects().is_empty());
+            assert!(!policy.check(&context, Reason::Response));
+        }
+    }
+}
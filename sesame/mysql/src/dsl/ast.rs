@@ -0,0 +1,181 @@
+use std::convert::TryFrom;
+
+use sesame::policy::Reason;
+
+// Values that policy rules compute over.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum DslValue {
+    Null,
+    Bool(bool),
+    Int(i64),
+    Str(String),
+    List(Vec<DslValue>),
+}
+
+impl DslValue {
+    // Convert a cell of a DB row into a value.
+    pub fn from_mysql(value: &mysql::Value) -> Self {
+        match value {
+            mysql::Value::NULL => DslValue::Null,
+            mysql::Value::Bytes(bytes) => DslValue::Str(String::from_utf8_lossy(bytes).to_string()),
+            mysql::Value::Int(i) => DslValue::Int(*i),
+            mysql::Value::UInt(u) => match i64::try_from(*u) {
+                Ok(i) => DslValue::Int(i),
+                Err(_) => DslValue::Str(u.to_string()),
+            },
+            other => DslValue::Str(other.as_sql(true)),
+        }
+    }
+
+    // Null is never equal to anything, and ints and strings compare by their string form
+    // (DB columns and context attributes often disagree on how ids are represented).
+    fn equals(&self, other: &DslValue) -> bool {
+        match (self, other) {
+            (DslValue::Null, _) | (_, DslValue::Null) => false,
+            (DslValue::Int(i), DslValue::Str(s)) | (DslValue::Str(s), DslValue::Int(i)) => {
+                &i.to_string() == s
+            }
+            (DslValue::List(l1), DslValue::List(l2)) => {
+                l1.len() == l2.len() && l1.iter().zip(l2.iter()).all(|(v1, v2)| v1.equals(v2))
+            }
+            (v1, v2) => v1 == v2,
+        }
+    }
+
+    // Lists contain their elements, strings are treated as comma separated lists.
+    fn contains(&self, value: &DslValue) -> bool {
+        match self {
+            DslValue::List(list) => list.iter().any(|v| v.equals(value)),
+            DslValue::Str(s) => s
+                .split(',')
+                .map(|e| DslValue::Str(String::from(e.trim())))
+                .any(|v| v.equals(value)),
+            _ => false,
+        }
+    }
+}
+
+// Kinds of reasons rules can be restricted to.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum ReasonKind {
+    DB,
+    Render,
+    Cookie,
+    Redirect,
+    Session,
+    Stream,
+    FileWrite,
+    Response,
+    Custom,
+}
+impl ReasonKind {
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "DB" => Some(ReasonKind::DB),
+            "Render" => Some(ReasonKind::Render),
+            "Cookie" => Some(ReasonKind::Cookie),
+            "Redirect" => Some(ReasonKind::Redirect),
+            "Session" => Some(ReasonKind::Session),
+            "Stream" => Some(ReasonKind::Stream),
+            "FileWrite" => Some(ReasonKind::FileWrite),
+            "Response" => Some(ReasonKind::Response),
+            "Custom" => Some(ReasonKind::Custom),
+            _ => None,
+        }
+    }
+    pub fn matches(&self, reason: &Reason<'_>) -> bool {
+        matches!(
+            (self, reason),
+            (ReasonKind::DB, Reason::DB(_, _))
+                | (ReasonKind::Render, Reason::TemplateRender(_))
+                | (ReasonKind::Cookie, Reason::Cookie(_))
+                | (ReasonKind::Redirect, Reason::Redirect(_))
+                | (ReasonKind::Session, Reason::Session(_))
+                | (ReasonKind::Stream, Reason::Stream(_))
+                | (ReasonKind::FileWrite, Reason::FileWrite(_))
+                | (ReasonKind::Response, Reason::Response)
+                | (ReasonKind::Custom, Reason::Custom(_))
+        )
+    }
+}
+
+// Expressions, row attributes are resolved to column indices at parse time.
+#[derive(Clone, Debug)]
+pub(crate) enum Expr {
+    Literal(DslValue),
+    List(Vec<Expr>),
+    Context(String),
+    Row(usize),
+    Eq(Box<Expr>, Box<Expr>),
+    NotEq(Box<Expr>, Box<Expr>),
+    In(Box<Expr>, Box<Expr>),
+    Not(Box<Expr>),
+    And(Box<Expr>, Box<Expr>),
+    Or(Box<Expr>, Box<Expr>),
+}
+
+impl Expr {
+    pub fn eval(&self, context: &dyn Fn(&str) -> Option<DslValue>, row: &[DslValue]) -> DslValue {
+        match self {
+            Expr::Literal(value) => value.clone(),
+            Expr::List(list) => DslValue::List(list.iter().map(|e| e.eval(context, row)).collect()),
+            Expr::Context(attribute) => context(attribute).unwrap_or(DslValue::Null),
+            Expr::Row(column) => row[*column].clone(),
+            Expr::Eq(e1, e2) => {
+                DslValue::Bool(e1.eval(context, row).equals(&e2.eval(context, row)))
+            }
+            Expr::NotEq(e1, e2) => {
+                let (v1, v2) = (e1.eval(context, row), e2.eval(context, row));
+                DslValue::Bool(v1 != DslValue::Null && v2 != DslValue::Null && !v1.equals(&v2))
+            }
+            Expr::In(e1, e2) => {
+                let v1 = e1.eval(context, row);
+                DslValue::Bool(v1 != DslValue::Null && e2.eval(context, row).contains(&v1))
+            }
+            Expr::Not(e) => DslValue::Bool(!e.holds(context, row)),
+            Expr::And(e1, e2) => DslValue::Bool(e1.holds(context, row) && e2.holds(context, row)),
+            Expr::Or(e1, e2) => DslValue::Bool(e1.holds(context, row) || e2.holds(context, row)),
+        }
+    }
+
+    // Only true is truthy.
+    pub fn holds(&self, context: &dyn Fn(&str) -> Option<DslValue>, row: &[DslValue]) -> bool {
+        self.eval(context, row) == DslValue::Bool(true)
+    }
+}
+
+// allow if <condition> [for reason in [...]].
+#[derive(Clone, Debug)]
+pub(crate) struct Rule {
+    pub condition: Expr,
+    pub reasons: Option<Vec<ReasonKind>>,
+}
+
+// policy <name> on <table>(<columns>) protect <columns> [subjects <columns>] { <rules> }
+#[derive(Clone, Debug)]
+pub(crate) struct PolicyDef {
+    pub name: String,
+    pub table: String,
+    pub columns: Vec<String>,
+    pub protected: Vec<usize>,
+    pub subjects: Vec<usize>,
+    pub rules: Vec<Rule>,
+}
+
+impl PolicyDef {
+    // Data is allowed if any rule that applies to the reason allows it.
+    pub fn allows(
+        &self,
+        context: &dyn Fn(&str) -> Option<DslValue>,
+        row: &[DslValue],
+        reason: &Reason<'_>,
+    ) -> bool {
+        self.rules.iter().any(|rule| {
+            let applies = match &rule.reasons {
+                None => true,
+                Some(reasons) => reasons.iter().any(|kind| kind.matches(reason)),
+            };
+            applies && rule.condition.holds(context, row)
+        })
+    }
+}
@@ -0,0 +1,7 @@
+mod ast;
+mod parser;
+mod policy;
+
+pub use ast::DslValue;
+pub use parser::DslError;
+pub use policy::*;
@@ -0,0 +1,309 @@
+use std::fmt::{Display, Formatter};
+
+use crate::dsl::ast::{DslValue, Expr, PolicyDef, ReasonKind, Rule};
+
+// Errors from parsing policy files, with the line they occurred at.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DslError {
+    pub line: usize,
+    pub message: String,
+}
+impl DslError {
+    fn new<S: ToString>(line: usize, message: S) -> Self {
+        Self {
+            line,
+            message: message.to_string(),
+        }
+    }
+}
+impl Display for DslError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "policy error at line {}: {}", self.line, self.message)
+    }
+}
+impl std::error::Error for DslError {}
+
+#[derive(Clone, Debug, PartialEq)]
+enum Token {
+    Ident(String),
+    Str(String),
+    Int(i64),
+    Symbol(&'static str),
+}
+
+// Split the source into tokens, each tagged with its line.
+fn tokenize(source: &str) -> Result<Vec<(Token, usize)>, DslError> {
+    const SYMBOLS: [&str; 11] = ["==", "!=", "(", ")", "[", "]", "{", "}", ",", ";", "."];
+
+    let mut tokens = Vec::new();
+    for (i, line) in source.lines().enumerate() {
+        let line_no = i + 1;
+        let mut rest = line.trim_start();
+        while !rest.is_empty() && !rest.starts_with('#') {
+            let c = rest.chars().next().unwrap();
+            if c == '"' {
+                let end = match rest[1..].find('"') {
+                    None => return Err(DslError::new(line_no, "unterminated string")),
+                    Some(end) => end + 1,
+                };
+                tokens.push((Token::Str(String::from(&rest[1..end])), line_no));
+                rest = &rest[end + 1..];
+            } else if c.is_ascii_digit() || c == '-' {
+                let end = rest[1..]
+                    .find(|c: char| !c.is_ascii_digit())
+                    .map_or(rest.len(), |end| end + 1);
+                let int = rest[..end]
+                    .parse()
+                    .map_err(|_| DslError::new(line_no, format!("bad number {}", &rest[..end])))?;
+                tokens.push((Token::Int(int), line_no));
+                rest = &rest[end..];
+            } else if c.is_alphabetic() || c == '_' {
+                let end = rest
+                    .find(|c: char| !(c.is_alphanumeric() || c == '_'))
+                    .unwrap_or(rest.len());
+                tokens.push((Token::Ident(String::from(&rest[..end])), line_no));
+                rest = &rest[end..];
+            } else {
+                match SYMBOLS.iter().find(|symbol| rest.starts_with(*symbol)) {
+                    None => return Err(DslError::new(line_no, format!("unexpected {:?}", c))),
+                    Some(symbol) => {
+                        tokens.push((Token::Symbol(symbol), line_no));
+                        rest = &rest[symbol.len()..];
+                    }
+                }
+            }
+            rest = rest.trim_start();
+        }
+    }
+    Ok(tokens)
+}
+
+struct Parser {
+    tokens: Vec<(Token, usize)>,
+    position: usize,
+}
+
+impl Parser {
+    fn line(&self) -> usize {
+        match self.tokens.get(self.position) {
+            Some((_, line)) => *line,
+            None => self.tokens.last().map_or(0, |(_, line)| *line),
+        }
+    }
+    fn error<S: ToString>(&self, message: S) -> DslError {
+        DslError::new(self.line(), message)
+    }
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.position).map(|(token, _)| token)
+    }
+    fn next(&mut self) -> Result<Token, DslError> {
+        match self.tokens.get(self.position) {
+            None => Err(self.error("unexpected end of input")),
+            Some((token, _)) => {
+                self.position += 1;
+                Ok(token.clone())
+            }
+        }
+    }
+
+    // Consume the given keyword/symbol if it is next.
+    fn accept_keyword(&mut self, keyword: &str) -> bool {
+        if matches!(self.peek(), Some(Token::Ident(ident)) if ident == keyword) {
+            self.position += 1;
+            return true;
+        }
+        false
+    }
+    fn accept_symbol(&mut self, symbol: &str) -> bool {
+        if matches!(self.peek(), Some(Token::Symbol(s)) if *s == symbol) {
+            self.position += 1;
+            return true;
+        }
+        false
+    }
+    fn expect_keyword(&mut self, keyword: &str) -> Result<(), DslError> {
+        if self.accept_keyword(keyword) {
+            Ok(())
+        } else {
+            Err(self.error(format!("expected `{}`", keyword)))
+        }
+    }
+    fn expect_symbol(&mut self, symbol: &str) -> Result<(), DslError> {
+        if self.accept_symbol(symbol) {
+            Ok(())
+        } else {
+            Err(self.error(format!("expected `{}`", symbol)))
+        }
+    }
+    fn ident(&mut self) -> Result<String, DslError> {
+        match self.next()? {
+            Token::Ident(ident) => Ok(ident),
+            token => Err(self.error(format!("expected identifier, found {:?}", token))),
+        }
+    }
+    fn ident_list(&mut self) -> Result<Vec<String>, DslError> {
+        let mut idents = vec![self.ident()?];
+        while self.accept_symbol(",") {
+            idents.push(self.ident()?);
+        }
+        Ok(idents)
+    }
+
+    // policy <name> on <table>(<columns>) protect <columns> [subjects <columns>] { <rules> }
+    fn policy(&mut self) -> Result<PolicyDef, DslError> {
+        self.expect_keyword("policy")?;
+        let name = self.ident()?;
+        self.expect_keyword("on")?;
+        let table = self.ident()?;
+        self.expect_symbol("(")?;
+        let columns = self.ident_list()?;
+        self.expect_symbol(")")?;
+
+        self.expect_keyword("protect")?;
+        let mut protected = Vec::new();
+        for column in self.ident_list()? {
+            protected.push(self.column(&columns, &column)?);
+        }
+        let mut subjects = Vec::new();
+        if self.accept_keyword("subjects") {
+            for column in self.ident_list()? {
+                subjects.push(self.column(&columns, &column)?);
+            }
+        }
+
+        self.expect_symbol("{")?;
+        let mut rules = Vec::new();
+        while !self.accept_symbol("}") {
+            rules.push(self.rule(&columns)?);
+        }
+        Ok(PolicyDef {
+            name,
+            table,
+            columns,
+            protected,
+            subjects,
+            rules,
+        })
+    }
+
+    fn column(&self, columns: &[String], column: &str) -> Result<usize, DslError> {
+        match columns.iter().position(|c| c == column) {
+            Some(index) => Ok(index),
+            None => Err(self.error(format!("unknown column {}", column))),
+        }
+    }
+
+    // allow if <condition> [for reason in [<reasons>]];
+    fn rule(&mut self, columns: &[String]) -> Result<Rule, DslError> {
+        self.expect_keyword("allow")?;
+        self.expect_keyword("if")?;
+        let condition = self.or(columns)?;
+        let reasons = if self.accept_keyword("for") {
+            self.expect_keyword("reason")?;
+            self.expect_keyword("in")?;
+            self.expect_symbol("[")?;
+            let mut reasons = Vec::new();
+            for name in self.ident_list()? {
+                match ReasonKind::from_name(&name) {
+                    Some(reason) => reasons.push(reason),
+                    None => return Err(self.error(format!("unknown reason {}", name))),
+                }
+            }
+            self.expect_symbol("]")?;
+            Some(reasons)
+        } else {
+            None
+        };
+        self.expect_symbol(";")?;
+        Ok(Rule { condition, reasons })
+    }
+
+    fn or(&mut self, columns: &[String]) -> Result<Expr, DslError> {
+        let mut expr = self.and(columns)?;
+        while self.accept_keyword("or") {
+            expr = Expr::Or(Box::new(expr), Box::new(self.and(columns)?));
+        }
+        Ok(expr)
+    }
+    fn and(&mut self, columns: &[String]) -> Result<Expr, DslError> {
+        let mut expr = self.not(columns)?;
+        while self.accept_keyword("and") {
+            expr = Expr::And(Box::new(expr), Box::new(self.not(columns)?));
+        }
+        Ok(expr)
+    }
+    fn not(&mut self, columns: &[String]) -> Result<Expr, DslError> {
+        if self.accept_keyword("not") {
+            Ok(Expr::Not(Box::new(self.not(columns)?)))
+        } else {
+            self.comparison(columns)
+        }
+    }
+    fn comparison(&mut self, columns: &[String]) -> Result<Expr, DslError> {
+        let left = Box::new(self.atom(columns)?);
+        if self.accept_symbol("==") {
+            Ok(Expr::Eq(left, Box::new(self.atom(columns)?)))
+        } else if self.accept_symbol("!=") {
+            Ok(Expr::NotEq(left, Box::new(self.atom(columns)?)))
+        } else if self.accept_keyword("in") {
+            Ok(Expr::In(left, Box::new(self.atom(columns)?)))
+        } else {
+            Ok(*left)
+        }
+    }
+    fn atom(&mut self, columns: &[String]) -> Result<Expr, DslError> {
+        match self.next()? {
+            Token::Str(s) => Ok(Expr::Literal(DslValue::Str(s))),
+            Token::Int(i) => Ok(Expr::Literal(DslValue::Int(i))),
+            Token::Symbol("(") => {
+                let expr = self.or(columns)?;
+                self.expect_symbol(")")?;
+                Ok(expr)
+            }
+            Token::Symbol("[") => {
+                let mut list = Vec::new();
+                if !self.accept_symbol("]") {
+                    list.push(self.atom(columns)?);
+                    while self.accept_symbol(",") {
+                        list.push(self.atom(columns)?);
+                    }
+                    self.expect_symbol("]")?;
+                }
+                Ok(Expr::List(list))
+            }
+            Token::Ident(ident) => match ident.as_str() {
+                "true" => Ok(Expr::Literal(DslValue::Bool(true))),
+                "false" => Ok(Expr::Literal(DslValue::Bool(false))),
+                "null" => Ok(Expr::Literal(DslValue::Null)),
+                "ctx" => {
+                    self.expect_symbol(".")?;
+                    Ok(Expr::Context(self.ident()?))
+                }
+                "row" => {
+                    self.expect_symbol(".")?;
+                    let column = self.ident()?;
+                    Ok(Expr::Row(self.column(columns, &column)?))
+                }
+                _ => Err(self.error(format!("unexpected {}", ident))),
+            },
+            token => Err(self.error(format!("unexpected {:?}", token))),
+        }
+    }
+}
+
+// Parse a policy file into its policy definitions.
+pub(crate) fn parse(source: &str) -> Result<Vec<PolicyDef>, DslError> {
+    let mut parser = Parser {
+        tokens: tokenize(source)?,
+        position: 0,
+    };
+    let mut policies: Vec<PolicyDef> = Vec::new();
+    while parser.peek().is_some() {
+        let policy = parser.policy()?;
+        if policies.iter().any(|p| p.name == policy.name) {
+            return Err(parser.error(format!("policy {} defined twice", policy.name)));
+        }
+        policies.push(policy);
+    }
+    Ok(policies)
+}
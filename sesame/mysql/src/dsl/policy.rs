@@ -0,0 +1,304 @@
+use std::any::Any;
+use std::collections::HashSet;
+use std::fmt::{Debug, Formatter};
+use std::marker::PhantomData;
+use std::path::Path;
+use std::sync::Arc;
+
+use sesame::context::UnprotectedContext;
+use sesame::policy::{AnyPolicy, Reason, SimplePolicy};
+
+use crate::dsl::ast::{DslValue, PolicyDef};
+use crate::dsl::parser::{parse, DslError};
+use crate::policy::add_schema_policy_factory;
+
+// Implemented by the (folded) context data type of the application to expose the attributes
+// that policy rules can refer to as ctx.<attribute>.
+pub trait DslContext: Any {
+    fn attribute(&self, name: &str) -> Option<DslValue>;
+}
+
+// A policy compiled from a policy file, attached to a single row.
+// Joining DslPolicies never stacks: the joined policy keeps all the (policy, row) pairs and only
+// allows access if all of them do.
+// A policy attached to a row that does not match its declared columns (e.g. because the table
+// was altered without updating the policy file) denies everything.
+pub struct DslPolicy<C: DslContext> {
+    instances: Vec<(Arc<PolicyDef>, Vec<DslValue>)>,
+    malformed: bool,
+    _c: PhantomData<fn() -> C>,
+}
+
+impl<C: DslContext> DslPolicy<C> {
+    fn new(policy: Arc<PolicyDef>, row: &[mysql::Value]) -> Self {
+        let malformed = row.len() != policy.columns.len();
+        let row = row.iter().map(DslValue::from_mysql).collect();
+        Self {
+            instances: vec![(policy, row)],
+            malformed,
+            _c: PhantomData,
+        }
+    }
+}
+
+impl<C: DslContext> SimplePolicy for DslPolicy<C> {
+    fn simple_name(&self) -> String {
+        let names: Vec<&str> = self
+            .instances
+            .iter()
+            .map(|(policy, _)| policy.name.as_str())
+            .collect();
+        if self.malformed {
+            format!("DslPolicy({}, malformed row)", names.join(", "))
+        } else {
+            format!("DslPolicy({})", names.join(", "))
+        }
+    }
+    fn simple_check(&self, context: &UnprotectedContext, reason: Reason<'_>) -> bool {
+        if self.malformed {
+            return false;
+        }
+        let context = match context.downcast_ref::<C>() {
+            None => return false,
+            Some(context) => context,
+        };
+        let attribute = |name: &str| context.attribute(name);
+        self.instances
+            .iter()
+            .all(|(policy, row)| policy.allows(&attribute, row, &reason))
+    }
+    // The values of the columns listed in the policy's `subjects` clause.
+    fn simple_subjects(&self) -> Vec<String> {
+        self.instances
+            .iter()
+            .flat_map(|(policy, row)| policy.subjects.iter().filter_map(move |c| row.get(*c)))
+            .filter_map(|value| match value {
+                DslValue::Int(i) => Some(i.to_string()),
+                DslValue::Str(s) => Some(s.clone()),
+                _ => None,
+            })
+            .collect()
+    }
+    fn simple_join_direct(&mut self, other: &mut Self) {
+        self.malformed = self.malformed || other.malformed;
+        let mut seen: HashSet<(*const PolicyDef, Vec<DslValue>)> = self
+            .instances
+            .iter()
+            .map(|(policy, row)| (Arc::as_ptr(policy), row.clone()))
+            .collect();
+        for (policy, row) in other.instances.drain(..) {
+            if seen.insert((Arc::as_ptr(&policy), row.clone())) {
+                self.instances.push((policy, row));
+            }
+        }
+    }
+}
+
+impl<C: DslContext> Clone for DslPolicy<C> {
+    fn clone(&self) -> Self {
+        Self {
+            instances: self.instances.clone(),
+            malformed: self.malformed,
+            _c: PhantomData,
+        }
+    }
+}
+impl<C: DslContext> Debug for DslPolicy<C> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.simple_name())
+    }
+}
+
+// A parsed policy file.
+// Policy files are made of policy definitions like the following:
+//
+//   policy ChatAccess on chats(id, sender, recipient, content, acl) protect content
+//       subjects sender, recipient {
+//       allow if ctx.user == row.sender or ctx.user in row.acl for reason in [Render, Response];
+//       allow if ctx.role == "admin";
+//   }
+//
+// The columns list the table's columns in order, `protect` lists the columns the policy is
+// attached to, and the optional `subjects` lists the columns identifying the data subjects the
+// protected data is about (used to find it for subject access and erasure requests).
+// Data is allowed if any rule whose reasons (if specified) match allows it.
+pub struct DslProgram {
+    policies: Vec<Arc<PolicyDef>>,
+}
+
+impl DslProgram {
+    pub fn parse(source: &str) -> Result<Self, DslError> {
+        Ok(Self {
+            policies: parse(source)?.into_iter().map(Arc::new).collect(),
+        })
+    }
+    pub fn from_file<T: AsRef<Path>>(path: T) -> Result<Self, DslError> {
+        match std::fs::read_to_string(path) {
+            Ok(source) => Self::parse(&source),
+            Err(e) => Err(DslError {
+                line: 0,
+                message: e.to_string(),
+            }),
+        }
+    }
+
+    pub fn policy_names(&self) -> Vec<&str> {
+        self.policies.iter().map(|p| p.name.as_str()).collect()
+    }
+
+    // Construct the named policy for the given row.
+    pub fn policy<C: DslContext>(&self, name: &str, row: &[mysql::Value]) -> Option<DslPolicy<C>> {
+        self.policies
+            .iter()
+            .find(|p| p.name == name)
+            .map(|p| DslPolicy::new(p.clone(), row))
+    }
+
+    // Register every policy in the program as a schema policy for its protected columns.
+    // Should be called once at startup, before any queries are made.
+    pub fn register<C: DslContext>(&self) {
+        for policy in &self.policies {
+            for column in &policy.protected {
+                let policy = policy.clone();
+                add_schema_policy_factory(
+                    policy.table.clone(),
+                    *column,
+                    Box::new(move |row: &Vec<mysql::Value>| {
+                        AnyPolicy::new(DslPolicy::<C>::new(policy.clone(), row))
+                    }),
+                );
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::dsl::{DslContext, DslProgram, DslValue};
+    use crate::policy::get_schema_policies;
+    use sesame::context::UnprotectedContext;
+    use sesame::policy::{JoinAPI, Policy, Reason};
+
+    const PROGRAM: &str = r#"
+        # Chats are visible to their sender and anyone in their acl.
+        policy ChatAccess on dsl_chats(id, sender, content, acl) protect content, acl
+            subjects sender {
+            allow if ctx.user == row.sender or ctx.user in row.acl for reason in [Render, Response];
+            allow if ctx.role == "admin" and not (row.sender == "root");
+        }
+    "#;
+
+    pub struct User {
+        user: &'static str,
+        role: &'static str,
+    }
+    impl DslContext for User {
+        fn attribute(&self, name: &str) -> Option<DslValue> {
+            match name {
+                "user" => Some(DslValue::Str(String::from(self.user))),
+                "role" => Some(DslValue::Str(String::from(self.role))),
+                _ => None,
+            }
+        }
+    }
+    fn user(user: &'static str, role: &'static str) -> UnprotectedContext {
+        UnprotectedContext::test(User { user, role })
+    }
+    fn row(id: i64, sender: &str, acl: &str) -> Vec<mysql::Value> {
+        vec![
+            mysql::Value::Int(id),
+            mysql::Value::from(sender),
+            mysql::Value::from("hello"),
+            mysql::Value::from(acl),
+        ]
+    }
+
+    #[test]
+    fn parse_errors() {
+        let err = DslProgram::parse("policy P on t(a) protect b {}")
+            .err()
+            .unwrap();
+        assert_eq!(err.line, 1);
+        let err = DslProgram::parse("policy P on t(a) protect a {\n allow if row.b == 1;\n}")
+            .err()
+            .unwrap();
+        assert_eq!(err.line, 2);
+        let err = DslProgram::parse(
+            "policy P on t(a) protect a {\n allow if true for reason in [Foo];\n}",
+        )
+        .err()
+        .unwrap();
+        assert_eq!(err.line, 2);
+    }
+
+    #[test]
+    fn dsl_policy_check() {
+        let program = DslProgram::parse(PROGRAM).unwrap();
+        assert_eq!(program.policy_names(), vec!["ChatAccess"]);
+        let policy = program
+            .policy::<User>("ChatAccess", &row(1, "alice", "bob, carl"))
+            .unwrap();
+
+        let alice = user("alice", "student");
+        let carl = user("carl", "student");
+        let dan = user("dan", "student");
+        let admin = user("eve", "admin");
+        assert!(policy.check(&alice, Reason::Response));
+        assert!(policy.check(&carl, Reason::TemplateRender("")));
+        assert!(!policy.check(&carl, Reason::Cookie("")));
+        assert!(!policy.check(&dan, Reason::Response));
+        assert!(policy.check(&admin, Reason::Cookie("")));
+
+        let root = program
+            .policy::<User>("ChatAccess", &row(2, "root", ""))
+            .unwrap();
+        assert!(!root.check(&admin, Reason::Response));
+    }
+
+    #[test]
+    fn dsl_policy_join() {
+        let program = DslProgram::parse(PROGRAM).unwrap();
+        let policy1 = program
+            .policy::<User>("ChatAccess", &row(1, "alice", "bob"))
+            .unwrap();
+        let policy2 = program
+            .policy::<User>("ChatAccess", &row(2, "bob", "alice"))
+            .unwrap();
+        let joined = policy1.join(policy2);
+
+        let alice = user("alice", "student");
+        let carl = user("carl", "student");
+        assert!(joined.check(&alice, Reason::Response));
+        assert!(!joined.check(&carl, Reason::Response));
+        assert!(joined.is::<super::DslPolicy<User>>());
+    }
+
+    #[test]
+    fn dsl_policy_malformed_row() {
+        let program = DslProgram::parse(PROGRAM).unwrap();
+        let mut short = row(1, "alice", "bob");
+        short.pop();
+        let malformed = program.policy::<User>("ChatAccess", &short).unwrap();
+        let alice = user("alice", "student");
+        let admin = user("eve", "admin");
+        assert!(!malformed.check(&alice, Reason::Response));
+        assert!(!malformed.check(&admin, Reason::Response));
+
+        let policy = program
+            .policy::<User>("ChatAccess", &row(2, "alice", "bob"))
+            .unwrap();
+        assert!(!policy.join(malformed).check(&alice, Reason::Response));
+    }
+
+    #[test]
+    fn dsl_policy_subjects() {
+        let program = DslProgram::parse(PROGRAM).unwrap();
+        program.register::<User>();
+        let row = row(1, "alice", "bob");
+        let subjects =
+            |column| get_schema_policies(String::from("dsl_chats"), column, &row).subjects();
+        assert_eq!(subjects(2), vec!["alice"]);
+        assert_eq!(subjects(3), vec!["alice"]);
+        assert!(subjects(1).is_empty());
+    }
+}
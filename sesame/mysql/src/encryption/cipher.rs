@@ -0,0 +1,206 @@
+use std::convert::TryInto;
+
+use aes_gcm::aead::{Aead, AeadCore, KeyInit, OsRng, Payload};
+use aes_gcm::{Aes256Gcm, Nonce};
+
+use crate::encryption::registry::{get_key_provider, normalize};
+use crate::{PConResult, SesameMySqlError};
+
+// Ciphertexts are stored as VERSION | key id (4 bytes) | nonce (12 bytes) | AEAD output.
+// The table and column are authenticated, so ciphertexts cannot be moved between columns.
+const VERSION: u8 = 1;
+const HEADER: usize = 1 + 4 + 12;
+
+fn error<S: ToString>(message: S) -> SesameMySqlError {
+    SesameMySqlError::EncryptionError(message.to_string())
+}
+
+// Values are serialized with a type tag so that they decrypt to the same type.
+fn encode(value: mysql::Value) -> Vec<u8> {
+    let mut bytes = Vec::new();
+    match value {
+        mysql::Value::NULL => bytes.push(0),
+        mysql::Value::Bytes(b) => {
+            bytes.push(1);
+            bytes.extend(b);
+        }
+        mysql::Value::Int(i) => {
+            bytes.push(2);
+            bytes.extend(i.to_le_bytes());
+        }
+        mysql::Value::UInt(u) => {
+            bytes.push(3);
+            bytes.extend(u.to_le_bytes());
+        }
+        mysql::Value::Float(f) => {
+            bytes.push(4);
+            bytes.extend(f.to_le_bytes());
+        }
+        mysql::Value::Double(d) => {
+            bytes.push(5);
+            bytes.extend(d.to_le_bytes());
+        }
+        mysql::Value::Date(y, m, d, h, mi, s, us) => {
+            bytes.push(6);
+            bytes.extend(y.to_le_bytes());
+            bytes.extend([m, d, h, mi, s]);
+            bytes.extend(us.to_le_bytes());
+        }
+        mysql::Value::Time(neg, d, h, m, s, us) => {
+            bytes.push(7);
+            bytes.push(neg as u8);
+            bytes.extend(d.to_le_bytes());
+            bytes.extend([h, m, s]);
+            bytes.extend(us.to_le_bytes());
+        }
+    }
+    bytes
+}
+
+fn decode(bytes: &[u8]) -> PConResult<mysql::Value> {
+    let (tag, b) = bytes.split_first().ok_or_else(|| error("empty value"))?;
+    let bad = |_| error("bad value");
+    Ok(match tag {
+        0 => mysql::Value::NULL,
+        1 => mysql::Value::Bytes(b.to_vec()),
+        2 => mysql::Value::Int(i64::from_le_bytes(b.try_into().map_err(bad)?)),
+        3 => mysql::Value::UInt(u64::from_le_bytes(b.try_into().map_err(bad)?)),
+        4 => mysql::Value::Float(f32::from_le_bytes(b.try_into().map_err(bad)?)),
+        5 => mysql::Value::Double(f64::from_le_bytes(b.try_into().map_err(bad)?)),
+        6 if b.len() == 11 => mysql::Value::Date(
+            u16::from_le_bytes([b[0], b[1]]),
+            b[2],
+            b[3],
+            b[4],
+            b[5],
+            b[6],
+            u32::from_le_bytes(b[7..11].try_into().map_err(bad)?),
+        ),
+        7 if b.len() == 12 => mysql::Value::Time(
+            b[0] != 0,
+            u32::from_le_bytes(b[1..5].try_into().map_err(bad)?),
+            b[5],
+            b[6],
+            b[7],
+            u32::from_le_bytes(b[8..12].try_into().map_err(bad)?),
+        ),
+        _ => return Err(error("bad value")),
+    })
+}
+
+// Ciphertexts are bound to their table and column, so they cannot be moved to another column.
+// They are not bound to their row: values are encrypted when statement params are bound, before
+// the row's key is known (e.g. auto increment ids), and so anyone with write access to the DB can
+// swap the encrypted values of two rows of the same column without it being detected.
+// Names are normalized like in the registry, since statements may spell them differently.
+fn aad(table_name: &str, column_name: &str) -> Vec<u8> {
+    format!("{}.{}", normalize(table_name), normalize(column_name)).into_bytes()
+}
+
+// Encrypt value for storage in the given column using the current key.
+// NULL is stored as is, so that it remains NULL in the DB.
+pub(crate) fn encrypt(
+    table_name: &str,
+    column_name: &str,
+    value: mysql::Value,
+) -> PConResult<mysql::Value> {
+    if value == mysql::Value::NULL {
+        return Ok(value);
+    }
+    let (id, key) = get_key_provider()?.current_key()?;
+    let cipher = Aes256Gcm::new_from_slice(&key).map_err(error)?;
+    let nonce = Aes256Gcm::generate_nonce(&mut OsRng);
+    let payload = Payload {
+        msg: &encode(value),
+        aad: &aad(table_name, column_name),
+    };
+    let ciphertext = cipher.encrypt(&nonce, payload).map_err(error)?;
+
+    let mut bytes = Vec::with_capacity(HEADER + ciphertext.len());
+    bytes.push(VERSION);
+    bytes.extend(id.to_be_bytes());
+    bytes.extend(&nonce[..]);
+    bytes.extend(ciphertext);
+    Ok(mysql::Value::Bytes(bytes))
+}
+
+// The id of the key the stored value was encrypted with.
+pub(crate) fn key_id(value: &mysql::Value) -> PConResult<Option<u32>> {
+    match value {
+        mysql::Value::NULL => Ok(None),
+        mysql::Value::Bytes(bytes) if bytes.len() > HEADER && bytes[0] == VERSION => {
+            Ok(Some(u32::from_be_bytes(bytes[1..5].try_into().unwrap())))
+        }
+        _ => Err(error("value is not encrypted")),
+    }
+}
+
+// Decrypt a value read from the given column.
+pub(crate) fn decrypt(
+    table_name: &str,
+    column_name: &str,
+    value: mysql::Value,
+) -> PConResult<mysql::Value> {
+    let id = match key_id(&value)? {
+        None => return Ok(mysql::Value::NULL),
+        Some(id) => id,
+    };
+    let bytes = match &value {
+        mysql::Value::Bytes(bytes) => bytes,
+        _ => unreachable!(),
+    };
+    let key = get_key_provider()?.key(id)?;
+    let cipher = Aes256Gcm::new_from_slice(&key).map_err(error)?;
+    let payload = Payload {
+        msg: &bytes[HEADER..],
+        aad: &aad(table_name, column_name),
+    };
+    let plaintext = cipher
+        .decrypt(<&Nonce<_>>::from(&bytes[5..HEADER]), payload)
+        .map_err(error)?;
+    decode(&plaintext)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::encryption::cipher::{decode, decrypt, encode, encrypt, key_id};
+    use crate::encryption::{set_key_provider, LocalFileKeyProvider};
+
+    #[test]
+    fn encode_decode_values() {
+        let values = vec![
+            mysql::Value::NULL,
+            mysql::Value::from("hello"),
+            mysql::Value::Int(-5),
+            mysql::Value::UInt(7),
+            mysql::Value::Float(1.5),
+            mysql::Value::Double(-2.25),
+            mysql::Value::Date(2024, 1, 6, 10, 30, 0, 15),
+            mysql::Value::Time(true, 1, 2, 3, 4, 5),
+        ];
+        for value in values {
+            assert_eq!(decode(&encode(value.clone())).unwrap(), value);
+        }
+    }
+
+    #[test]
+    fn encrypt_decrypt_values() {
+        let path = std::env::temp_dir().join("sesame_test_keys_cipher");
+        let _ = std::fs::remove_file(&path);
+        set_key_provider(LocalFileKeyProvider::new(&path).unwrap());
+
+        let value = mysql::Value::from("secret");
+        let encrypted = encrypt("users", "ssn", value.clone()).unwrap();
+        assert_ne!(encrypted, value);
+        assert_eq!(key_id(&encrypted).unwrap(), Some(1));
+        assert_eq!(decrypt("users", "ssn", encrypted.clone()).unwrap(), value);
+
+        // Ciphertexts are bound to their column, however it is spelled.
+        assert_eq!(decrypt("Users", "SSN", encrypted.clone()).unwrap(), value);
+        assert!(decrypt("users", "name", encrypted).is_err());
+        // NULL stays NULL.
+        let null = encrypt("users", "ssn", mysql::Value::NULL).unwrap();
+        assert_eq!(null, mysql::Value::NULL);
+        std::fs::remove_file(&path).unwrap();
+    }
+}
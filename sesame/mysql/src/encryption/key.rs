@@ -0,0 +1,130 @@
+use std::collections::BTreeMap;
+use std::fs::OpenOptions;
+use std::io::Write;
+#[cfg(unix)]
+use std::os::unix::fs::OpenOptionsExt;
+use std::path::{Path, PathBuf};
+use std::sync::RwLock;
+
+use aes_gcm::aead::{KeyInit, OsRng};
+use aes_gcm::Aes256Gcm;
+
+use crate::{PConResult, SesameMySqlError};
+
+// Provides the keys used to encrypt columns.
+// Keys are identified by an id stored alongside every ciphertext, so that values encrypted with
+// older keys can still be decrypted after rotating to a new key.
+pub trait KeyProvider: Send + Sync {
+    // The id and bytes of the key new values are encrypted with.
+    fn current_key(&self) -> PConResult<(u32, Vec<u8>)>;
+    // The bytes of a (possibly retired) key.
+    fn key(&self, id: u32) -> PConResult<Vec<u8>>;
+}
+
+// Keys stored in a local file, one "<id> <hex key>" per line.
+// The key with the highest id is the current key.
+pub struct LocalFileKeyProvider {
+    path: PathBuf,
+    keys: RwLock<BTreeMap<u32, Vec<u8>>>,
+}
+
+fn error<S: ToString>(message: S) -> SesameMySqlError {
+    SesameMySqlError::EncryptionError(message.to_string())
+}
+
+impl LocalFileKeyProvider {
+    // Loads the keys in path, creating the file with a fresh key if it does not exist.
+    pub fn new<P: AsRef<Path>>(path: P) -> PConResult<Self> {
+        let provider = LocalFileKeyProvider {
+            path: path.as_ref().to_path_buf(),
+            keys: RwLock::new(BTreeMap::new()),
+        };
+        if !provider.path.exists() {
+            provider.rotate()?;
+            return Ok(provider);
+        }
+
+        let contents = std::fs::read_to_string(&provider.path).map_err(error)?;
+        let mut keys = provider.keys.write().unwrap();
+        for (i, line) in contents.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() {
+                continue;
+            }
+            let parsed = line.split_once(' ').and_then(|(id, key)| {
+                Some((id.parse::<u32>().ok()?, hex::decode(key.trim()).ok()?))
+            });
+            match parsed {
+                Some((id, key)) if key.len() == 32 => keys.insert(id, key),
+                _ => return Err(error(format!("bad key on line {}", i + 1))),
+            };
+        }
+        drop(keys);
+        Ok(provider)
+    }
+
+    // Generate a new current key and persist it.
+    // Values encrypted with older keys remain readable, see SesameConn::reencrypt_column to
+    // re-encrypt them with the new key.
+    pub fn rotate(&self) -> PConResult<u32> {
+        let mut keys = self.keys.write().unwrap();
+        let id = keys.keys().next_back().map_or(1, |id| id + 1);
+        let key = Aes256Gcm::generate_key(OsRng).to_vec();
+        let mut options = OpenOptions::new();
+        options.create(true).append(true);
+        // Only the owner can read a newly created key file.
+        #[cfg(unix)]
+        options.mode(0o600);
+        let mut file = options.open(&self.path).map_err(error)?;
+        writeln!(file, "{} {}", id, hex::encode(&key)).map_err(error)?;
+        keys.insert(id, key);
+        Ok(id)
+    }
+}
+
+impl KeyProvider for LocalFileKeyProvider {
+    fn current_key(&self) -> PConResult<(u32, Vec<u8>)> {
+        let keys = self.keys.read().unwrap();
+        match keys.iter().next_back() {
+            None => Err(error("no keys")),
+            Some((id, key)) => Ok((*id, key.clone())),
+        }
+    }
+    fn key(&self, id: u32) -> PConResult<Vec<u8>> {
+        let keys = self.keys.read().unwrap();
+        match keys.get(&id) {
+            None => Err(error(format!("unknown key {}", id))),
+            Some(key) => Ok(key.clone()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::encryption::{KeyProvider, LocalFileKeyProvider};
+
+    #[test]
+    fn local_file_key_rotation() {
+        let path = std::env::temp_dir().join("sesame_test_keys_rotation");
+        let _ = std::fs::remove_file(&path);
+
+        let provider = LocalFileKeyProvider::new(&path).unwrap();
+        let (id, key) = provider.current_key().unwrap();
+        assert_eq!((id, key.len()), (1, 32));
+        assert_eq!(provider.rotate().unwrap(), 2);
+
+        // Keys persist, old keys remain available.
+        let provider = LocalFileKeyProvider::new(&path).unwrap();
+        assert_eq!(provider.current_key().unwrap().0, 2);
+        assert_eq!(provider.key(1).unwrap(), key);
+        assert!(provider.key(3).is_err());
+
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            let mode = std::fs::metadata(&path).unwrap().permissions().mode();
+            assert_eq!(mode & 0o777, 0o600);
+        }
+        std::fs::remove_file(&path).unwrap();
+    }
+}
@@ -0,0 +1,10 @@
+mod cipher;
+mod key;
+mod registry;
+mod statement;
+
+pub use key::*;
+pub use registry::*;
+
+pub(crate) use cipher::{decrypt, encrypt, key_id};
+pub(crate) use statement::param_columns;
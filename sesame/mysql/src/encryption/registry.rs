@@ -0,0 +1,59 @@
+use std::collections::HashSet;
+use std::sync::{Arc, RwLock};
+
+use crate::encryption::KeyProvider;
+use crate::{PConResult, SesameMySqlError};
+
+// Which (table, column name) pairs are encrypted, and with which keys.
+lazy_static! {
+    static ref ENCRYPTED_COLUMNS: RwLock<HashSet<(String, String)>> = RwLock::new(HashSet::new());
+    static ref KEY_PROVIDER: RwLock<Option<Arc<dyn KeyProvider>>> = RwLock::new(None);
+}
+
+// MySQL compares column names (and, on some platforms, table names) case insensitively, so
+// names are registered and looked up in lower case: otherwise `VALUE` would not be encrypted.
+pub(crate) fn normalize(name: &str) -> String {
+    name.to_lowercase()
+}
+
+// Values written to this column are encrypted, and decrypted when read.
+// The column should be a binary type (e.g. BLOB), and cannot be filtered on in queries.
+pub fn add_encrypted_column(table_name: &str, column_name: &str) {
+    ENCRYPTED_COLUMNS
+        .write()
+        .unwrap()
+        .insert((normalize(table_name), normalize(column_name)));
+}
+
+pub fn set_key_provider<K: KeyProvider + 'static>(provider: K) {
+    *KEY_PROVIDER.write().unwrap() = Some(Arc::new(provider));
+}
+
+pub(crate) fn is_encrypted(table_name: &str, column_name: &str) -> bool {
+    ENCRYPTED_COLUMNS
+        .read()
+        .unwrap()
+        .contains(&(normalize(table_name), normalize(column_name)))
+}
+
+pub(crate) fn has_encrypted_columns(table_name: &str) -> bool {
+    let table_name = normalize(table_name);
+    ENCRYPTED_COLUMNS
+        .read()
+        .unwrap()
+        .iter()
+        .any(|(table, _)| *table == table_name)
+}
+
+pub(crate) fn get_key_provider() -> PConResult<Arc<dyn KeyProvider>> {
+    match &*KEY_PROVIDER.read().unwrap() {
+        None => Err(SesameMySqlError::EncryptionError(String::from(
+            "no key provider",
+        ))),
+        Some(provider) => Ok(provider.clone()),
+    }
+}
+
+pub(crate) fn any_encrypted_columns() -> bool {
+    !ENCRYPTED_COLUMNS.read().unwrap().is_empty()
+}
@@ -0,0 +1,347 @@
+use crate::encryption::registry::{has_encrypted_columns, is_encrypted};
+use crate::{PConResult, SesameMySqlError};
+
+// Finds which column each `?` parameter of a statement is written to, so that values written to
+// encrypted columns can be encrypted.
+// Supports `INSERT INTO t (cols) VALUES (?, ..), .. [ON DUPLICATE KEY UPDATE col = ?, ..]`,
+// `INSERT INTO t SET col = ?, ..` and `UPDATE t SET col = ?, .. [WHERE ..]`. Parameters that are
+// the entire value of an encrypted column map to it, parameters that are only part of the value
+// of an unencrypted column or that are not written (e.g. in WHERE) map to None. Statements that
+// write to tables with encrypted columns fail if any other parameter is found, since it is not
+// known whether it ends up in an encrypted column.
+#[derive(Debug, PartialEq)]
+enum Token {
+    Word(String),
+    Param,
+    Symbol(char),
+    Literal,
+}
+
+fn tokenize(stmt: &str) -> Vec<Token> {
+    let mut tokens = Vec::new();
+    let mut chars = stmt.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            c if c.is_whitespace() => {}
+            '?' => tokens.push(Token::Param),
+            '\'' | '"' => {
+                // Skip string literal (including escaped and doubled quotes).
+                while let Some(n) = chars.next() {
+                    if n == '\\' {
+                        chars.next();
+                    } else if n == c {
+                        if chars.peek() == Some(&c) {
+                            chars.next();
+                        } else {
+                            break;
+                        }
+                    }
+                }
+                tokens.push(Token::Literal);
+            }
+            '`' => {
+                let word: String = chars.by_ref().take_while(|n| *n != '`').collect();
+                tokens.push(Token::Word(word));
+            }
+            c if c.is_alphanumeric() || c == '_' => {
+                let mut word = String::from(c);
+                while let Some(n) = chars.peek() {
+                    if n.is_alphanumeric() || *n == '_' || *n == '$' {
+                        word.push(*n);
+                        chars.next();
+                    } else {
+                        break;
+                    }
+                }
+                tokens.push(Token::Word(word));
+            }
+            c => tokens.push(Token::Symbol(c)),
+        }
+    }
+    tokens
+}
+
+fn is_keyword(token: Option<&Token>, keyword: &str) -> bool {
+    matches!(token, Some(Token::Word(w)) if w.eq_ignore_ascii_case(keyword))
+}
+
+// Reads a possibly qualified name (db.table), returning the last component.
+fn name(tokens: &[Token], i: &mut usize) -> Option<String> {
+    let mut result = None;
+    while let Some(Token::Word(w)) = tokens.get(*i) {
+        result = Some(w.clone());
+        *i += 1;
+        if tokens.get(*i) != Some(&Token::Symbol('.')) {
+            break;
+        }
+        *i += 1;
+    }
+    result
+}
+
+fn error<S: ToString>(message: S) -> SesameMySqlError {
+    SesameMySqlError::EncryptionError(message.to_string())
+}
+
+// Returns the table and the column of every parameter, or None if the statement does not write
+// to a table with encrypted columns.
+pub(crate) fn param_columns(stmt: &str) -> PConResult<Option<(String, Vec<Option<String>>)>> {
+    let tokens = tokenize(stmt);
+    let mut i = 0;
+    if is_keyword(tokens.first(), "INSERT") || is_keyword(tokens.first(), "REPLACE") {
+        i += 1;
+        while is_keyword(tokens.get(i), "IGNORE")
+            || is_keyword(tokens.get(i), "LOW_PRIORITY")
+            || is_keyword(tokens.get(i), "DELAYED")
+            || is_keyword(tokens.get(i), "HIGH_PRIORITY")
+        {
+            i += 1;
+        }
+        if is_keyword(tokens.get(i), "INTO") {
+            i += 1;
+        }
+        let table = match name(&tokens, &mut i) {
+            Some(table) if has_encrypted_columns(&table) => table,
+            _ => return Ok(None),
+        };
+        if is_keyword(tokens.get(i), "SET") {
+            let mut params = Vec::new();
+            assignment_params(&tokens[i + 1..], &table, &mut params)?;
+            return Ok(Some((table, params)));
+        }
+        if tokens.get(i) != Some(&Token::Symbol('(')) {
+            return Err(error(format!(
+                "writes to {} must list columns explicitly",
+                table
+            )));
+        }
+        i += 1;
+        let mut columns = Vec::new();
+        while let Some(Token::Word(column)) = tokens.get(i) {
+            columns.push(column.clone());
+            i += 1;
+            if tokens.get(i) == Some(&Token::Symbol(',')) {
+                i += 1;
+            }
+        }
+        if tokens.get(i) == Some(&Token::Symbol(')')) {
+            i += 1;
+        }
+        let params = insert_params(&tokens[i..], &table, &columns)?;
+        Ok(Some((table, params)))
+    } else if is_keyword(tokens.first(), "UPDATE") {
+        i += 1;
+        while is_keyword(tokens.get(i), "IGNORE") || is_keyword(tokens.get(i), "LOW_PRIORITY") {
+            i += 1;
+        }
+        let table = match name(&tokens, &mut i) {
+            Some(table) if has_encrypted_columns(&table) => table,
+            _ => return Ok(None),
+        };
+        if !is_keyword(tokens.get(i), "SET") {
+            return Err(error(format!(
+                "writes to {} must be of the form UPDATE {} SET ..",
+                table, table
+            )));
+        }
+        let mut params = Vec::new();
+        assignment_params(&tokens[i + 1..], &table, &mut params)?;
+        Ok(Some((table, params)))
+    } else {
+        Ok(None)
+    }
+}
+
+fn unknown_param(table: &str) -> SesameMySqlError {
+    error(format!(
+        "cannot tell which column a parameter of a write to {} is written to",
+        table
+    ))
+}
+fn partial_param(table: &str, column: &str) -> SesameMySqlError {
+    error(format!(
+        "parameters written to encrypted column {}.{} must be its entire value",
+        table, column
+    ))
+}
+
+// Params of the VALUES tuples are written to the column at their position.
+fn insert_params(
+    tokens: &[Token],
+    table: &str,
+    columns: &[String],
+) -> PConResult<Vec<Option<String>>> {
+    let mut params = Vec::new();
+    let mut depth = 0;
+    let mut position = 0;
+    let mut in_values = false;
+    for (i, token) in tokens.iter().enumerate() {
+        let previous = i.checked_sub(1).map(|j| &tokens[j]);
+        match token {
+            Token::Word(w) if depth == 0 && w.eq_ignore_ascii_case("VALUES") => in_values = true,
+            Token::Word(w) if depth == 0 && w.eq_ignore_ascii_case("VALUE") => in_values = true,
+            Token::Word(w) if depth == 0 && w.eq_ignore_ascii_case("UPDATE") => {
+                // ON DUPLICATE KEY UPDATE col = ?, ..
+                assignment_params(&tokens[i + 1..], table, &mut params)?;
+                break;
+            }
+            Token::Word(_) if depth == 0 => in_values = false,
+            Token::Symbol('(') => {
+                depth += 1;
+                if depth == 1 {
+                    position = 0;
+                }
+            }
+            Token::Symbol(')') => depth -= 1,
+            Token::Symbol(',') if depth == 1 => position += 1,
+            Token::Param => {
+                let column = match columns.get(position) {
+                    Some(column) if in_values && depth > 0 => column,
+                    _ => return Err(unknown_param(table)),
+                };
+                let alone = depth == 1
+                    && matches!(previous, Some(Token::Symbol('(' | ',')))
+                    && matches!(tokens.get(i + 1), Some(Token::Symbol(')' | ',')));
+                if alone {
+                    params.push(Some(column.clone()));
+                } else if is_encrypted(table, column) {
+                    return Err(partial_param(table, column));
+                } else {
+                    params.push(None);
+                }
+            }
+            _ => {}
+        }
+    }
+    Ok(params)
+}
+
+// Params in an assignment list (`col = ?, ..`) are written to the column assigned.
+// Encrypted columns cannot be compared against params (e.g. in WHERE), since encryption is
+// randomized.
+fn assignment_params(
+    tokens: &[Token],
+    table: &str,
+    params: &mut Vec<Option<String>>,
+) -> PConResult<()> {
+    let ends_assignments = |token: Option<&Token>| {
+        is_keyword(token, "WHERE") || is_keyword(token, "ORDER") || is_keyword(token, "LIMIT")
+    };
+    let mut depth = 0;
+    let mut in_set = true;
+    // The column of the current assignment, and whether its `=` was seen.
+    let mut column: Option<String> = None;
+    let mut assigning = false;
+    for (i, token) in tokens.iter().enumerate() {
+        let previous = i.checked_sub(1).map(|j| &tokens[j]);
+        let next = tokens.get(i + 1);
+        match token {
+            _ if depth == 0 && ends_assignments(Some(token)) => in_set = false,
+            Token::Word(w) if in_set && depth == 0 && !assigning => column = Some(w.clone()),
+            Token::Symbol('=') if in_set && depth == 0 && !assigning => assigning = true,
+            Token::Symbol(',') if in_set && depth == 0 => {
+                column = None;
+                assigning = false;
+            }
+            Token::Symbol('(') => depth += 1,
+            Token::Symbol(')') => depth -= 1,
+            Token::Param if in_set => {
+                let column = match &column {
+                    Some(column) if assigning => column,
+                    _ => return Err(unknown_param(table)),
+                };
+                let alone = depth == 0
+                    && previous == Some(&Token::Symbol('='))
+                    && (next.is_none()
+                        || next == Some(&Token::Symbol(','))
+                        || ends_assignments(next));
+                if alone {
+                    params.push(Some(column.clone()));
+                } else if is_encrypted(table, column) {
+                    return Err(partial_param(table, column));
+                } else {
+                    params.push(None);
+                }
+            }
+            Token::Param => {
+                if let (Some(Token::Word(column)), Some(Token::Symbol('='))) =
+                    (i.checked_sub(2).map(|j| &tokens[j]), previous)
+                {
+                    if is_encrypted(table, column) {
+                        return Err(error(format!(
+                            "encrypted column {}.{} cannot be compared",
+                            table, column
+                        )));
+                    }
+                }
+                params.push(None);
+            }
+            _ => {}
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::encryption::add_encrypted_column;
+    use crate::encryption::registry::is_encrypted;
+    use crate::encryption::statement::param_columns;
+
+    #[test]
+    fn find_param_columns() {
+        add_encrypted_column("secrets", "value");
+        let some = |s: &str| Some(String::from(s));
+
+        let (table, params) =
+            param_columns("INSERT INTO secrets (id, value) VALUES (?, ?), (5, ?)")
+                .unwrap()
+                .unwrap();
+        assert_eq!(table, "secrets");
+        assert_eq!(params, vec![some("id"), some("value"), some("value")]);
+
+        let (_, params) = param_columns("UPDATE `secrets` SET value = ?, id = id + ? WHERE id = ?")
+            .unwrap()
+            .unwrap();
+        assert_eq!(params, vec![some("value"), None, None]);
+
+        let (_, params) = param_columns(
+            "INSERT INTO secrets (id, value) VALUES (?, ?) ON DUPLICATE KEY UPDATE value = ?",
+        )
+        .unwrap()
+        .unwrap();
+        assert_eq!(params, vec![some("id"), some("value"), some("value")]);
+        let (_, params) = param_columns("INSERT INTO secrets SET id = ?, secrets.value = ?")
+            .unwrap()
+            .unwrap();
+        assert_eq!(params, vec![some("id"), some("value")]);
+
+        assert!(param_columns("UPDATE secrets SET id = ? WHERE value = ?").is_err());
+
+        // Names are case insensitive.
+        let (_, params) = param_columns("INSERT INTO Secrets (ID, VALUE) VALUES (?, ?)")
+            .unwrap()
+            .unwrap();
+        assert_eq!(params, vec![some("ID"), some("VALUE")]);
+        assert!(is_encrypted("Secrets", "VALUE"));
+        assert!(param_columns("UPDATE SECRETS SET Value = CONCAT(?, '')").is_err());
+
+        // Params that are only part of the value written to an encrypted column, or that are
+        // written to an unknown column, are rejected.
+        assert!(param_columns("UPDATE secrets SET value = CONCAT(?, '') WHERE id = ?").is_err());
+        assert!(param_columns(
+            "INSERT INTO secrets (id, value) VALUES (?, ?) \
+             ON DUPLICATE KEY UPDATE value = CONCAT(?, '')"
+        )
+        .is_err());
+        assert!(param_columns("INSERT INTO secrets (id, value) VALUES (?, ?, ?)").is_err());
+        assert!(param_columns("INSERT INTO secrets (id, value) SELECT ?, ?").is_err());
+        assert!(param_columns("INSERT INTO secrets VALUES (?, ?)").is_err());
+        assert!(param_columns("INSERT INTO others VALUES (?, ?)")
+            .unwrap()
+            .is_none());
+        assert!(param_columns("SELECT * FROM secrets WHERE id = ?")
+            .unwrap()
+            .is_none());
+    }
+}
@@ -5,6 +5,7 @@ use std::fmt::{Debug, Display, Error as FmtError, Formatter};
 pub enum SesameMySqlError {
     SesameError(SesameError),
     MySqlError(mysql::Error),
+    EncryptionError(String),
 }
 
 impl Display for SesameMySqlError {
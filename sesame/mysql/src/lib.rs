@@ -8,19 +8,27 @@ extern crate lazy_static;
 extern crate mysql;
 
 mod connection;
+mod consent;
+mod dsl;
+mod encryption;
 mod error;
 mod param;
 mod params;
 mod policy;
 mod result;
 mod row;
+mod session;
 mod value;
 
 pub use connection::*;
+pub use consent::*;
+pub use dsl::*;
+pub use encryption::*;
 pub use error::*;
 pub use param::*;
 pub use params::*;
 pub use policy::*;
 pub use result::*;
 pub use row::*;
+pub use session::*;
 pub use value::*;
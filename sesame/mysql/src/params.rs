@@ -1,12 +1,12 @@
 use sesame::context::{Context, ContextData};
-use sesame::error::SesameError;
 use sesame::extensions::{
     ExtensionContext, SesameExtension, SesameRefExtension, UncheckedSesameExtension,
 };
 use sesame::pcon::EitherPCon;
 use sesame::policy::{AnyPolicy, Reason};
 
-use crate::PConParam;
+use crate::encryption::{any_encrypted_columns, encrypt, is_encrypted, param_columns};
+use crate::{PConParam, PConResult};
 
 // Use Sesame Extension to execute policy check on PCon parameters
 // and retrieve the data when policy check is successful for writing to the DB.
@@ -43,7 +43,7 @@ impl PConParams {
         self,
         context: Context<D>,
         reason: Reason,
-    ) -> Result<mysql::params::Params, SesameError> {
+    ) -> PConResult<mysql::params::Params> {
         match self {
             PConParams::Empty => Ok(mysql::params::Params::Empty),
             PConParams::Positional(vec) => {
@@ -57,6 +57,22 @@ impl PConParams {
                         }
                     }
                 }
+                // Encrypt values written to encrypted columns after they pass their policies.
+                if let Reason::DB(stmt, _) = reason {
+                    if any_encrypted_columns() {
+                        if let Some((table, columns)) = param_columns(stmt)? {
+                            let values = std::mem::take(&mut ext.vec);
+                            for (i, value) in values.into_iter().enumerate() {
+                                match columns.get(i) {
+                                    Some(Some(column)) if is_encrypted(&table, column) => {
+                                        ext.push(encrypt(&table, column, value)?)
+                                    }
+                                    _ => ext.push(value),
+                                }
+                            }
+                        }
+                    }
+                }
                 Ok(ext.into_params())
             }
         }
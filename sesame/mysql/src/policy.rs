@@ -1,6 +1,7 @@
 use std::collections::HashMap;
 use std::sync::RwLock;
 
+use sesame::policy::rbac::{RoleContext, RolePolicy};
 use sesame::policy::{AnyPolicy, AnyPolicyable, NoPolicy, Policy, PolicyAnd, PolicyOr};
 
 #[cfg(feature = "derive")]
@@ -30,6 +31,32 @@ impl<P1: SchemaPolicy, P2: SchemaPolicy> SchemaPolicy for PolicyOr<P1, P2> {
     }
 }
 
+// Describes how to construct RolePolicy from rows of the application's tables.
+// Implemented by the application on the same type it implements RoleContext for.
+pub trait RoleSchema: RoleContext {
+    // Index of the column storing the ID of the row's owner (if any).
+    fn owner_column(table_name: &str) -> Option<usize>;
+    // Roles allowed to access data in the given table.
+    fn table_roles(table_name: &str) -> Vec<String>;
+}
+impl<C: RoleSchema> SchemaPolicy for RolePolicy<C> {
+    fn from_row(table_name: &str, row: &Vec<mysql::Value>) -> Self {
+        let policy = RolePolicy::with_roles(&C::table_roles(table_name));
+        // A missing (out of range) or NULL owner column means the row has no owner, so only the
+        // table roles are allowed.
+        let owner = C::owner_column(table_name).and_then(|column| match row.get(column) {
+            Some(mysql::Value::Bytes(bytes)) => Some(String::from_utf8_lossy(bytes).to_string()),
+            Some(mysql::Value::Int(i)) => Some(i.to_string()),
+            Some(mysql::Value::UInt(u)) => Some(u.to_string()),
+            _ => None,
+        });
+        match owner {
+            None => policy,
+            Some(owner) => policy.allow_owner(owner),
+        }
+    }
+}
+
 // Global static singleton.
 type SchemaPolicyFactory = dyn (Fn(&Vec<mysql::Value>) -> AnyPolicy) + Send + Sync;
 type SchemaPolicyMap = HashMap<(String, usize), Vec<Box<SchemaPolicyFactory>>>;
@@ -63,15 +90,73 @@ pub fn get_schema_policies(
     }
 }
 
+// All the (table, column) pairs that have schema policies.
+pub fn get_schema_policy_columns() -> Vec<(String, usize)> {
+    let map = SCHEMA_POLICIES.read().unwrap();
+    let mut columns: Vec<(String, usize)> = map.keys().cloned().collect();
+    columns.sort();
+    columns
+}
+
 // Register Policy T as a schema policy associated with the table and column.
 // Never use this function directly, instead use the #[schema_policy(...)] macro.
 extern crate small_ctor;
 pub use small_ctor::ctor as register;
 pub fn add_schema_policy<T: SchemaPolicy + AnyPolicyable>(table_name: String, column: usize) {
+    add_schema_policy_factory(
+        table_name.clone(),
+        column,
+        Box::new(move |row: &Vec<mysql::Value>| AnyPolicy::new(T::from_row(&table_name, row))),
+    );
+}
+pub(crate) fn add_schema_policy_factory(
+    table_name: String,
+    column: usize,
+    factory: Box<SchemaPolicyFactory>,
+) {
     let mut map = SCHEMA_POLICIES.write().unwrap();
-    map.entry((table_name.clone(), column))
-        .or_default()
-        .push(Box::new(move |row: &Vec<mysql::Value>| {
-            AnyPolicy::new(T::from_row(&table_name, row))
-        }));
+    map.entry((table_name, column)).or_default().push(factory);
+}
+
+#[cfg(test)]
+mod tests {
+    use sesame::policy::rbac::{RoleContext, RolePolicy};
+
+    use crate::policy::{RoleSchema, SchemaPolicy};
+
+    pub struct User {}
+    impl RoleContext for User {
+        fn principal(&self) -> Option<&str> {
+            None
+        }
+        fn roles(&self) -> Vec<&str> {
+            Vec::new()
+        }
+    }
+    impl RoleSchema for User {
+        fn owner_column(_table_name: &str) -> Option<usize> {
+            Some(1)
+        }
+        fn table_roles(_table_name: &str) -> Vec<String> {
+            vec![String::from("admin")]
+        }
+    }
+
+    #[test]
+    fn role_policy_from_row() {
+        let row = vec![mysql::Value::Int(0), mysql::Value::from("alice")];
+        let policy = RolePolicy::<User>::from_row("grades", &row);
+        assert!(policy.owners().contains("alice"));
+
+        // NULL and missing owners allow the table roles only.
+        let row = vec![mysql::Value::Int(0), mysql::Value::NULL];
+        let policy = RolePolicy::<User>::from_row("grades", &row);
+        assert!(policy.owners().is_empty());
+        assert!(policy.roles().contains("admin"));
+
+        let row = vec![mysql::Value::Int(0)];
+        let policy = RolePolicy::<User>::from_row("grades", &row);
+        assert!(policy.owners().is_empty());
+        assert!(policy.roles().contains("admin"));
+    }
 }
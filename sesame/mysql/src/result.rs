@@ -4,6 +4,7 @@ use crate::PConRow;
 pub use mysql::SetColumns as PConSetColumns;
 
 // Our result wrapper.
+// Rows that cannot be decrypted (see add_encrypted_column) are returned as IO errors.
 pub struct PConQueryResult<'c, 't, 'tc, T: mysql::prelude::Protocol> {
     pub(crate) result: mysql::QueryResult<'c, 't, 'tc, T>,
 }
@@ -24,7 +25,12 @@ impl<'c, 't, 'tc, T: mysql::prelude::Protocol> Iterator for PConQueryResult<'c,
         match self.result.next() {
             None => None,
             Some(row) => match row {
-                Ok(row) => Some(Ok(PConRow::new(row))),
+                Ok(row) => Some(PConRow::new(row).map_err(|e| {
+                    mysql::Error::IoError(std::io::Error::new(
+                        std::io::ErrorKind::InvalidData,
+                        e.to_string(),
+                    ))
+                })),
                 Err(e) => Some(Err(e)),
             },
         }
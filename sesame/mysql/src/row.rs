@@ -1,22 +1,41 @@
 use sesame::pcon::PCon;
-use sesame::policy::AnyPolicy;
+use sesame::policy::{AnyPolicy, Policy};
 
+use crate::encryption::{any_encrypted_columns, decrypt, is_encrypted};
 use crate::policy::get_schema_policies;
-use crate::{PConFromValue, PConValue};
+use crate::{PConFromValue, PConResult, PConValue};
 
 // mysql imports.
 pub use mysql::prelude::ColumnIndex as PConColumnIndex;
 
 // A result row.
+// Encrypted columns are decrypted into raw, and only ever leave it inside a PCon.
 #[derive(Clone)]
 pub struct PConRow {
     row: mysql::Row,
     raw: Vec<mysql::Value>,
+    decrypted: Vec<bool>,
 }
 impl PConRow {
-    pub(super) fn new(row: mysql::Row) -> Self {
-        let raw = row.clone().unwrap();
-        PConRow { row, raw }
+    pub(super) fn new(row: mysql::Row) -> PConResult<Self> {
+        let mut raw = row.clone().unwrap();
+        let mut decrypted = vec![false; raw.len()];
+        if any_encrypted_columns() {
+            // Use the original table and column, since the query may alias them.
+            for (i, column) in row.columns_ref().iter().enumerate() {
+                let (table, name) = (column.org_table_str(), column.org_name_str());
+                if is_encrypted(&table, &name) {
+                    let value = std::mem::replace(&mut raw[i], mysql::Value::NULL);
+                    raw[i] = decrypt(&table, &name, value)?;
+                    decrypted[i] = true;
+                }
+            }
+        }
+        Ok(PConRow {
+            row,
+            raw,
+            decrypted,
+        })
     }
 
     pub fn get<T: PConFromValue, I: PConColumnIndex>(
@@ -26,7 +45,11 @@ impl PConRow {
         let columns = self.row.columns_ref();
         let idx = index.idx(columns)?;
         let table = columns[idx].table_str().into_owned();
-        let val = self.row.get(index)?;
+        let val = if self.decrypted[idx] {
+            mysql::from_value(self.raw[idx].clone())
+        } else {
+            self.row.get(index)?
+        };
         Some(PCon::new(val, get_schema_policies(table, idx, &self.raw)))
     }
 
@@ -37,10 +60,37 @@ impl PConRow {
         let columns = self.row.columns_ref();
         let idx = index.idx(columns)?;
         let table = columns[idx].table_str().into_owned();
-        let val = self.row.take(index)?;
+        let val = if self.decrypted[idx] {
+            self.row.take::<mysql::Value, I>(index)?;
+            mysql::from_value(self.raw[idx].clone())
+        } else {
+            self.row.take(index)?
+        };
         Some(PCon::new(val, get_schema_policies(table, idx, &self.raw)))
     }
 
+    // The names of the columns in this row.
+    pub fn column_names(&self) -> Vec<String> {
+        self.row
+            .columns_ref()
+            .iter()
+            .map(|column| column.name_str().into_owned())
+            .collect()
+    }
+
+    // The data subjects of the given cell, as exposed by its schema policies (see
+    // Policy::subjects).
+    pub fn subjects<I: PConColumnIndex>(&self, index: I) -> Vec<String> {
+        let columns = self.row.columns_ref();
+        match index.idx(columns) {
+            None => Vec::new(),
+            Some(idx) => {
+                let table = columns[idx].table_str().into_owned();
+                get_schema_policies(table, idx, &self.raw).subjects()
+            }
+        }
+    }
+
     pub fn unwrap(self) -> Vec<PConValue> {
         self.raw
             .iter()
@@ -0,0 +1,77 @@
+use mysql::prelude::Queryable;
+
+use sesame::policy::NotAPolicyContainer;
+use sesame::session::{SessionRecord, SessionStore};
+
+use crate::consent::{time_to_value, value_to_time};
+use crate::{PConOpts, PConResult};
+
+// Sessions stored in a DB table, which is created if it does not exist.
+// The data is stored as is, it was already checked against its policy when it was put in the
+// session.
+pub struct MySqlSessionStore {
+    pool: mysql::Pool,
+    table: String,
+}
+
+// The pool holds no policies (its options contain closures, which opt out of auto traits).
+impl NotAPolicyContainer for MySqlSessionStore {}
+
+impl MySqlSessionStore {
+    pub fn new<T: Into<PConOpts>>(opts: T, table: &str) -> PConResult<Self> {
+        let pool = mysql::Pool::new(opts)?;
+        pool.get_conn()?.query_drop(format!(
+            "CREATE TABLE IF NOT EXISTS {} (\
+               id VARCHAR(64) PRIMARY KEY, \
+               data MEDIUMTEXT NOT NULL, \
+               expires DATETIME(6) NOT NULL)",
+            table
+        ))?;
+        Ok(Self {
+            pool,
+            table: String::from(table),
+        })
+    }
+
+    // Delete all expired sessions, returns how many were deleted.
+    pub fn delete_expired(&self) -> PConResult<u64> {
+        let mut conn = self.pool.get_conn()?;
+        conn.query_drop(format!(
+            "DELETE FROM {} WHERE expires <= UTC_TIMESTAMP(6)",
+            self.table
+        ))?;
+        Ok(conn.affected_rows())
+    }
+}
+
+impl SessionStore for MySqlSessionStore {
+    fn load(&self, id: &str) -> Result<Option<SessionRecord>, String> {
+        let mut conn = self.pool.get_conn().map_err(|e| e.to_string())?;
+        let row: Option<(String, mysql::Value)> = conn
+            .exec_first(
+                format!("SELECT data, expires FROM {} WHERE id = ?", self.table),
+                (id,),
+            )
+            .map_err(|e| e.to_string())?;
+        match row {
+            None => Ok(None),
+            Some((data, expires)) => Ok(Some(SessionRecord::new(data, value_to_time(expires)?))),
+        }
+    }
+    fn save(&self, id: &str, record: SessionRecord) -> Result<(), String> {
+        let mut conn = self.pool.get_conn().map_err(|e| e.to_string())?;
+        conn.exec_drop(
+            format!(
+                "REPLACE INTO {} (id, data, expires) VALUES (?, ?, ?)",
+                self.table
+            ),
+            (id, record.data, time_to_value(&record.expires)),
+        )
+        .map_err(|e| e.to_string())
+    }
+    fn delete(&self, id: &str) -> Result<(), String> {
+        let mut conn = self.pool.get_conn().map_err(|e| e.to_string())?;
+        conn.exec_drop(format!("DELETE FROM {} WHERE id = ?", self.table), (id,))
+            .map_err(|e| e.to_string())
+    }
+}
@@ -0,0 +1,96 @@
+use rocket::http::{ContentType, Method, Status};
+use serde_json::{json, Value};
+
+use sesame::consent::{get_consent_store, ConsentRecord};
+
+use crate::rocket::{PConData, PConRequest, PConResponseOutcome, SesameRoute, SesameRouteInfo};
+
+// Identifies the data subject making a request (e.g. from an authentication cookie), so that
+// the consent routes can record their decisions.
+// Like FrontendPolicy, this is given the raw request, and must be implemented carefully.
+pub trait ConsentSubject: 'static {
+    fn subject(request: &rocket::Request<'_>) -> Option<String>;
+}
+
+fn record_to_json(record: &ConsentRecord) -> Value {
+    json!({
+        "purpose": record.purpose,
+        "granted": record.granted,
+        "time": record.time.to_string(),
+    })
+}
+
+// Apply the update (if any) and respond with the full history of the subject.
+fn update_consent<S: ConsentSubject>(
+    request: PConRequest<'_, '_>,
+    update: Option<bool>,
+) -> Result<(ContentType, String), Status> {
+    let request = request.get_request();
+    let subject = S::subject(request).ok_or(Status::Unauthorized)?;
+    let store = get_consent_store().ok_or(Status::InternalServerError)?;
+    if let Some(granted) = update {
+        let purpose: String = match request.param(1) {
+            Some(Ok(purpose)) => purpose,
+            _ => return Err(Status::BadRequest),
+        };
+        store
+            .record(ConsentRecord::new(&subject, &purpose, granted))
+            .map_err(|_| Status::InternalServerError)?;
+    }
+    let history = store
+        .history(&subject)
+        .map_err(|_| Status::InternalServerError)?;
+    let history: Vec<Value> = history.iter().map(record_to_json).collect();
+    Ok((ContentType::JSON, Value::Array(history).to_string()))
+}
+
+async fn history_handler<'a, 'r, S: ConsentSubject>(
+    request: PConRequest<'a, 'r>,
+    _data: PConData<'a>,
+) -> PConResponseOutcome<'a> {
+    PConResponseOutcome::from(request, update_consent::<S>(request, None))
+}
+async fn grant_handler<'a, 'r, S: ConsentSubject>(
+    request: PConRequest<'a, 'r>,
+    _data: PConData<'a>,
+) -> PConResponseOutcome<'a> {
+    PConResponseOutcome::from(request, update_consent::<S>(request, Some(true)))
+}
+async fn revoke_handler<'a, 'r, S: ConsentSubject>(
+    request: PConRequest<'a, 'r>,
+    _data: PConData<'a>,
+) -> PConResponseOutcome<'a> {
+    PConResponseOutcome::from(request, update_consent::<S>(request, Some(false)))
+}
+
+// Routes for managing the consent of the requesting subject, using the global consent store
+// (see sesame::consent::set_consent_store). All routes respond with the subject's full
+// consent history as JSON.
+//   GET  /consent                   history.
+//   POST /consent/<purpose>         grant consent to purpose.
+//   POST /consent/<purpose>/revoke  revoke consent to purpose.
+pub fn consent_routes<S: ConsentSubject>() -> Vec<SesameRoute> {
+    vec![
+        SesameRouteInfo {
+            method: Method::Get,
+            uri: "/consent",
+            handler: |request, data| Box::pin(history_handler::<S>(request, data)),
+            requirement: None,
+        }
+        .into(),
+        SesameRouteInfo {
+            method: Method::Post,
+            uri: "/consent/<purpose>",
+            handler: |request, data| Box::pin(grant_handler::<S>(request, data)),
+            requirement: None,
+        }
+        .into(),
+        SesameRouteInfo {
+            method: Method::Post,
+            uri: "/consent/<purpose>/revoke",
+            handler: |request, data| Box::pin(revoke_handler::<S>(request, data)),
+            requirement: None,
+        }
+        .into(),
+    ]
+}
@@ -19,6 +19,9 @@ mod mysql {
             match self {
                 SesameMySqlError::SesameError(error) => error.respond_to(request),
                 SesameMySqlError::MySqlError(_error) => Err(rocket::http::Status { code: 500 }),
+                SesameMySqlError::EncryptionError(_error) => {
+                    Err(rocket::http::Status { code: 500 })
+                }
             }
         }
     }
@@ -29,6 +32,7 @@ mod mysql {
 pub enum SesameRenderError {
     SesameError(SesameError),
     FigmentError(figment::Error),
+    SerializationError(String),
 }
 impl Display for SesameRenderError {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
@@ -41,6 +45,9 @@ impl<'a, 'r, 'o: 'r> PConResponder<'a, 'r, 'o> for SesameRenderError {
         match self {
             SesameRenderError::SesameError(err) => err.respond_to(request),
             SesameRenderError::FigmentError(_err) => Err(rocket::http::Status::InternalServerError),
+            SesameRenderError::SerializationError(_err) => {
+                Err(rocket::http::Status::InternalServerError)
+            }
         }
     }
 }
@@ -56,6 +63,97 @@ impl From<figment::Error> for SesameRenderError {
         SesameRenderError::FigmentError(e)
     }
 }
+impl From<serde_json::Error> for SesameRenderError {
+    fn from(e: serde_json::Error) -> Self {
+        SesameRenderError::SerializationError(e.to_string())
+    }
+}
 
 // Results.
 pub type SesameRenderResult<T> = Result<T, SesameRenderError>;
+
+// Errors that can occur when reading or writing sessions.
+#[derive(Clone, Debug)]
+pub enum SesameSessionError {
+    SesameError(SesameError),
+    StoreError(String),
+}
+impl Display for SesameSessionError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        Debug::fmt(self, f)
+    }
+}
+impl std::error::Error for SesameSessionError {}
+impl<'a, 'r, 'o: 'r> PConResponder<'a, 'r, 'o> for SesameSessionError {
+    fn respond_to(self, request: PConRequest<'a, 'r>) -> PConResponseResult<'o> {
+        match self {
+            SesameSessionError::SesameError(err) => err.respond_to(request),
+            SesameSessionError::StoreError(_err) => Err(rocket::http::Status::InternalServerError),
+        }
+    }
+}
+impl From<SesameError> for SesameSessionError {
+    fn from(e: SesameError) -> Self {
+        SesameSessionError::SesameError(e)
+    }
+}
+
+// Results.
+pub type SesameSessionResult<T> = Result<T, SesameSessionError>;
+
+// Errors that can occur when adding cookies.
+#[derive(Clone, Debug)]
+pub enum SesameCookieError {
+    SesameError(SesameError),
+    MissingSigningKey,
+    // Cookies read from the jar cannot be added back, create a new cookie instead.
+    ReadOnlyCookie,
+}
+impl Display for SesameCookieError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        Debug::fmt(self, f)
+    }
+}
+impl std::error::Error for SesameCookieError {}
+impl<'a, 'r, 'o: 'r> PConResponder<'a, 'r, 'o> for SesameCookieError {
+    fn respond_to(self, request: PConRequest<'a, 'r>) -> PConResponseResult<'o> {
+        match self {
+            SesameCookieError::SesameError(err) => err.respond_to(request),
+            SesameCookieError::MissingSigningKey => Err(rocket::http::Status::InternalServerError),
+            SesameCookieError::ReadOnlyCookie => Err(rocket::http::Status::InternalServerError),
+        }
+    }
+}
+impl From<SesameError> for SesameCookieError {
+    fn from(e: SesameError) -> Self {
+        SesameCookieError::SesameError(e)
+    }
+}
+
+// Results.
+pub type SesameCookieResult<T> = Result<T, SesameCookieError>;
+
+// Errors that can occur when verifying bearer tokens.
+#[derive(Clone, Debug)]
+pub enum SesameJwtError {
+    JwksError(String),
+    MissingToken,
+    UnknownKey,
+    InvalidToken(String),
+    BadClaims(&'static str),
+    ConfigError(&'static str),
+}
+impl Display for SesameJwtError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        Debug::fmt(self, f)
+    }
+}
+impl std::error::Error for SesameJwtError {}
+impl From<jsonwebtoken::errors::Error> for SesameJwtError {
+    fn from(e: jsonwebtoken::errors::Error) -> Self {
+        SesameJwtError::InvalidToken(e.to_string())
+    }
+}
+
+// Results.
+pub type SesameJwtResult<T> = Result<T, SesameJwtError>;
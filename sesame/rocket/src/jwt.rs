@@ -0,0 +1,179 @@
+use std::path::Path;
+use std::str::FromStr;
+
+use jsonwebtoken::jwk::{Jwk, PublicKeyUse};
+use jsonwebtoken::{decode, decode_header, DecodingKey, Validation};
+use rocket::http::Status;
+use serde_json::Value;
+
+use crate::error::{SesameJwtError, SesameJwtResult};
+use crate::rocket::{
+    FromPConRequest, InputPConValue, PConRequest, PConRequestOutcome, RequestPConJson,
+};
+
+pub use jsonwebtoken::jwk::JwkSet;
+pub use jsonwebtoken::Algorithm;
+
+// Bearer token (JWT) verification configuration, must be managed by rocket, e.g.
+// `.manage(PConJwtVerifier::from_jwks_file("jwks.json")?.issuer("https://sso.example.com/realms/app").audience("app"))`.
+// Tokens are verified against the keys of a JWKS, e.g. the one an OIDC provider publishes at its
+// jwks_uri, so this works with any provider (Keycloak, Auth0, ...).
+pub struct PConJwtVerifier {
+    keys: JwkSet,
+    algorithms: Vec<Algorithm>,
+    issuers: Vec<String>,
+    audiences: Vec<String>,
+    skip_audience: bool,
+    leeway: u64,
+}
+impl PConJwtVerifier {
+    pub fn new(keys: JwkSet) -> Self {
+        Self {
+            keys,
+            algorithms: vec![Algorithm::RS256],
+            issuers: Vec::new(),
+            audiences: Vec::new(),
+            skip_audience: false,
+            leeway: 60,
+        }
+    }
+    pub fn from_jwks_json(jwks: &str) -> SesameJwtResult<Self> {
+        match serde_json::from_str(jwks) {
+            Err(err) => Err(SesameJwtError::JwksError(err.to_string())),
+            Ok(keys) => Ok(Self::new(keys)),
+        }
+    }
+    pub fn from_jwks_file<T: AsRef<Path>>(path: T) -> SesameJwtResult<Self> {
+        match std::fs::read_to_string(path) {
+            Err(err) => Err(SesameJwtError::JwksError(err.to_string())),
+            Ok(jwks) => Self::from_jwks_json(&jwks),
+        }
+    }
+    // Algorithms tokens may be signed with (RS256 by default).
+    pub fn algorithms(mut self, algorithms: &[Algorithm]) -> Self {
+        self.algorithms = algorithms.to_vec();
+        self
+    }
+    // Tokens must have one of the issuers/audiences as their iss/aud.
+    // At least one of each is required, otherwise every token is rejected (tokens from the same
+    // provider but meant for other applications would be accepted).
+    pub fn issuer(mut self, issuer: &str) -> Self {
+        self.issuers.push(String::from(issuer));
+        self
+    }
+    pub fn audience(mut self, audience: &str) -> Self {
+        self.audiences.push(String::from(audience));
+        self
+    }
+    // Accept tokens for any audience, only if the provider issues tokens for this application alone.
+    pub fn insecure_skip_audience(mut self) -> Self {
+        self.skip_audience = true;
+        self
+    }
+    // Clock skew (in seconds) tolerated when checking exp and nbf.
+    pub fn leeway(mut self, leeway: u64) -> Self {
+        self.leeway = leeway;
+        self
+    }
+
+    // Tokens that do not name their key are only accepted if there is a single key.
+    fn find_key(&self, kid: Option<&str>) -> Option<&Jwk> {
+        match kid {
+            Some(kid) => self.keys.find(kid),
+            None if self.keys.keys.len() == 1 => self.keys.keys.first(),
+            None => None,
+        }
+    }
+
+    // Checks the signature and registered claims, returns the (unprotected) claims.
+    // Not public, applications get the claims protected via claims() or PConJwtClaims.
+    fn verify(&self, token: &str) -> SesameJwtResult<Value> {
+        if self.issuers.is_empty() {
+            return Err(SesameJwtError::ConfigError("no issuer is configured"));
+        }
+        if self.audiences.is_empty() && !self.skip_audience {
+            return Err(SesameJwtError::ConfigError("no audience is configured"));
+        }
+        let header = decode_header(token)?;
+        if !self.algorithms.contains(&header.alg) {
+            return Err(SesameJwtError::InvalidToken(format!(
+                "algorithm {:?} is not allowed",
+                header.alg
+            )));
+        }
+
+        let jwk = self
+            .find_key(header.kid.as_deref())
+            .ok_or(SesameJwtError::UnknownKey)?;
+        if let Some(PublicKeyUse::Encryption) = jwk.common.public_key_use {
+            return Err(SesameJwtError::UnknownKey);
+        }
+        if let Some(alg) = jwk.common.key_algorithm {
+            if Algorithm::from_str(&alg.to_string()).ok() != Some(header.alg) {
+                return Err(SesameJwtError::UnknownKey);
+            }
+        }
+        let key = DecodingKey::from_jwk(jwk)?;
+
+        let mut validation = Validation::new(header.alg);
+        validation.leeway = self.leeway;
+        validation.set_issuer(&self.issuers);
+        if self.skip_audience && self.audiences.is_empty() {
+            validation.validate_aud = false;
+        } else {
+            validation.set_audience(&self.audiences);
+        }
+        Ok(decode::<Value>(token, &key, &validation)?.claims)
+    }
+
+    // Verify the bearer token in the Authorization header of the request, and return its claims,
+    // with every PCon in T protected by the policy its FrontendPolicy constructs from the request.
+    pub fn claims<T: RequestPConJson>(&self, request: PConRequest<'_, '_>) -> SesameJwtResult<T> {
+        let token = bearer_token(request).ok_or(SesameJwtError::MissingToken)?;
+        let claims = self.verify(token)?;
+        T::from_json(InputPConValue::new(claims), request).map_err(SesameJwtError::BadClaims)
+    }
+}
+
+fn bearer_token<'a>(request: PConRequest<'a, '_>) -> Option<&'a str> {
+    let header = request.get_request().headers().get_one("Authorization")?;
+    let (scheme, token) = header.trim().split_once(' ')?;
+    if scheme.eq_ignore_ascii_case("bearer") {
+        Some(token.trim())
+    } else {
+        None
+    }
+}
+
+// Request guard for the verified claims of the request's bearer token, using the managed
+// PConJwtVerifier. T is typically a struct with PCon fields deriving RequestPConJson (and
+// SesameType, so it can be used in ContextData).
+pub struct PConJwtClaims<T: RequestPConJson>(pub T);
+impl<T: RequestPConJson> PConJwtClaims<T> {
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+}
+
+#[rocket::async_trait]
+impl<'a, 'r, T: RequestPConJson + Send> FromPConRequest<'a, 'r> for PConJwtClaims<T> {
+    type PConError = SesameJwtError;
+    async fn from_pcon_request(
+        request: PConRequest<'a, 'r>,
+    ) -> PConRequestOutcome<Self, Self::PConError> {
+        let verifier = match request.get_request().rocket().state::<PConJwtVerifier>() {
+            None => {
+                let err = SesameJwtError::JwksError(String::from("PConJwtVerifier is not managed"));
+                return PConRequestOutcome::Failure((Status::InternalServerError, err));
+            }
+            Some(verifier) => verifier,
+        };
+        match verifier.claims(request) {
+            Ok(claims) => PConRequestOutcome::Success(PConJwtClaims(claims)),
+            Err(err @ SesameJwtError::ConfigError(_)) => {
+                PConRequestOutcome::Failure((Status::InternalServerError, err))
+            }
+            Err(err) => PConRequestOutcome::Failure((Status::Unauthorized, err)),
+        }
+    }
+}
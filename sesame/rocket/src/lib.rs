@@ -3,12 +3,25 @@
 extern crate sesame_derive;
 
 // Export these
+pub mod consent;
 pub mod context;
 pub mod error;
+pub mod jwt;
 pub mod policy;
 pub mod render;
 pub mod rocket;
+pub mod session;
 pub mod testing;
 
 #[cfg(feature = "orm")]
 pub mod orm;
+
+// Framework independent parts (JSON, redirects), also used by derived code.
+pub use sesame_web as web;
+
+// Frontend specific items derived code refers to, found under the same path in every frontend
+// crate (e.g. sesame_axum::frontend).
+#[doc(hidden)]
+pub mod frontend {
+    pub use crate::rocket::{PConRequest, RequestPConJson};
+}
@@ -1,3 +1,4 @@
+use sesame::policy::rbac::{RoleContext, RolePolicy};
 use sesame::policy::{NoPolicy, Policy, PolicyAnd, PolicyOr};
 
 // Front end policy can be constructed from HTTP requests and from cookies.
@@ -59,3 +60,31 @@ impl<P1: FrontendPolicy, P2: FrontendPolicy> FrontendPolicy for PolicyOr<P1, P2>
         )
     }
 }
+
+// Describes how to construct RolePolicy for data submitted by a request (e.g. the authenticated
+// user owns the data they submit).
+// Implemented by the application on the same type it implements RoleContext for.
+pub trait RoleFrontend: RoleContext {
+    // The ID of the owner of the data in the request (if any).
+    fn request_owner(request: &rocket::Request<'_>) -> Option<String>;
+    // Roles allowed to access data in the request.
+    fn request_roles(_request: &rocket::Request<'_>) -> Vec<String> {
+        Vec::new()
+    }
+}
+impl<C: RoleFrontend> FrontendPolicy for RolePolicy<C> {
+    fn from_request(request: &rocket::Request<'_>) -> Self {
+        let policy = RolePolicy::with_roles(&C::request_roles(request));
+        match C::request_owner(request) {
+            None => policy,
+            Some(owner) => policy.allow_owner(owner),
+        }
+    }
+    fn from_cookie<'a, 'r>(
+        _name: &str,
+        _cookie: &'a rocket::http::Cookie<'static>,
+        request: &'a rocket::Request<'r>,
+    ) -> Self {
+        Self::from_request(request)
+    }
+}
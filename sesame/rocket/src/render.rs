@@ -49,18 +49,24 @@ impl<'a> Renderable<'a> {
         self,
         template: &str,
         context: &ExtensionContext,
+    ) -> SesameRenderResult<FValue> {
+        self.transform_with(context, &Reason::TemplateRender(template))
+    }
+    pub(crate) fn transform_with(
+        self,
+        context: &ExtensionContext,
+        reason: &Reason<'_>,
     ) -> SesameRenderResult<FValue> {
         match self {
             Renderable::PCon(pcon) => {
                 let mut checker = RenderPolicyChecker {};
-                let reason = Reason::TemplateRender(template);
-                Ok(pcon.checked_extension(&mut checker, context, reason)??)
+                Ok(pcon.checked_extension(&mut checker, context, reason.clone())??)
             }
             Renderable::Serialize(obj) => Ok(FValue::serialize(obj)?),
             Renderable::Dict(map) => {
                 let mut tmap: BTreeMap<String, FValue> = BTreeMap::new();
                 for (k, v) in map {
-                    let v = v.transform(template, context)?;
+                    let v = v.transform_with(context, reason)?;
                     tmap.insert(k.clone(), v);
                 }
                 Ok(FValue::from(tmap))
@@ -68,7 +74,7 @@ impl<'a> Renderable<'a> {
             Renderable::Array(vec) => {
                 let mut tvec: Vec<FValue> = Vec::new();
                 for v in vec {
-                    let v = v.transform(template, context)?;
+                    let v = v.transform_with(context, reason)?;
                     tvec.push(v);
                 }
                 Ok(FValue::from(tvec))
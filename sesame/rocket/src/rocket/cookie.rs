@@ -5,11 +5,11 @@ use std::option::Option;
 use time::{Duration, OffsetDateTime};
 
 use sesame::context::{Context, ContextData};
-use sesame::error::SesameResult;
 use sesame::extensions::{ExtensionContext, SesameExtension};
 use sesame::pcon::PCon;
 use sesame::policy::{Policy, Reason, RefPolicy};
 
+use crate::error::{SesameCookieError, SesameCookieResult};
 use crate::policy::FrontendPolicy;
 
 // Cookies are build from PCons, should they also be built from non pcons?
@@ -76,8 +76,40 @@ impl<'c, P: FrontendPolicy> PConCookieBuilder<'c, P> {
 // Cookies are pcon-ed by default.
 enum PConCookieEnum<'a, P: FrontendPolicy> {
     Read(&'a rocket::http::Cookie<'static>, P),
+    // Private or signed cookies that were decrypted or verified when read.
+    Verified(rocket::http::Cookie<'static>, P, CookieProtection<'a>),
     Write(rocket::http::Cookie<'a>, PCon<Cow<'a, str>, P>),
 }
+
+// How a cookie is protected in transit.
+// Signed cookies carry their key, so a cookie can only be signed if there is a key.
+#[derive(Clone, Copy)]
+enum CookieProtection<'k> {
+    Plain,
+    #[cfg(feature = "secrets")]
+    Private,
+    Signed(&'k cookie::Key),
+}
+
+// Key used for signing cookies, must be managed by rocket, e.g.
+// `.manage(CookieSigningKey::generate())`.
+// Private cookies use rocket's own `secret_key` instead.
+pub struct CookieSigningKey {
+    key: cookie::Key,
+}
+impl CookieSigningKey {
+    // Derive the key from a master key of at least 64 bytes.
+    pub fn from_master(master: &[u8]) -> Self {
+        Self {
+            key: cookie::Key::from(master),
+        }
+    }
+    pub fn generate() -> Self {
+        Self {
+            key: cookie::Key::generate(),
+        }
+    }
+}
 pub struct PConCookie<'a, P: FrontendPolicy> {
     pub(self) cookie: PConCookieEnum<'a, P>,
 }
@@ -103,6 +135,7 @@ impl<'c, P: FrontendPolicy> PConCookie<'c, P> {
     pub fn name(&self) -> &str {
         match &self.cookie {
             PConCookieEnum::Read(cookie, _) => cookie.name(),
+            PConCookieEnum::Verified(cookie, _, _) => cookie.name(),
             PConCookieEnum::Write(cookie, _) => cookie.name(),
         }
     }
@@ -112,6 +145,9 @@ impl<'c, P: FrontendPolicy> PConCookie<'c, P> {
             PConCookieEnum::Read(cookie, policy) => {
                 PCon::new(cookie.value(), RefPolicy::new(policy))
             }
+            PConCookieEnum::Verified(cookie, policy, _) => {
+                PCon::new(cookie.value(), RefPolicy::new(policy))
+            }
             PConCookieEnum::Write(_, pcon) => pcon.as_ref_pcon(),
         }
     }
@@ -121,29 +157,50 @@ impl<'c, P: FrontendPolicy> From<PConCookie<'c, P>> for PCon<String, P> {
     fn from(cookie: PConCookie<'c, P>) -> PCon<String, P> {
         match cookie.cookie {
             PConCookieEnum::Read(cookie, policy) => PCon::new(String::from(cookie.value()), policy),
+            PConCookieEnum::Verified(cookie, policy, _) => {
+                PCon::new(String::from(cookie.value()), policy)
+            }
             PConCookieEnum::Write(_name, pcon) => pcon.into_pcon(),
         }
     }
 }
 
 // Extension for checking cookie policy and adding it to jar.
+// Private cookies are encrypted and signed cookies are signed only after the check succeeds.
 struct CookieExtension<'a, 'r> {
     jar: &'a rocket::http::CookieJar<'r>,
     cookie: &'a rocket::http::Cookie<'static>,
+    protection: CookieProtection<'a>,
 }
 impl<'a, 'r> CookieExtension<'a, 'r> {
     pub fn new(
         jar: &'a rocket::http::CookieJar<'r>,
         cookie: &'a rocket::http::Cookie<'static>,
+        protection: CookieProtection<'a>,
     ) -> Self {
-        Self { jar, cookie }
+        Self {
+            jar,
+            cookie,
+            protection,
+        }
     }
 }
 impl<'a, 'r, P: Policy> SesameExtension<Cow<'static, str>, P, ()> for CookieExtension<'a, 'r> {
     fn apply(&mut self, data: Cow<'static, str>, _policy: P) -> () {
         let mut cookie = self.cookie.clone();
         cookie.set_value(data);
-        self.jar.add(cookie)
+        match self.protection {
+            CookieProtection::Plain => self.jar.add(cookie),
+            #[cfg(feature = "secrets")]
+            CookieProtection::Private => self.jar.add_private(cookie),
+            CookieProtection::Signed(key) => {
+                let mut signed = cookie::CookieJar::new();
+                signed.signed_mut(key).add(cookie);
+                for cookie in signed.delta() {
+                    self.jar.add(cookie.clone());
+                }
+            }
+        }
     }
 }
 
@@ -161,23 +218,37 @@ impl<'a, 'r> PConCookieJar<'a, 'r> {
         PConCookieJar { jar, request }
     }
 
-    pub fn add<P: FrontendPolicy, D: ContextData>(
+    // None if the application did not manage a CookieSigningKey.
+    fn signing_key(&self) -> Option<&'a cookie::Key> {
+        let key = self.request.rocket().state::<CookieSigningKey>()?;
+        Some(&key.key)
+    }
+
+    // Only cookies created by the application can be added, not ones read from the jar.
+    fn add_protected<P: FrontendPolicy, D: ContextData>(
         &self,
         cookie: PConCookie<'static, P>,
         ctx: Context<D>,
-    ) -> SesameResult<()> {
+        protection: CookieProtection<'_>,
+    ) -> SesameCookieResult<()> {
         match cookie.cookie {
-            PConCookieEnum::Read(_, _) => {
-                unreachable!("Create the cookie yourself then add it.");
-            }
             PConCookieEnum::Write(cookie, pcon) => {
                 let ctx = ExtensionContext::new(ctx);
                 let reason = Reason::Cookie(cookie.name());
-                let mut ext = CookieExtension::new(self.jar, &cookie);
-                pcon.checked_extension(&mut ext, &ctx, reason)
+                let mut ext = CookieExtension::new(self.jar, &cookie, protection);
+                Ok(pcon.checked_extension(&mut ext, &ctx, reason)?)
             }
+            _ => Err(SesameCookieError::ReadOnlyCookie),
         }
     }
+
+    pub fn add<P: FrontendPolicy, D: ContextData>(
+        &self,
+        cookie: PConCookie<'static, P>,
+        ctx: Context<D>,
+    ) -> SesameCookieResult<()> {
+        self.add_protected(cookie, ctx, CookieProtection::Plain)
+    }
     pub fn get<P: FrontendPolicy>(&self, name: &str) -> Option<PConCookie<'a, P>> {
         match self.jar.get(name) {
             None => None,
@@ -189,9 +260,68 @@ impl<'a, 'r> PConCookieJar<'a, 'r> {
             }
         }
     }
+
+    // Encrypted and authenticated using rocket's secret_key.
+    #[cfg(feature = "secrets")]
+    pub fn add_private<P: FrontendPolicy, D: ContextData>(
+        &self,
+        cookie: PConCookie<'static, P>,
+        ctx: Context<D>,
+    ) -> SesameCookieResult<()> {
+        self.add_protected(cookie, ctx, CookieProtection::Private)
+    }
+    // The policy is only attached if the cookie decrypts successfully.
+    #[cfg(feature = "secrets")]
+    pub fn get_private<P: FrontendPolicy>(&self, name: &str) -> Option<PConCookie<'a, P>> {
+        match self.jar.get_private(name) {
+            None => None,
+            Some(cookie) => {
+                let p = P::from_cookie(name, &cookie, self.request);
+                Some(PConCookie {
+                    cookie: PConCookieEnum::Verified(cookie, p, CookieProtection::Private),
+                })
+            }
+        }
+    }
+
+    // Readable by the client but integrity checked using the managed CookieSigningKey.
+    // Fails with MissingSigningKey if no CookieSigningKey is managed.
+    pub fn add_signed<P: FrontendPolicy, D: ContextData>(
+        &self,
+        cookie: PConCookie<'static, P>,
+        ctx: Context<D>,
+    ) -> SesameCookieResult<()> {
+        let key = self
+            .signing_key()
+            .ok_or(SesameCookieError::MissingSigningKey)?;
+        self.add_protected(cookie, ctx, CookieProtection::Signed(key))
+    }
+    // The policy is only attached if the signature is valid.
+    // None if no CookieSigningKey is managed, since nothing can be verified.
+    pub fn get_signed<P: FrontendPolicy>(&self, name: &str) -> Option<PConCookie<'a, P>> {
+        let key = self.signing_key()?;
+        let cookie = self.jar.get(name)?;
+        let mut signed = cookie::CookieJar::new();
+        signed.add_original(cookie.clone());
+        match signed.signed(key).get(name) {
+            None => None,
+            Some(cookie) => {
+                let p = P::from_cookie(name, &cookie, self.request);
+                Some(PConCookie {
+                    cookie: PConCookieEnum::Verified(cookie, p, CookieProtection::Signed(key)),
+                })
+            }
+        }
+    }
+
     pub fn remove<P: FrontendPolicy>(&self, cookie: PConCookie<'static, P>) {
         match cookie.cookie {
             PConCookieEnum::Read(cookie, _) => self.jar.remove(cookie.clone()),
+            #[cfg(feature = "secrets")]
+            PConCookieEnum::Verified(cookie, _, CookieProtection::Private) => {
+                self.jar.remove_private(cookie)
+            }
+            PConCookieEnum::Verified(cookie, _, _) => self.jar.remove(cookie),
             PConCookieEnum::Write(_, _) => {
                 unreachable!("Get the cookie using get then remove it")
             }
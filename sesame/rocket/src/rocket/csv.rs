@@ -0,0 +1,125 @@
+use chrono::{NaiveDate, NaiveDateTime, NaiveTime};
+use rocket::http::ContentType;
+use serde_json::Value;
+
+use sesame::context::{Context, ContextData};
+use sesame::extensions::ExtensionContext;
+use sesame::pcon::PCon;
+use sesame::policy::{AnyPolicyable, Reason};
+
+use crate::rocket::{
+    OutputPConValue, PConRequest, PConResponder, PConResponse, PConResponseResult, ResponsePConJson,
+};
+
+// Trait for flattening data into CSV rows, every cell is checked on its own.
+pub trait ResponsePConCsv {
+    // Names of the columns, single values have one unnamed column.
+    fn csv_columns() -> Vec<String>;
+    // Exactly one cell per column.
+    fn to_csv_cells(self) -> Vec<OutputPConValue>;
+}
+
+// Single values are a single cell.
+macro_rules! impl_base_types {
+    ($T: ty) => {
+        impl ResponsePConCsv for $T {
+            fn csv_columns() -> Vec<String> {
+                vec![String::new()]
+            }
+            fn to_csv_cells(self) -> Vec<OutputPConValue> {
+                vec![self.to_json()]
+            }
+        }
+    };
+}
+impl_base_types!(String);
+impl_base_types!(bool);
+impl_base_types!(u64);
+impl_base_types!(i64);
+impl_base_types!(f64);
+impl_base_types!(i32);
+impl_base_types!(u32);
+impl_base_types!(NaiveDateTime);
+impl_base_types!(NaiveDate);
+impl_base_types!(NaiveTime);
+
+impl<T: ResponsePConJson, P: AnyPolicyable> ResponsePConCsv for PCon<T, P> {
+    fn csv_columns() -> Vec<String> {
+        vec![String::new()]
+    }
+    fn to_csv_cells(self) -> Vec<OutputPConValue> {
+        vec![self.to_json()]
+    }
+}
+
+// None is a row of empty cells.
+impl<T: ResponsePConCsv> ResponsePConCsv for Option<T> {
+    fn csv_columns() -> Vec<String> {
+        T::csv_columns()
+    }
+    fn to_csv_cells(self) -> Vec<OutputPConValue> {
+        match self {
+            None => T::csv_columns()
+                .into_iter()
+                .map(|_| OutputPConValue::Value(Value::Null))
+                .collect(),
+            Some(v) => v.to_csv_cells(),
+        }
+    }
+}
+
+// Endpoints can return (Vec<T: ResponsePConCsv>, Context), which Sesame turns into a CSV file
+// with a header line, after checking every cell with Reason::Response.
+// Nothing is sent if any check fails.
+pub struct PConCsv<T: ResponsePConCsv, D: ContextData>(pub Vec<T>, pub Context<D>);
+impl<T: ResponsePConCsv, D: ContextData> From<(Vec<T>, Context<D>)> for PConCsv<T, D> {
+    fn from((rows, context): (Vec<T>, Context<D>)) -> Self {
+        Self(rows, context)
+    }
+}
+
+impl<'a, 'r, 'o: 'r, T: ResponsePConCsv, D: ContextData> PConResponder<'a, 'r, 'o>
+    for PConCsv<T, D>
+{
+    fn respond_to(self, request: PConRequest<'a, 'r>) -> PConResponseResult<'o> {
+        let (rows, context) = (self.0, self.1);
+        let context = ExtensionContext::new(context);
+        let mut body = csv_line(T::csv_columns().into_iter().map(Value::String));
+        for row in rows {
+            let mut cells = Vec::new();
+            for cell in row.to_csv_cells() {
+                match cell.transform_with(&context, &Reason::Response) {
+                    Err(err) => return err.respond_to(request),
+                    Ok(cell) => cells.push(cell),
+                }
+            }
+            body.push_str(&csv_line(cells));
+        }
+        let content_type = ContentType::new("text", "csv");
+        let result =
+            rocket::response::Responder::respond_to((content_type, body), request.get_request())?;
+        Ok(PConResponse::new(result))
+    }
+}
+
+// Turns checked cells into a line of CSV (RFC 4180).
+pub(crate) fn csv_line<I: IntoIterator<Item = Value>>(cells: I) -> String {
+    let cells: Vec<String> = cells.into_iter().map(csv_cell).collect();
+    format!("{}\r\n", cells.join(","))
+}
+
+// Strings that spreadsheets would run as formulas (CSV injection) are prefixed with '.
+// Numbers are left as is, a negative number is not a formula.
+fn csv_cell(value: Value) -> String {
+    let cell = match value {
+        Value::Null => String::new(),
+        Value::String(s) if s.starts_with(['=', '+', '-', '@', '\t', '\r']) => format!("'{}", s),
+        Value::String(s) => s,
+        value => value.to_string(),
+    };
+    if cell.contains(|c: char| matches!(c, ',' | '"' | '\n' | '\r')) {
+        format!("\"{}\"", cell.replace('"', "\"\""))
+    } else {
+        cell
+    }
+}
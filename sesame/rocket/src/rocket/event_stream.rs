@@ -0,0 +1,129 @@
+use futures::future::ready;
+use futures::{Stream, StreamExt};
+use rocket::response::stream::{Event, EventStream};
+
+use sesame::context::{Context, ContextData};
+use sesame::extensions::{ExtensionContext, SesameExtension};
+use sesame::pcon::PCon;
+use sesame::policy::{Policy, Reason};
+
+use crate::error::SesameRenderResult;
+use crate::render::PConRender;
+use crate::rocket::{
+    PConRequest, PConResponder, PConResponse, PConResponseResult, ResponsePConJson,
+};
+
+// Items of a PConEventStream, turned into the data of an event after checking every PCon inside.
+pub trait PConEventData {
+    fn into_event_data(
+        self,
+        context: &ExtensionContext,
+        reason: &Reason<'_>,
+    ) -> SesameRenderResult<String>;
+}
+
+// Protected text is sent as is.
+struct EventExtension {}
+impl<P: Policy> SesameExtension<String, P, String> for EventExtension {
+    fn apply(&mut self, data: String, _policy: P) -> String {
+        data
+    }
+}
+impl<P: Policy> PConEventData for PCon<String, P> {
+    fn into_event_data(
+        self,
+        context: &ExtensionContext,
+        reason: &Reason<'_>,
+    ) -> SesameRenderResult<String> {
+        Ok(self.checked_extension(&mut EventExtension {}, context, reason.clone())?)
+    }
+}
+
+// Sent as JSON.
+pub struct PConEventJson<T: ResponsePConJson>(pub T);
+impl<T: ResponsePConJson> PConEventData for PConEventJson<T> {
+    fn into_event_data(
+        self,
+        context: &ExtensionContext,
+        reason: &Reason<'_>,
+    ) -> SesameRenderResult<String> {
+        Ok(self
+            .0
+            .to_json()
+            .transform_with(context, reason)?
+            .to_string())
+    }
+}
+
+// Rendered like template parameters, then sent as JSON.
+pub struct PConEventRender<T: PConRender>(pub T);
+impl<T: PConRender> PConEventData for PConEventRender<T> {
+    fn into_event_data(
+        self,
+        context: &ExtensionContext,
+        reason: &Reason<'_>,
+    ) -> SesameRenderResult<String> {
+        let value = self.0.render().transform_with(context, reason)?;
+        Ok(serde_json::to_string(&value)?)
+    }
+}
+
+// What to do with events that fail their policy check.
+#[derive(Clone, Debug)]
+pub enum DeniedEvents {
+    // Skip the event.
+    Drop,
+    // Send the event with this data instead.
+    Redact(String),
+}
+
+// Server-sent events responder.
+// Every event is checked against the context with Reason::Stream(path) right before it is sent,
+// so events produced long after the request (e.g. from a channel) are still checked.
+pub struct PConEventStream<S, D: ContextData + Clone> {
+    stream: S,
+    context: Context<D>,
+    denied: DeniedEvents,
+}
+impl<S, D: ContextData + Clone> PConEventStream<S, D> {
+    pub fn new(stream: S, context: Context<D>) -> Self {
+        Self {
+            stream,
+            context,
+            denied: DeniedEvents::Drop,
+        }
+    }
+    pub fn denied(mut self, denied: DeniedEvents) -> Self {
+        self.denied = denied;
+        self
+    }
+}
+
+impl<'a, 'r, 'o: 'r, S, T, D> PConResponder<'a, 'r, 'o> for PConEventStream<S, D>
+where
+    'a: 'o,
+    S: Stream<Item = T> + Send + 'o,
+    T: PConEventData,
+    D: ContextData + Clone,
+{
+    fn respond_to(self, request: PConRequest<'a, 'r>) -> PConResponseResult<'o> {
+        let request = request.get_request();
+        let path = request.uri().path().to_string();
+        let context = self.context;
+        let denied = self.denied;
+        let events = self.stream.filter_map(move |item| {
+            let reason = Reason::Stream(&path);
+            let context = ExtensionContext::new(context.clone());
+            let event = match item.into_event_data(&context, &reason) {
+                Ok(data) => Some(Event::data(data)),
+                Err(_) => match &denied {
+                    DeniedEvents::Drop => None,
+                    DeniedEvents::Redact(data) => Some(Event::data(data.clone())),
+                },
+            };
+            ready(event)
+        });
+        let response = rocket::response::Responder::respond_to(EventStream::from(events), request)?;
+        Ok(PConResponse::new(response))
+    }
+}
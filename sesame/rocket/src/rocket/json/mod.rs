@@ -5,8 +5,8 @@ mod request;
 mod response;
 
 pub use json::*;
-pub use request::*;
 pub use response::*;
+pub use sesame_web::json::*;
 
 // Can use this as an argument or return this from route functions.
 pub struct PConJson<T>(pub T);
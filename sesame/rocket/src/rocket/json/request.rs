@@ -1,33 +1,8 @@
 use crate::rocket::{
-    FromPConData, PConData, PConDataOutcome, PConJson, PConRequest, RequestPConJson,
+    FromPConData, InputPConValue, PConData, PConDataOutcome, PConJson, PConRequest,
+    RequestPConJson,
 };
 use rocket::data::ByteUnit;
-use serde_json::Value;
-
-// Wrapper around serde_json::Value created from requests and consumed by our types.
-pub struct InputPConValue {
-    pub(super) value: Value,
-}
-impl InputPConValue {
-    pub fn new(value: Value) -> Self {
-        Self { value }
-    }
-    pub fn get(&mut self, key: &str) -> Result<InputPConValue, &'static str> {
-        match self.value.as_object_mut() {
-            None => Err("Bad JSON"),
-            Some(map) => match map.remove(key) {
-                None => Ok(InputPConValue::new(Value::Null)),
-                Some(val) => Ok(InputPConValue::new(val)),
-            },
-        }
-    }
-    pub fn into_json<T: RequestPConJson>(
-        self,
-        request: PConRequest<'_, '_>,
-    ) -> Result<T, &'static str> {
-        T::from_json(self, request)
-    }
-}
 
 async fn parse_body<'a, 'r>(
     r: PConRequest<'a, 'r>,
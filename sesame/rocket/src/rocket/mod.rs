@@ -1,32 +1,40 @@
 mod cookie;
 mod cors;
+mod csv;
 mod data;
+mod event_stream;
 mod form;
 mod headers;
 mod json;
 mod redirect;
-mod redirect_parameters;
 mod request;
 mod response;
 mod rocket;
 mod route;
+mod streamed;
+mod temp_file;
 mod template;
+mod websocket;
 
 pub use crate::rocket::cookie::*;
 pub use crate::rocket::cors::*;
+pub use crate::rocket::csv::*;
 pub use crate::rocket::data::*;
+pub use crate::rocket::event_stream::*;
 pub use crate::rocket::form::*;
 pub use crate::rocket::headers::*;
 pub use crate::rocket::json::*;
 pub use crate::rocket::redirect::*;
-pub use crate::rocket::redirect_parameters::*;
 pub use crate::rocket::request::*;
 pub use crate::rocket::response::*;
 pub use crate::rocket::rocket::*;
 pub use crate::rocket::route::*;
+pub use crate::rocket::streamed::*;
+pub use crate::rocket::temp_file::*;
 pub use crate::rocket::template::*;
+pub use crate::rocket::websocket::*;
 
 #[cfg(feature = "derive")]
 pub use sesame_derive::{
-    get, post, route, routes, FromPConForm, RequestPConJson, ResponsePConJson,
+    get, post, route, routes, FromPConForm, RequestPConJson, ResponsePConCsv, ResponsePConJson,
 };
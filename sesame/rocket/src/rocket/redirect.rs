@@ -1,14 +1,13 @@
-use crate::rocket::redirect_parameters::RedirectParams;
 use crate::rocket::request::PConRequest;
 use crate::rocket::response::{PConResponder, PConResponse, PConResponseResult};
 use std::convert::TryInto;
 
-use crate::rocket::IntoRedirectParams;
-use dynfmt::{Format, SimpleCurlyFormat};
 use rocket::http::uri::Reference;
 use sesame::context::{Context, ContextData};
 use sesame::error::SesameResult;
 
+pub use sesame_web::redirect::{IntoRedirectParams, RedirectParam, RedirectParams};
+
 // A redirect response.
 pub struct PConRedirect {
     redirect: rocket::response::Redirect,
@@ -20,9 +19,8 @@ impl PConRedirect {
         context: Context<D>,
     ) -> SesameResult<Self> {
         let params: RedirectParams = params.into(url, context)?;
-        let formatted_str = SimpleCurlyFormat.format(url, params.parameters).unwrap();
         Ok(PConRedirect {
-            redirect: rocket::response::Redirect::to(Into::<String>::into(formatted_str)),
+            redirect: rocket::response::Redirect::to(params.into_url()),
         })
     }
     pub fn to2<U: TryInto<Reference<'static>>>(url: U) -> Self {
@@ -20,6 +20,12 @@ impl SesameRocket<rocket::Build> {
             frontend: rocket::build(),
         }
     }
+    // Or start with a custom configuration (e.g. a figment with a different port).
+    pub fn custom<T: figment::Provider>(provider: T) -> Self {
+        SesameRocket {
+            frontend: rocket::custom(provider),
+        }
+    }
     // Finish building by launching and awaiting result.
     pub async fn launch(self) -> Result<(), rocket::Error> {
         self.frontend.launch().await
@@ -1,5 +1,14 @@
+use rocket::http::Status;
+
+use sesame::consent::Purpose;
+use sesame::context::{Context, ContextData};
+use sesame::extensions::{ExtensionContext, SesameExtension};
+use sesame::pcon::PCon;
+use sesame::policy::{Policy, Reason};
+
+use crate::policy::FrontendPolicy;
 use crate::rocket::data::PConData;
-use crate::rocket::request::PConRequest;
+use crate::rocket::request::{FromPConRequest, PConRequest};
 use crate::rocket::response::PConResponseOutcome;
 
 // The return type of a request's inner lambda handler.
@@ -9,12 +18,74 @@ type PConFuture<'a> = futures::future::BoxFuture<'a, PConResponseOutcome<'a>>;
 pub type SesameRouteHandlerLambda =
     for<'a, 'r> fn(request: PConRequest<'a, 'r>, data: PConData<'a>) -> PConFuture<'a>;
 
+// Checks a route requires before its handler runs, see check_route_policy.
+type PConRequirementFuture<'a> = futures::future::BoxFuture<'a, Result<(), Status>>;
+pub type SesameRouteRequirement =
+    for<'a, 'r> fn(request: PConRequest<'a, 'r>) -> PConRequirementFuture<'a>;
+
 // Our #[get(...)] #[post(...)], etc macros generate a struct with an ::info() function
 // that returns an instance of this.
 pub struct SesameRouteInfo {
     pub method: rocket::http::Method,
     pub uri: &'static str,
     pub handler: SesameRouteHandlerLambda,
+    pub requirement: Option<SesameRouteRequirement>,
+}
+
+// Policies that routes can require, e.g. #[get("/admin", require = AdminPolicy)].
+// The policy is constructed from the request (like any FrontendPolicy), and checked against the
+// context of the route (with context data Data) before the handler runs.
+pub trait RoutePolicy: FrontendPolicy {
+    type Data: ContextData;
+}
+
+// The reason routes check their required policy with, unless they declare a purpose
+// (e.g. purpose = "grading"), in which case Reason::Custom(&Purpose) is used instead.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RouteAccess {
+    pub uri: String,
+}
+
+struct RequirementExtension {}
+impl<P: Policy> SesameExtension<(), P, ()> for RequirementExtension {
+    fn apply(&mut self, _data: (), _policy: P) {}
+}
+
+// Used by the route macros to check the policy a route requires, responding with status if the
+// check fails.
+pub async fn check_route_policy<'a, 'r, P: RoutePolicy>(
+    request: PConRequest<'a, 'r>,
+    purpose: Option<&'static str>,
+    status: u16,
+) -> Result<(), Status>
+where
+    P::Data: FromPConRequest<'a, 'r>,
+{
+    use rocket::outcome::Outcome::*;
+
+    let context = match request.guard::<Context<P::Data>>().await {
+        Success(context) => context,
+        Failure((status, _)) => return Err(status),
+        Forward(_) => return Err(Status::new(status)),
+    };
+
+    let purpose = purpose.map(Purpose::new);
+    let access = RouteAccess {
+        uri: request
+            .route()
+            .map(|route| route.uri.to_string())
+            .unwrap_or_default(),
+    };
+    let reason = match &purpose {
+        Some(purpose) => Reason::Custom(purpose),
+        None => Reason::Custom(&access),
+    };
+
+    let policy = P::from_request(request.get_request());
+    let context = ExtensionContext::new(context);
+    let result =
+        PCon::new((), policy).checked_extension(&mut RequirementExtension {}, &context, reason);
+    result.map_err(|_| Status::new(status))
 }
 
 // SesameRoute is just a wrapper around a regular rocket Route.
@@ -31,7 +102,7 @@ impl From<SesameRouteInfo> for SesameRoute {
             route: rocket::route::Route::new(
                 value.method,
                 value.uri,
-                SesameRouteHandlerWrapper::new(value.handler),
+                SesameRouteHandlerWrapper::new(value.handler, value.requirement),
             ),
         }
     }
@@ -57,13 +128,21 @@ impl From<rocket::fs::FileServer> for SesameRoute {
 // to rocket.
 // This last part removes the PCon protection, but this is OK: application code
 // cannot access this part, as this handler is passed directly to rocket.
+// If the route requires a policy, it is checked before any of this happens.
 #[derive(Clone)]
 struct SesameRouteHandlerWrapper {
     handler: SesameRouteHandlerLambda,
+    requirement: Option<SesameRouteRequirement>,
 }
 impl SesameRouteHandlerWrapper {
-    pub fn new(handler: SesameRouteHandlerLambda) -> Self {
-        SesameRouteHandlerWrapper { handler }
+    pub fn new(
+        handler: SesameRouteHandlerLambda,
+        requirement: Option<SesameRouteRequirement>,
+    ) -> Self {
+        SesameRouteHandlerWrapper {
+            handler,
+            requirement,
+        }
     }
 }
 #[rocket::async_trait]
@@ -73,6 +152,11 @@ impl rocket::route::Handler for SesameRouteHandlerWrapper {
         request: &'a rocket::request::Request<'_>,
         data: rocket::data::Data<'a>,
     ) -> rocket::route::Outcome<'a> {
+        if let Some(requirement) = self.requirement {
+            if let Err(status) = requirement(PConRequest::new(request)).await {
+                return rocket::outcome::Outcome::Failure(status);
+            }
+        }
         let result_future: PConResponseOutcome<'a> =
             (self.handler)(PConRequest::new(request), PConData::new(data)).await;
         match result_future {
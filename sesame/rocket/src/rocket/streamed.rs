@@ -0,0 +1,160 @@
+use futures::future::ready;
+use futures::{stream, Stream, StreamExt};
+use rocket::http::ContentType;
+use rocket::response::stream::TextStream;
+use serde_json::Value;
+
+use sesame::context::{Context, ContextData};
+use sesame::extensions::ExtensionContext;
+use sesame::policy::Reason;
+
+use crate::rocket::csv::csv_line;
+use crate::rocket::{
+    OutputPConValue, PConRequest, PConResponder, PConResponse, PConResponseResult, ResponsePConCsv,
+    ResponsePConJson,
+};
+
+// What to do with values that fail their policy check in the middle of a streamed response.
+#[derive(Clone, Debug)]
+pub enum DeniedValues {
+    // End the response with an error marker in place of the row containing the value, since
+    // the status and the rows before it were already sent: an {"error": ...} object for JSON
+    // Lines, and a "#ERROR: ..." trailer line for CSV.
+    Fail,
+    // Send this string instead of the value.
+    Redact(String),
+}
+impl DeniedValues {
+    fn transform(&self, value: OutputPConValue, context: &ExtensionContext) -> Option<Value> {
+        let reason = Reason::Response;
+        match self {
+            DeniedValues::Fail => value.transform_with(context, &reason).ok(),
+            DeniedValues::Redact(redacted) => {
+                let redacted = Value::String(redacted.clone());
+                Some(value.transform_redacted(context, &reason, &redacted))
+            }
+        }
+    }
+}
+
+// Terminal markers for rows that fail their policy check with DeniedValues::Fail.
+const NDJSON_ERROR: &str = "{\"error\":\"policy check failed\"}\n";
+const CSV_ERROR: &str = "#ERROR: policy check failed\r\n";
+
+// Body made of lines, ending right after the first Err line (the error marker).
+fn respond_with_lines<'a, 'r, 'o: 'r, S>(
+    lines: S,
+    content_type: ContentType,
+    request: PConRequest<'a, 'r>,
+) -> PConResponseResult<'o>
+where
+    'a: 'o,
+    S: Stream<Item = Result<String, &'static str>> + Send + 'o,
+{
+    let lines = lines.scan(false, |failed, line| {
+        if *failed {
+            return ready(None);
+        }
+        ready(Some(match line {
+            Ok(line) => line,
+            Err(marker) => {
+                *failed = true;
+                String::from(marker)
+            }
+        }))
+    });
+    let mut response =
+        rocket::response::Responder::respond_to(TextStream::from(lines), request.get_request())?;
+    response.set_header(content_type);
+    Ok(PConResponse::new(response))
+}
+
+// Streams rows as JSON Lines without holding the whole response in memory.
+// Rows are checked with Reason::Response one at a time, as the body is sent, so the stream
+// can be lazy (e.g. rows read from the database as they are needed).
+pub struct PConNdjsonStream<S, D: ContextData + Clone> {
+    stream: S,
+    context: Context<D>,
+    denied: DeniedValues,
+}
+impl<S, D: ContextData + Clone> PConNdjsonStream<S, D> {
+    pub fn new(stream: S, context: Context<D>) -> Self {
+        Self {
+            stream,
+            context,
+            denied: DeniedValues::Fail,
+        }
+    }
+    pub fn denied(mut self, denied: DeniedValues) -> Self {
+        self.denied = denied;
+        self
+    }
+}
+
+impl<'a, 'r, 'o: 'r, S, T, D> PConResponder<'a, 'r, 'o> for PConNdjsonStream<S, D>
+where
+    'a: 'o,
+    S: Stream<Item = T> + Send + 'o,
+    T: ResponsePConJson,
+    D: ContextData + Clone,
+{
+    fn respond_to(self, request: PConRequest<'a, 'r>) -> PConResponseResult<'o> {
+        let context = self.context;
+        let denied = self.denied;
+        let lines = self.stream.map(move |row| -> Result<String, &'static str> {
+            let context = ExtensionContext::new(context.clone());
+            let row = denied
+                .transform(row.to_json(), &context)
+                .ok_or(NDJSON_ERROR)?;
+            Ok(format!("{}\n", row))
+        });
+        respond_with_lines(lines, ContentType::new("application", "x-ndjson"), request)
+    }
+}
+
+// Streams rows as CSV, starting with a header line.
+// Every cell is checked with Reason::Response, one row at a time, as the body is sent.
+pub struct PConCsvStream<S, D: ContextData + Clone> {
+    stream: S,
+    context: Context<D>,
+    denied: DeniedValues,
+}
+impl<S, D: ContextData + Clone> PConCsvStream<S, D> {
+    pub fn new(stream: S, context: Context<D>) -> Self {
+        Self {
+            stream,
+            context,
+            denied: DeniedValues::Fail,
+        }
+    }
+    pub fn denied(mut self, denied: DeniedValues) -> Self {
+        self.denied = denied;
+        self
+    }
+}
+
+impl<'a, 'r, 'o: 'r, S, T, D> PConResponder<'a, 'r, 'o> for PConCsvStream<S, D>
+where
+    'a: 'o,
+    S: Stream<Item = T> + Send + 'o,
+    T: ResponsePConCsv,
+    D: ContextData + Clone,
+{
+    fn respond_to(self, request: PConRequest<'a, 'r>) -> PConResponseResult<'o> {
+        let context = self.context;
+        let denied = self.denied;
+        let header = csv_line(T::csv_columns().into_iter().map(Value::String));
+        let rows = self.stream.map(move |row| -> Result<String, &'static str> {
+            let context = ExtensionContext::new(context.clone());
+            let cells = row
+                .to_csv_cells()
+                .into_iter()
+                .map(|cell| denied.transform(cell, &context))
+                .collect::<Option<Vec<_>>>()
+                .ok_or(CSV_ERROR)?;
+            Ok(csv_line(cells))
+        });
+        let lines = stream::once(ready(Ok(header))).chain(rows);
+        respond_with_lines(lines, ContentType::new("text", "csv"), request)
+    }
+}
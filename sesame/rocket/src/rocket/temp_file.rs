@@ -0,0 +1,193 @@
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+use rand::distributions::Alphanumeric;
+use rand::Rng;
+
+use rocket::data::Limits;
+use rocket::form::error::ErrorKind;
+use rocket::http::ContentType;
+use rocket::tokio::io::AsyncWriteExt;
+
+use sesame::context::{Context, ContextData};
+use sesame::error::SesameError;
+use sesame::extensions::{ExtensionContext, SesameExtension};
+use sesame::pcon::PCon;
+use sesame::policy::{Policy, Reason, RefPolicy};
+
+use crate::policy::FrontendPolicy;
+use crate::rocket::form::{FromPConFormField, PConDataField, PConFormResult, PConValueField};
+use crate::rocket::request::PConRequest;
+
+// Errors when persisting a PConTempFile.
+#[derive(Debug)]
+pub enum PConPersistError {
+    SesameError(SesameError),
+    IoError(std::io::Error),
+}
+impl From<SesameError> for PConPersistError {
+    fn from(e: SesameError) -> Self {
+        PConPersistError::SesameError(e)
+    }
+}
+impl From<std::io::Error> for PConPersistError {
+    fn from(e: std::io::Error) -> Self {
+        PConPersistError::IoError(e)
+    }
+}
+
+// Extension that releases the location of a file after its policy check.
+struct PersistExtension {}
+impl<'a, P: Policy> SesameExtension<&'a PathBuf, P, &'a PathBuf> for PersistExtension {
+    fn apply(&mut self, data: &'a PathBuf, _policy: P) -> &'a PathBuf {
+        data
+    }
+}
+
+// Where the content of a PConTempFile is, deleted on drop unless it was persisted.
+struct TempPath {
+    path: PathBuf,
+    persisted: bool,
+}
+impl TempPath {
+    // A new (empty) file with a random name in rocket's temporary directory.
+    fn create(dir: &Path) -> std::io::Result<(Self, std::fs::File)> {
+        let name: String = rand::thread_rng()
+            .sample_iter(&Alphanumeric)
+            .take(32)
+            .map(char::from)
+            .collect();
+        let path = dir.join(format!("sesame-upload-{}", name));
+        let file = std::fs::OpenOptions::new()
+            .write(true)
+            .create_new(true)
+            .open(&path)?;
+        Ok((
+            TempPath {
+                path,
+                persisted: false,
+            },
+            file,
+        ))
+    }
+}
+impl Drop for TempPath {
+    fn drop(&mut self) {
+        if !self.persisted {
+            let _ = std::fs::remove_file(&self.path);
+        }
+    }
+}
+
+// An uploaded file (e.g. a multipart data field), our version of rocket's TempFile.
+// The content is streamed to a temporary file (up to the "file" limit) instead of memory, and
+// the file name, content type, and content are all protected by the policy P constructs from the
+// request.
+pub struct PConTempFile<P: FrontendPolicy> {
+    file_name: Option<PCon<String, P>>,
+    content_type: PCon<ContentType, P>,
+    policy: P,
+    path: TempPath,
+}
+
+impl<P: FrontendPolicy> PConTempFile<P> {
+    // The sanitized file name (without extension) given by the client, if any.
+    pub fn file_name(&self) -> Option<&PCon<String, P>> {
+        self.file_name.as_ref()
+    }
+    pub fn content_type(&self) -> &PCon<ContentType, P> {
+        &self.content_type
+    }
+    // Read the whole content into memory.
+    pub async fn into_bytes(self) -> std::io::Result<PCon<Vec<u8>, P>> {
+        let content = rocket::tokio::fs::read(&self.path.path).await?;
+        Ok(PCon::new(content, self.policy))
+    }
+
+    // Move the file to path, after checking the policy with Reason::FileWrite(path).
+    // Nothing is written if the check fails.
+    pub async fn persist_to<D: ContextData + Clone, T: AsRef<Path>>(
+        &mut self,
+        path: T,
+        context: Context<D>,
+    ) -> Result<(), PConPersistError> {
+        let path = path.as_ref();
+        // ExtensionContext is not Send, so it must not be held across the awaits below.
+        let from = {
+            let context = ExtensionContext::new(context);
+            let target = path.to_string_lossy();
+            let reason = Reason::FileWrite(&target);
+            let pcon = PCon::new(&self.path.path, RefPolicy::new(&self.policy));
+            pcon.checked_extension(&mut PersistExtension {}, &context, reason)?
+                .clone()
+        };
+        // Renaming fails across file systems, copy then instead.
+        if rocket::tokio::fs::rename(&from, path).await.is_err() {
+            rocket::tokio::fs::copy(&from, path).await?;
+            let _ = rocket::tokio::fs::remove_file(&from).await;
+        }
+        self.path.path = path.to_path_buf();
+        self.path.persisted = true;
+        Ok(())
+    }
+}
+
+#[rocket::async_trait]
+impl<'a, 'r, P: FrontendPolicy> FromPConFormField<'a, 'r> for PConTempFile<P> {
+    // Like rocket, a plain value is accepted as a text file with no name.
+    fn from_pcon_value(
+        field: PConValueField<'a>,
+        req: PConRequest<'a, 'r>,
+    ) -> PConFormResult<'a, Self> {
+        let request = req.get_request();
+        let (path, mut file) =
+            TempPath::create(&request.rocket().config().temp_dir).map_err(ErrorKind::Io)?;
+        file.write_all(field.value.as_bytes())
+            .map_err(ErrorKind::Io)?;
+        Ok(PConTempFile {
+            file_name: None,
+            content_type: PCon::new(ContentType::Text, P::from_request(request)),
+            policy: P::from_request(request),
+            path,
+        })
+    }
+
+    async fn from_pcon_data(
+        field: PConDataField<'a, 'r>,
+        req: PConRequest<'a, 'r>,
+    ) -> PConFormResult<'a, Self> {
+        let request = req.get_request();
+        let limit = request.limits().get("file").unwrap_or(Limits::FILE);
+        let file_name = field
+            .file_name
+            .and_then(|name| name.as_str())
+            .map(|name| PCon::new(String::from(name), P::from_request(request)));
+        let content_type = PCon::new(field.content_type, P::from_request(request));
+
+        // The temporary file is deleted if anything below fails.
+        let (path, file) =
+            TempPath::create(&request.rocket().config().temp_dir).map_err(ErrorKind::Io)?;
+        let mut file = rocket::tokio::fs::File::from_std(file);
+        let written = field
+            .data
+            .get_data()
+            .open(limit)
+            .stream_to(&mut file)
+            .await
+            .map_err(ErrorKind::Io)?;
+        if !written.complete {
+            Err(ErrorKind::InvalidLength {
+                min: None,
+                max: Some(limit.as_u64()),
+            })?;
+        }
+        file.flush().await.map_err(ErrorKind::Io)?;
+
+        Ok(PConTempFile {
+            file_name,
+            content_type,
+            policy: P::from_request(request),
+            path,
+        })
+    }
+}
@@ -0,0 +1,212 @@
+use std::fmt::{Display, Formatter};
+use std::pin::Pin;
+
+use futures::future::BoxFuture;
+use futures::{SinkExt, StreamExt};
+use rocket::data::{IoHandler, IoStream};
+use rocket::http::Status;
+use tokio_tungstenite::tungstenite::handshake::derive_accept_key;
+use tokio_tungstenite::tungstenite::protocol::Role;
+use tokio_tungstenite::tungstenite::{Error, Message};
+use tokio_tungstenite::WebSocketStream;
+
+use sesame::context::{Context, ContextData};
+use sesame::error::SesameError;
+use sesame::extensions::{ExtensionContext, SesameExtension};
+use sesame::pcon::PCon;
+use sesame::policy::{Policy, Reason};
+
+use crate::policy::FrontendPolicy;
+use crate::rocket::{
+    PConRequest, PConResponder, PConResponse, PConResponseResult, ResponsePConJson,
+};
+
+// Errors when receiving from or sending over a PConWebSocketStream.
+#[derive(Debug)]
+pub enum PConWebSocketError {
+    SesameError(SesameError),
+    TransportError(Error),
+}
+impl From<SesameError> for PConWebSocketError {
+    fn from(e: SesameError) -> Self {
+        PConWebSocketError::SesameError(e)
+    }
+}
+impl From<Error> for PConWebSocketError {
+    fn from(e: Error) -> Self {
+        PConWebSocketError::TransportError(e)
+    }
+}
+impl Display for PConWebSocketError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PConWebSocketError::SesameError(e) => write!(f, "{}", e),
+            PConWebSocketError::TransportError(e) => write!(f, "{}", e),
+        }
+    }
+}
+impl std::error::Error for PConWebSocketError {}
+
+pub type PConWebSocketResult = Result<(), PConWebSocketError>;
+
+// Extension that releases the text of a message after its policy check.
+struct MessageExtension {}
+impl<P: Policy> SesameExtension<String, P, String> for MessageExtension {
+    fn apply(&mut self, data: String, _policy: P) -> String {
+        data
+    }
+}
+
+// A WebSocket upgrade request.
+// Endpoints create it from the request and the context outgoing messages are checked against, and
+// respond with channel(..), which upgrades the connection and runs the given handler on it.
+pub struct PConWebSocket<P: FrontendPolicy + Clone, D: ContextData + Clone> {
+    key: String,
+    policy: P,
+    context: Context<D>,
+    path: String,
+}
+
+impl<P, D> PConWebSocket<P, D>
+where
+    P: FrontendPolicy + Clone + 'static,
+    D: ContextData + Clone,
+{
+    // Fails with BadRequest if the request does not ask to be upgraded to a WebSocket.
+    pub fn new(request: PConRequest<'_, '_>, context: Context<D>) -> Result<Self, Status> {
+        let request = request.get_request();
+        let headers = request.headers();
+        let upgrade = headers
+            .get("Upgrade")
+            .flat_map(|value| value.split(','))
+            .any(|value| value.trim().eq_ignore_ascii_case("websocket"));
+        let version = headers.get_one("Sec-WebSocket-Version") == Some("13");
+        match headers.get_one("Sec-WebSocket-Key") {
+            Some(key) if upgrade && version => Ok(Self {
+                key: String::from(key),
+                policy: P::from_request(request),
+                context,
+                path: request.uri().path().to_string(),
+            }),
+            _ => Err(Status::BadRequest),
+        }
+    }
+
+    // Respond by upgrading the connection, then hand the stream to handler.
+    pub fn channel<F>(self, handler: F) -> PConWebSocketChannel
+    where
+        F: FnOnce(PConWebSocketStream<P, D>) -> BoxFuture<'static, PConWebSocketResult>
+            + Send
+            + 'static,
+    {
+        let PConWebSocket {
+            key,
+            policy,
+            context,
+            path,
+        } = self;
+        PConWebSocketChannel {
+            key,
+            handler: Box::new(move |stream| {
+                handler(PConWebSocketStream {
+                    stream,
+                    policy,
+                    context,
+                    path,
+                })
+            }),
+        }
+    }
+}
+
+// Responder that performs the WebSocket handshake and runs the handler once upgraded.
+pub struct PConWebSocketChannel {
+    key: String,
+    handler: Box<
+        dyn FnOnce(WebSocketStream<IoStream>) -> BoxFuture<'static, PConWebSocketResult> + Send,
+    >,
+}
+
+impl<'a, 'r, 'o: 'r> PConResponder<'a, 'r, 'o> for PConWebSocketChannel {
+    fn respond_to(self, _request: PConRequest<'a, 'r>) -> PConResponseResult<'o> {
+        // Rocket sets the status and the Connection/Upgrade headers when upgrading.
+        let response = rocket::response::Response::build()
+            .raw_header(
+                "Sec-WebSocket-Accept",
+                derive_accept_key(self.key.as_bytes()),
+            )
+            .upgrade("websocket", self)
+            .finalize();
+        Ok(PConResponse::new(response))
+    }
+}
+
+#[rocket::async_trait]
+impl IoHandler for PConWebSocketChannel {
+    async fn io(self: Pin<Box<Self>>, io: IoStream) -> std::io::Result<()> {
+        let channel = Pin::into_inner(self);
+        let stream = WebSocketStream::from_raw_socket(io, Role::Server, None).await;
+        (channel.handler)(stream)
+            .await
+            .map_err(|e| std::io::Error::new(std::io::ErrorKind::Other, e))
+    }
+}
+
+// An upgraded WebSocket connection whose messages are protected.
+// Inbound text frames are PCons with the policy P constructs from the upgrade request, and every
+// outbound message is checked against (a copy of) the context of the connection with
+// Reason::Stream(path).
+pub struct PConWebSocketStream<P: FrontendPolicy + Clone, D: ContextData + Clone> {
+    stream: WebSocketStream<IoStream>,
+    policy: P,
+    context: Context<D>,
+    path: String,
+}
+
+impl<P: FrontendPolicy + Clone, D: ContextData + Clone> PConWebSocketStream<P, D> {
+    // The next text message from the client, None once the connection is closed.
+    // Other frames are skipped (pings are answered by the underlying stream).
+    pub async fn recv(&mut self) -> Option<Result<PCon<String, P>, PConWebSocketError>> {
+        loop {
+            match self.stream.next().await? {
+                Ok(Message::Text(message)) => {
+                    return Some(Ok(PCon::new(message, self.policy.clone())))
+                }
+                Ok(Message::Close(_)) => return None,
+                Ok(_) => continue,
+                Err(Error::ConnectionClosed) => return None,
+                Err(e) => return Some(Err(e.into())),
+            }
+        }
+    }
+
+    // Send protected text as is.
+    pub async fn send_text<PT: Policy>(
+        &mut self,
+        message: PCon<String, PT>,
+    ) -> PConWebSocketResult {
+        // ExtensionContext is not Send, so it must not be held across the await below.
+        let message = {
+            let context = ExtensionContext::new(self.context.clone());
+            let reason = Reason::Stream(&self.path);
+            message.checked_extension(&mut MessageExtension {}, &context, reason)?
+        };
+        Ok(self.stream.send(Message::Text(message)).await?)
+    }
+
+    // Send a message as JSON, every PCon inside it is checked.
+    // Nothing is sent if any check fails.
+    pub async fn send<T: ResponsePConJson>(&mut self, message: T) -> PConWebSocketResult {
+        let message = {
+            let context = ExtensionContext::new(self.context.clone());
+            let reason = Reason::Stream(&self.path);
+            message.to_json().transform_with(&context, &reason)?
+        };
+        Ok(self.stream.send(Message::Text(message.to_string())).await?)
+    }
+
+    // Close the connection.
+    pub async fn close(&mut self) -> PConWebSocketResult {
+        Ok(self.stream.close(None).await?)
+    }
+}
@@ -0,0 +1,217 @@
+use std::marker::PhantomData;
+
+use chrono::{Duration, Utc};
+use rand::distributions::Alphanumeric;
+use rand::Rng;
+use rocket::http::{Cookie, SameSite, Status};
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+
+use sesame::context::{Context, ContextData, UnprotectedContext};
+use sesame::error::SesameError;
+use sesame::fold::fold;
+use sesame::pcon::PCon;
+use sesame::policy::{Policy, Reason};
+use sesame::session::{SessionRecord, SessionStore};
+use sesame::SesameType;
+
+use crate::error::{SesameSessionError, SesameSessionResult};
+use crate::policy::FrontendPolicy;
+use crate::rocket::{FromPConRequest, PConRequest, PConRequestOutcome};
+
+// Session configuration, must be managed by rocket, e.g.
+// `.manage(PConSessions::new(InMemorySessionStore::new()))`.
+// Only a random session id is stored in the cookie, the data stays on the server.
+pub struct PConSessions {
+    store: Box<dyn SessionStore>,
+    cookie: String,
+    ttl: Duration,
+    secure: bool,
+}
+impl PConSessions {
+    pub fn new<S: SessionStore + 'static>(store: S) -> Self {
+        Self {
+            store: Box::new(store),
+            cookie: String::from("sesame_session"),
+            ttl: Duration::days(1),
+            secure: true,
+        }
+    }
+    pub fn cookie_name(mut self, name: &str) -> Self {
+        self.cookie = String::from(name);
+        self
+    }
+    // Sessions expire ttl after they were last written.
+    pub fn ttl(mut self, ttl: Duration) -> Self {
+        self.ttl = ttl;
+        self
+    }
+    // The cookie is only sent over https by default, disable for local development over http.
+    pub fn secure(mut self, secure: bool) -> Self {
+        self.secure = secure;
+        self
+    }
+}
+
+fn new_session_id() -> String {
+    rand::thread_rng()
+        .sample_iter(&Alphanumeric)
+        .take(43)
+        .map(char::from)
+        .collect()
+}
+
+// Request guard for the session of the requesting client.
+// Writes are checked against the policy of the data with Reason::Session, reads are protected by
+// the policy P constructs from the request.
+pub struct PConSession<'a, 'r, T: SesameType, P: FrontendPolicy> {
+    request: PConRequest<'a, 'r>,
+    sessions: &'a PConSessions,
+    id: Option<String>,
+    _marker: PhantomData<fn() -> (T, P)>,
+}
+
+impl<'a, 'r, T: SesameType, P: FrontendPolicy> PConSession<'a, 'r, T, P> {
+    fn load(&self) -> SesameSessionResult<Option<SessionRecord>> {
+        let id = match &self.id {
+            None => return Ok(None),
+            Some(id) => id,
+        };
+        match self.sessions.store.load(id) {
+            Err(e) => Err(SesameSessionError::StoreError(e)),
+            Ok(Some(record)) if record.is_expired() => {
+                self.sessions
+                    .store
+                    .delete(id)
+                    .map_err(SesameSessionError::StoreError)?;
+                Ok(None)
+            }
+            Ok(record) => Ok(record),
+        }
+    }
+
+    fn set_cookie(&self, id: &str) {
+        let cookie = Cookie::build(self.sessions.cookie.clone(), String::from(id))
+            .path("/")
+            .http_only(true)
+            .secure(self.sessions.secure)
+            .same_site(SameSite::Lax)
+            .max_age(time::Duration::seconds(self.sessions.ttl.num_seconds()))
+            .finish();
+        self.request.get_request().cookies().add(cookie);
+    }
+
+    // The session data, if there is an unexpired session.
+    pub fn get(&self) -> SesameSessionResult<Option<PCon<T::Out, P>>>
+    where
+        T::Out: DeserializeOwned,
+    {
+        match self.load()? {
+            None => Ok(None),
+            Some(record) => {
+                let data = serde_json::from_str(&record.data)
+                    .map_err(|e| SesameSessionError::StoreError(e.to_string()))?;
+                let policy = P::from_request(self.request.get_request());
+                Ok(Some(PCon::new(data, policy)))
+            }
+        }
+    }
+
+    // Replace the session data, starting a new session if there is none.
+    pub fn set<D: ContextData>(&mut self, data: T, context: Context<D>) -> SesameSessionResult<()>
+    where
+        T::Out: Serialize,
+    {
+        let data = match fold(data) {
+            Ok(data) => data,
+            Err(_) => {
+                return Err(SesameError::SesameTypeFoldFailed(String::from("fold failed")).into())
+            }
+        };
+        let (data, policy) = data.consume();
+        let context = UnprotectedContext::from(context);
+        if !policy.check(&context, Reason::Session(&self.sessions.cookie)) {
+            return Err(SesameError::PolicyCheckFailed(policy.name()).into());
+        }
+
+        let data = serde_json::to_string(&data)
+            .map_err(|e| SesameSessionError::StoreError(e.to_string()))?;
+        // Only reuse the id of an existing session: ids the server did not issue (e.g. planted
+        // in the client's cookie by an attacker) must not be adopted (session fixation).
+        let id = match (&self.id, self.load()?) {
+            (Some(id), Some(_)) => id.clone(),
+            _ => new_session_id(),
+        };
+        let expires = Utc::now().naive_utc() + self.sessions.ttl;
+        self.sessions
+            .store
+            .save(&id, SessionRecord::new(data, expires))
+            .map_err(SesameSessionError::StoreError)?;
+        self.set_cookie(&id);
+        self.id = Some(id);
+        Ok(())
+    }
+
+    // Move the session to a fresh id, e.g. after login, to prevent session fixation.
+    pub fn rotate(&mut self) -> SesameSessionResult<()> {
+        let record = self.load()?;
+        if let Some(old) = &self.id {
+            self.sessions
+                .store
+                .delete(old)
+                .map_err(SesameSessionError::StoreError)?;
+        }
+        self.id = None;
+        if let Some(record) = record {
+            let id = new_session_id();
+            self.sessions
+                .store
+                .save(&id, record)
+                .map_err(SesameSessionError::StoreError)?;
+            self.set_cookie(&id);
+            self.id = Some(id);
+        }
+        Ok(())
+    }
+
+    // End the session, e.g. on logout.
+    pub fn destroy(&mut self) -> SesameSessionResult<()> {
+        if let Some(id) = self.id.take() {
+            self.sessions
+                .store
+                .delete(&id)
+                .map_err(SesameSessionError::StoreError)?;
+        }
+        let cookie = Cookie::build(self.sessions.cookie.clone(), "")
+            .path("/")
+            .finish();
+        self.request.get_request().cookies().remove(cookie);
+        Ok(())
+    }
+}
+
+#[rocket::async_trait]
+impl<'a, 'r, T: SesameType, P: FrontendPolicy> FromPConRequest<'a, 'r>
+    for PConSession<'a, 'r, T, P>
+{
+    type PConError = ();
+    async fn from_pcon_request(
+        request: PConRequest<'a, 'r>,
+    ) -> PConRequestOutcome<Self, Self::PConError> {
+        let rocket_request = request.get_request();
+        let sessions = match rocket_request.rocket().state::<PConSessions>() {
+            None => return PConRequestOutcome::Failure((Status::InternalServerError, ())),
+            Some(sessions) => sessions,
+        };
+        let id = rocket_request
+            .cookies()
+            .get(&sessions.cookie)
+            .map(|cookie| String::from(cookie.value()));
+        PConRequestOutcome::Success(PConSession {
+            request,
+            sessions,
+            id,
+            _marker: PhantomData,
+        })
+    }
+}
@@ -5,6 +5,7 @@ macro_rules! test_route {
             method: ::rocket::http::Method::$method,
             uri: $uri,
             handler: |request, data| ::std::boxed::Box::pin($handler(request, data)),
+            requirement: ::std::option::Option::None,
         })
     };
 }
@@ -0,0 +1,59 @@
+use sesame::consent::{get_consent_store, set_consent_store, InMemoryConsentStore};
+
+use sesame_rocket::consent::{consent_routes, ConsentSubject};
+use sesame_rocket::rocket::SesameRocket;
+use sesame_rocket::testing::SesameClient;
+
+use rocket::http::{Cookie, Status};
+use rocket::Request;
+
+pub struct UserSubject {}
+impl ConsentSubject for UserSubject {
+    fn subject(request: &Request<'_>) -> Option<String> {
+        request
+            .cookies()
+            .get("user")
+            .map(|cookie| String::from(cookie.value()))
+    }
+}
+
+#[test]
+fn test_consent_routes() {
+    set_consent_store(InMemoryConsentStore::new());
+
+    let rocket = SesameRocket::build().mount("/privacy", consent_routes::<UserSubject>());
+    let client = SesameClient::tracked(rocket).expect("valid `Rocket`");
+
+    // Must be authenticated.
+    let response = client.get("/privacy/consent").dispatch();
+    assert_eq!(response.status(), Status::Unauthorized);
+
+    // Grant then revoke.
+    let response = client
+        .post("/privacy/consent/marketing")
+        .cookie(Cookie::new("user", "alice"))
+        .dispatch();
+    assert_eq!(response.status(), Status::Ok);
+    let store = get_consent_store().unwrap();
+    assert!(store.has_consent("alice", "marketing"));
+
+    let response = client
+        .post("/privacy/consent/marketing/revoke")
+        .cookie(Cookie::new("user", "alice"))
+        .dispatch();
+    assert_eq!(response.status(), Status::Ok);
+    assert!(!store.has_consent("alice", "marketing"));
+
+    // Full history is returned.
+    let response = client
+        .get("/privacy/consent")
+        .cookie(Cookie::new("user", "alice"))
+        .dispatch();
+    let history: serde_json::Value =
+        serde_json::from_str(&response.into_string().unwrap()).unwrap();
+    let history = history.as_array().unwrap();
+    assert_eq!(history.len(), 2);
+    assert_eq!(history[0]["purpose"], "marketing");
+    assert_eq!(history[0]["granted"], true);
+    assert_eq!(history[1]["granted"], false);
+}
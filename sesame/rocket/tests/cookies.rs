@@ -0,0 +1,147 @@
+#![cfg(feature = "secrets")]
+
+use sesame::context::{Context, UnprotectedContext};
+use sesame::pcon::PCon;
+use sesame::policy::{Join, Policy, Reason};
+use sesame::verified::VerifiedRegion;
+
+use sesame_rocket::policy::FrontendPolicy;
+use sesame_rocket::rocket::{
+    ContextResponse, CookieSigningKey, PConCookie, PConData, PConRequest, PConResponseOutcome,
+    SesameRocket,
+};
+use sesame_rocket::test_route;
+use sesame_rocket::testing::SesameClient;
+
+use rocket::http::{Cookie, Status};
+use rocket::Request;
+
+// Remembers the value of the cookie it was attached to, so tests can see that policies are only
+// created from decrypted/verified cookies.
+#[derive(Clone)]
+pub struct SessionPolicy {
+    pub value: String,
+}
+impl Join for SessionPolicy {}
+impl Policy for SessionPolicy {
+    fn name(&self) -> String {
+        String::from("SessionPolicy")
+    }
+    fn check(&self, _: &UnprotectedContext, _: Reason) -> bool {
+        !self.value.is_empty()
+    }
+}
+impl FrontendPolicy for SessionPolicy {
+    fn from_request(_request: &'_ Request<'_>) -> Self {
+        SessionPolicy {
+            value: String::new(),
+        }
+    }
+    fn from_cookie<'a, 'r>(
+        _name: &str,
+        cookie: &'a Cookie<'static>,
+        _request: &'a Request<'r>,
+    ) -> Self {
+        SessionPolicy {
+            value: String::from(cookie.value()),
+        }
+    }
+}
+
+pub async fn set<'a, 'r>(
+    request: PConRequest<'a, 'r>,
+    _data: PConData<'a>,
+) -> PConResponseOutcome<'a> {
+    let session = PCon::new(
+        String::from("session-id"),
+        SessionPolicy { value: "ok".into() },
+    );
+    let session = PConCookie::new("session", session);
+    request
+        .cookies()
+        .add_private(session, Context::test(()))
+        .unwrap();
+
+    let user = PCon::new(String::from("kinan"), SessionPolicy { value: "ok".into() });
+    let user = PConCookie::new("user", user);
+    request
+        .cookies()
+        .add_signed(user, Context::test(()))
+        .unwrap();
+
+    // Policy check fails, nothing is added.
+    let bad = PCon::new(String::from("bad"), SessionPolicy { value: "".into() });
+    let bad = PConCookie::new("bad", bad);
+    assert!(request
+        .cookies()
+        .add_private(bad, Context::test(()))
+        .is_err());
+
+    PConResponseOutcome::from(request, String::from("set"))
+}
+
+pub async fn get<'a, 'r>(
+    request: PConRequest<'a, 'r>,
+    _data: PConData<'a>,
+) -> PConResponseOutcome<'a> {
+    let session: Option<PConCookie<SessionPolicy>> = request.cookies().get_private("session");
+    let user: Option<PConCookie<SessionPolicy>> = request.cookies().get_signed("user");
+    let result = match (session, user) {
+        (Some(session), Some(user)) => {
+            let session: PCon<String, SessionPolicy> = session.into();
+            let user: PCon<String, SessionPolicy> = user.into();
+            assert_eq!(session.policy().value, "session-id");
+            assert_eq!(user.policy().value, "kinan");
+            let session = session.into_verified(VerifiedRegion::new(|s| s));
+            let user = user.into_verified(VerifiedRegion::new(|u| u));
+            format!("{} {}", session, user)
+        }
+        _ => String::from("missing"),
+    };
+    let result = PCon::new(result, SessionPolicy { value: "ok".into() });
+    PConResponseOutcome::from(request, ContextResponse::from((result, Context::test(()))))
+}
+
+fn client() -> SesameClient {
+    let rocket = SesameRocket::build()
+        .manage(CookieSigningKey::generate())
+        .mount(
+            "/",
+            vec![test_route!(Get, "/set", set), test_route!(Get, "/get", get)],
+        );
+    SesameClient::tracked(rocket).expect("valid `Rocket`")
+}
+
+#[test]
+fn test_private_and_signed_cookies() {
+    let client = client();
+    let response = client.get("/set").dispatch();
+    assert_eq!(response.status(), Status::new(200));
+
+    // Private cookies are encrypted, signed cookies are readable.
+    let session = response
+        .cookies()
+        .get("session")
+        .unwrap()
+        .value()
+        .to_string();
+    let user = response.cookies().get("user").unwrap().value().to_string();
+    assert!(!session.contains("session-id"));
+    assert!(user.ends_with("kinan") && user != "kinan");
+    assert!(response.cookies().get("bad").is_none());
+
+    let response = client.get("/get").dispatch();
+    assert_eq!(response.status(), Status::new(200));
+    assert_eq!(response.into_string().unwrap(), "session-id kinan");
+}
+
+#[test]
+fn test_tampered_cookies() {
+    let client = client();
+    let response = client
+        .get("/get")
+        .cookie(Cookie::new("session", "session-id"))
+        .cookie(Cookie::new("user", "kinan"))
+        .dispatch();
+    assert_eq!(response.into_string().unwrap(), "missing");
+}
@@ -0,0 +1,177 @@
+use std::collections::HashMap;
+
+use sesame::context::{Context, UnprotectedContext};
+use sesame::pcon::PCon;
+use sesame::policy::{Reason, SimplePolicy};
+
+use sesame_rocket::rocket::{
+    PConCsv, PConData, PConNdjson, PConRequest, PConResponseOutcome, ResponsePConCsv,
+    ResponsePConJson, SesameRocket,
+};
+use sesame_rocket::test_route;
+use sesame_rocket::testing::SesameClient;
+
+use rocket::http::{ContentType, Status};
+
+#[derive(Clone)]
+pub struct HardcodedPolicy(pub bool);
+impl SimplePolicy for HardcodedPolicy {
+    fn simple_name(&self) -> String {
+        String::from("HardcodedPolicy")
+    }
+    fn simple_check(&self, _context: &UnprotectedContext, reason: Reason<'_>) -> bool {
+        self.0 && matches!(reason, Reason::Response)
+    }
+    fn simple_join_direct(&mut self, other: &mut Self) {
+        self.0 = self.0 && other.0;
+    }
+}
+
+#[derive(ResponsePConCsv, ResponsePConJson)]
+pub struct Student {
+    name: PCon<String, HardcodedPolicy>,
+    email: Option<PCon<String, HardcodedPolicy>>,
+}
+
+#[derive(ResponsePConCsv)]
+pub struct GradeRow {
+    student: Student,
+    grade: PCon<u64, HardcodedPolicy>,
+}
+
+fn grades(allowed: bool) -> Vec<GradeRow> {
+    vec![
+        GradeRow {
+            student: Student {
+                name: PCon::new(String::from("Kinan"), HardcodedPolicy(true)),
+                email: Some(PCon::new(
+                    String::from("kinan@brown.edu"),
+                    HardcodedPolicy(true),
+                )),
+            },
+            grade: PCon::new(90, HardcodedPolicy(true)),
+        },
+        GradeRow {
+            student: Student {
+                name: PCon::new(String::from("\"Corinn\""), HardcodedPolicy(true)),
+                email: None,
+            },
+            grade: PCon::new(95, HardcodedPolicy(allowed)),
+        },
+    ]
+}
+
+pub async fn csv<'a, 'r>(
+    request: PConRequest<'a, 'r>,
+    _data: PConData<'a>,
+) -> PConResponseOutcome<'a> {
+    PConResponseOutcome::from(request, PConCsv(grades(true), Context::test(())))
+}
+
+pub async fn formulas<'a, 'r>(
+    request: PConRequest<'a, 'r>,
+    _data: PConData<'a>,
+) -> PConResponseOutcome<'a> {
+    let students = vec![
+        Student {
+            name: PCon::new(String::from("=1+2"), HardcodedPolicy(true)),
+            email: Some(PCon::new(String::from("@evil"), HardcodedPolicy(true))),
+        },
+        Student {
+            name: PCon::new(String::from("-2,+3"), HardcodedPolicy(true)),
+            email: Some(PCon::new(String::from("\tx"), HardcodedPolicy(true))),
+        },
+    ];
+    PConResponseOutcome::from(request, PConCsv(students, Context::test(())))
+}
+
+pub async fn denied<'a, 'r>(
+    request: PConRequest<'a, 'r>,
+    _data: PConData<'a>,
+) -> PConResponseOutcome<'a> {
+    PConResponseOutcome::from(request, PConCsv(grades(false), Context::test(())))
+}
+
+pub async fn ndjson<'a, 'r>(
+    request: PConRequest<'a, 'r>,
+    _data: PConData<'a>,
+) -> PConResponseOutcome<'a> {
+    let students: Vec<Student> = grades(true).into_iter().map(|row| row.student).collect();
+    PConResponseOutcome::from(request, PConNdjson(students, Context::test(())))
+}
+
+fn client() -> SesameClient {
+    let rocket = SesameRocket::build().mount(
+        "/",
+        vec![
+            test_route!(Get, "/csv", csv),
+            test_route!(Get, "/formulas", formulas),
+            test_route!(Get, "/denied", denied),
+            test_route!(Get, "/ndjson", ndjson),
+        ],
+    );
+    SesameClient::tracked(rocket).expect("valid `Rocket`")
+}
+
+#[test]
+fn test_csv_columns() {
+    assert_eq!(
+        GradeRow::csv_columns(),
+        vec!["student.name", "student.email", "grade"]
+    );
+}
+
+#[test]
+fn test_csv() {
+    let client = client();
+    let response = client.get("/csv").dispatch();
+    assert_eq!(response.status(), Status::new(200));
+    assert_eq!(
+        response.content_type(),
+        Some(ContentType::new("text", "csv"))
+    );
+    assert_eq!(
+        response.into_string().unwrap(),
+        "student.name,student.email,grade\r\n\
+        Kinan,kinan@brown.edu,90\r\n\
+        \"\"\"Corinn\"\"\",,95\r\n"
+    );
+}
+
+#[test]
+fn test_csv_formulas() {
+    let client = client();
+    let response = client.get("/formulas").dispatch();
+    assert_eq!(
+        response.into_string().unwrap(),
+        "name,email\r\n\
+        '=1+2,'@evil\r\n\
+        \"'-2,+3\",'\tx\r\n"
+    );
+}
+
+#[test]
+fn test_csv_denied() {
+    let client = client();
+    let response = client.get("/denied").dispatch();
+    assert_eq!(response.status(), Status::new(491));
+}
+
+#[test]
+fn test_ndjson() {
+    let client = client();
+    let response = client.get("/ndjson").dispatch();
+    assert_eq!(response.status(), Status::new(200));
+    let body = response.into_string().unwrap();
+    let lines: Vec<serde_json::Value> = body
+        .lines()
+        .map(|line| serde_json::from_str(line).unwrap())
+        .collect();
+    assert_eq!(
+        lines,
+        vec![
+            serde_json::json!({"name": "Kinan", "email": "kinan@brown.edu"}),
+            serde_json::json!({"name": "\"Corinn\"", "email": null}),
+        ]
+    );
+}
@@ -0,0 +1,89 @@
+use futures::stream;
+
+use sesame::context::{Context, UnprotectedContext};
+use sesame::pcon::PCon;
+use sesame::policy::{Reason, SimplePolicy};
+
+use sesame_rocket::rocket::{
+    DeniedEvents, PConData, PConEventJson, PConEventStream, PConRequest, PConResponseOutcome,
+    SesameRocket,
+};
+use sesame_rocket::test_route;
+use sesame_rocket::testing::SesameClient;
+
+use rocket::http::Status;
+
+#[derive(Clone)]
+pub struct HardcodedPolicy(pub bool);
+impl SimplePolicy for HardcodedPolicy {
+    fn simple_name(&self) -> String {
+        String::from("HardcodedPolicy")
+    }
+    fn simple_check(&self, _context: &UnprotectedContext, reason: Reason<'_>) -> bool {
+        self.0 && matches!(reason, Reason::Stream(_))
+    }
+    fn simple_join_direct(&mut self, other: &mut Self) {
+        self.0 = self.0 && other.0;
+    }
+}
+
+fn events() -> Vec<PCon<String, HardcodedPolicy>> {
+    vec![
+        PCon::new(String::from("first"), HardcodedPolicy(true)),
+        PCon::new(String::from("secret"), HardcodedPolicy(false)),
+        PCon::new(String::from("last"), HardcodedPolicy(true)),
+    ]
+}
+
+pub async fn dropped<'a, 'r>(
+    request: PConRequest<'a, 'r>,
+    _data: PConData<'a>,
+) -> PConResponseOutcome<'a> {
+    let stream = PConEventStream::new(stream::iter(events()), Context::test(()));
+    PConResponseOutcome::from(request, stream)
+}
+
+pub async fn redacted<'a, 'r>(
+    request: PConRequest<'a, 'r>,
+    _data: PConData<'a>,
+) -> PConResponseOutcome<'a> {
+    let events = events().into_iter().map(|e| PConEventJson(vec![e]));
+    let stream = PConEventStream::new(stream::iter(events), Context::test(()))
+        .denied(DeniedEvents::Redact(String::from("redacted")));
+    PConResponseOutcome::from(request, stream)
+}
+
+fn client() -> SesameClient {
+    let rocket = SesameRocket::build().mount(
+        "/",
+        vec![
+            test_route!(Get, "/dropped", dropped),
+            test_route!(Get, "/redacted", redacted),
+        ],
+    );
+    SesameClient::tracked(rocket).expect("valid `Rocket`")
+}
+
+#[test]
+fn test_event_stream_drop() {
+    let client = client();
+    let response = client.get("/dropped").dispatch();
+    assert_eq!(response.status(), Status::new(200));
+    let body = response.into_string().unwrap();
+    assert!(body.contains("first"));
+    assert!(body.contains("last"));
+    assert!(!body.contains("secret"));
+    assert_eq!(body.matches("data:").count(), 2);
+}
+
+#[test]
+fn test_event_stream_redact() {
+    let client = client();
+    let response = client.get("/redacted").dispatch();
+    let body = response.into_string().unwrap();
+    assert!(body.contains("[\"first\"]"));
+    assert!(body.contains("[\"last\"]"));
+    assert!(body.contains("redacted"));
+    assert!(!body.contains("secret"));
+    assert_eq!(body.matches("data:").count(), 3);
+}
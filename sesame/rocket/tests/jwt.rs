@@ -0,0 +1,152 @@
+use jsonwebtoken::{encode, EncodingKey, Header};
+use serde_json::json;
+
+use sesame::pcon::PCon;
+use sesame::policy::NoPolicy;
+use sesame::SesameType;
+
+use sesame_rocket::jwt::{Algorithm, PConJwtClaims, PConJwtVerifier};
+use sesame_rocket::rocket::{
+    PConData, PConRequest, PConResponseOutcome, RequestPConJson, SesameRocket,
+};
+use sesame_rocket::test_route;
+use sesame_rocket::testing::SesameClient;
+
+use rocket::http::{Header as HttpHeader, Status};
+
+const SECRET: &[u8] = b"secret-key-for-sesame-tests-01234567";
+const JWKS: &str = r#"{
+  "keys": [
+    {
+      "kty": "oct",
+      "kid": "test-key",
+      "alg": "HS256",
+      "k": "c2VjcmV0LWtleS1mb3Itc2VzYW1lLXRlc3RzLTAxMjM0NTY3"
+    }
+  ]
+}"#;
+
+#[derive(SesameType, RequestPConJson)]
+pub struct Claims {
+    pub sub: PCon<String, NoPolicy>,
+    pub email: Option<PCon<String, NoPolicy>>,
+}
+
+fn token(kid: &str, claims: serde_json::Value) -> String {
+    let mut header = Header::new(Algorithm::HS256);
+    header.kid = Some(String::from(kid));
+    encode(&header, &claims, &EncodingKey::from_secret(SECRET)).unwrap()
+}
+
+pub async fn whoami<'a, 'r>(
+    request: PConRequest<'a, 'r>,
+    _data: PConData<'a>,
+) -> PConResponseOutcome<'a> {
+    let claims = match request.guard::<PConJwtClaims<Claims>>().await {
+        rocket::outcome::Outcome::Success(claims) => claims.into_inner(),
+        _ => return PConResponseOutcome::Failure(Status::Unauthorized),
+    };
+    let email = match claims.email {
+        None => String::from("none"),
+        Some(email) => email.discard_box(),
+    };
+    let response = format!("{} {}", claims.sub.discard_box(), email);
+    PConResponseOutcome::from(request, response)
+}
+
+fn verifier() -> PConJwtVerifier {
+    PConJwtVerifier::from_jwks_json(JWKS)
+        .unwrap()
+        .algorithms(&[Algorithm::HS256])
+        .issuer("https://sso.example.com/realms/sesame")
+}
+
+fn client() -> SesameClient {
+    client_with(verifier().audience("sesame"))
+}
+
+fn client_with(verifier: PConJwtVerifier) -> SesameClient {
+    let rocket = SesameRocket::build()
+        .manage(verifier)
+        .mount("/", vec![test_route!(Get, "/whoami", whoami)]);
+    SesameClient::tracked(rocket).expect("valid `Rocket`")
+}
+
+fn claims(iss: &str, exp: u64) -> serde_json::Value {
+    json!({
+        "sub": "kinan",
+        "email": "kinan@brown.edu",
+        "iss": iss,
+        "aud": "sesame",
+        "exp": exp,
+    })
+}
+
+#[test]
+fn test_valid_token() {
+    let client = client();
+    let token = token(
+        "test-key",
+        claims("https://sso.example.com/realms/sesame", 9999999999),
+    );
+    let response = client
+        .get("/whoami")
+        .header(HttpHeader::new(
+            "Authorization",
+            format!("Bearer {}", token),
+        ))
+        .dispatch();
+    assert_eq!(response.status(), Status::Ok);
+    assert_eq!(response.into_string().unwrap(), "kinan kinan@brown.edu");
+}
+
+#[test]
+fn test_invalid_tokens() {
+    let client = client();
+    let response = client.get("/whoami").dispatch();
+    assert_eq!(response.status(), Status::Unauthorized);
+
+    let tokens = vec![
+        // Expired.
+        token(
+            "test-key",
+            claims("https://sso.example.com/realms/sesame", 1000),
+        ),
+        // Wrong issuer.
+        token("test-key", claims("https://evil.example.com", 9999999999)),
+        // Unknown key.
+        token(
+            "other-key",
+            claims("https://sso.example.com/realms/sesame", 9999999999),
+        ),
+    ];
+    for token in tokens {
+        let response = client
+            .get("/whoami")
+            .header(HttpHeader::new(
+                "Authorization",
+                format!("Bearer {}", token),
+            ))
+            .dispatch();
+        assert_eq!(response.status(), Status::Unauthorized);
+    }
+}
+
+#[test]
+fn test_missing_audience() {
+    let token = token(
+        "test-key",
+        claims("https://sso.example.com/realms/sesame", 9999999999),
+    );
+    let header = HttpHeader::new("Authorization", format!("Bearer {}", token));
+
+    // Without an audience every token is rejected.
+    let client = client_with(verifier());
+    let response = client.get("/whoami").header(header.clone()).dispatch();
+    assert_eq!(response.status(), Status::InternalServerError);
+
+    // Unless the application opts out explicitly.
+    let client = client_with(verifier().insecure_skip_audience());
+    let response = client.get("/whoami").header(header).dispatch();
+    assert_eq!(response.status(), Status::Ok);
+}
@@ -0,0 +1,168 @@
+use sesame::context::{Context, UnprotectedContext};
+use sesame::pcon::PCon;
+use sesame::policy::{NoPolicy, Reason, SimplePolicy};
+use sesame::session::InMemorySessionStore;
+
+use sesame_rocket::rocket::{PConData, PConRequest, PConResponseOutcome, SesameRocket};
+use sesame_rocket::session::{PConSession, PConSessions};
+use sesame_rocket::test_route;
+use sesame_rocket::testing::SesameClient;
+
+use rocket::http::{Cookie, Status};
+
+// Allows storing in the session only.
+#[derive(Clone)]
+pub struct SessionOnlyPolicy {}
+impl SimplePolicy for SessionOnlyPolicy {
+    fn simple_name(&self) -> String {
+        String::from("SessionOnlyPolicy")
+    }
+    fn simple_check(&self, _context: &UnprotectedContext, reason: Reason<'_>) -> bool {
+        matches!(reason, Reason::Session("sid"))
+    }
+    fn simple_join_direct(&mut self, _other: &mut Self) {}
+}
+
+#[derive(Clone)]
+pub struct RejectPolicy {}
+impl SimplePolicy for RejectPolicy {
+    fn simple_name(&self) -> String {
+        String::from("RejectPolicy")
+    }
+    fn simple_check(&self, _context: &UnprotectedContext, _reason: Reason<'_>) -> bool {
+        false
+    }
+    fn simple_join_direct(&mut self, _other: &mut Self) {}
+}
+
+type UserSession<'a, 'r> = PConSession<'a, 'r, PCon<String, SessionOnlyPolicy>, NoPolicy>;
+
+pub async fn login<'a, 'r>(
+    request: PConRequest<'a, 'r>,
+    _data: PConData<'a>,
+) -> PConResponseOutcome<'a> {
+    // Policy check fails, nothing is stored.
+    let mut rejected: PConSession<PCon<String, RejectPolicy>, NoPolicy> =
+        request.guard().await.unwrap();
+    let data = PCon::new(String::from("kinan"), RejectPolicy {});
+    assert!(rejected.set(data, Context::test(())).is_err());
+
+    let mut session: UserSession = request.guard().await.unwrap();
+    let user = PCon::new(String::from("kinan"), SessionOnlyPolicy {});
+    session.set(user, Context::test(())).unwrap();
+    PConResponseOutcome::from(request, String::from("ok"))
+}
+
+pub async fn me<'a, 'r>(
+    request: PConRequest<'a, 'r>,
+    _data: PConData<'a>,
+) -> PConResponseOutcome<'a> {
+    let session: UserSession = request.guard().await.unwrap();
+    let result = match session.get().unwrap() {
+        None => String::from("anonymous"),
+        Some(user) => user.discard_box(),
+    };
+    PConResponseOutcome::from(request, result)
+}
+
+pub async fn rotate<'a, 'r>(
+    request: PConRequest<'a, 'r>,
+    _data: PConData<'a>,
+) -> PConResponseOutcome<'a> {
+    let mut session: UserSession = request.guard().await.unwrap();
+    session.rotate().unwrap();
+    PConResponseOutcome::from(request, String::from("ok"))
+}
+
+pub async fn logout<'a, 'r>(
+    request: PConRequest<'a, 'r>,
+    _data: PConData<'a>,
+) -> PConResponseOutcome<'a> {
+    let mut session: UserSession = request.guard().await.unwrap();
+    session.destroy().unwrap();
+    PConResponseOutcome::from(request, String::from("ok"))
+}
+
+fn build(ttl: chrono::Duration) -> SesameRocket<rocket::Build> {
+    let sessions = PConSessions::new(InMemorySessionStore::new())
+        .cookie_name("sid")
+        .ttl(ttl);
+    SesameRocket::build().manage(sessions).mount(
+        "/",
+        vec![
+            test_route!(Get, "/login", login),
+            test_route!(Get, "/me", me),
+            test_route!(Get, "/rotate", rotate),
+            test_route!(Get, "/logout", logout),
+        ],
+    )
+}
+
+fn client(ttl: chrono::Duration) -> SesameClient {
+    SesameClient::tracked(build(ttl)).expect("valid `Rocket`")
+}
+
+#[test]
+fn test_session() {
+    let client = client(chrono::Duration::hours(1));
+    assert_eq!(
+        client.get("/me").dispatch().into_string().unwrap(),
+        "anonymous"
+    );
+
+    let response = client.get("/login").dispatch();
+    assert_eq!(response.status(), Status::new(200));
+    let cookie = response.cookies().get("sid").unwrap();
+    assert_eq!(cookie.secure(), Some(true));
+    let id = cookie.value().to_string();
+    assert!(!id.contains("kinan"));
+    assert_eq!(client.get("/me").dispatch().into_string().unwrap(), "kinan");
+
+    // Rotation keeps the data under a new id.
+    let response = client.get("/rotate").dispatch();
+    let rotated = response.cookies().get("sid").unwrap().value().to_string();
+    assert_ne!(id, rotated);
+    assert_eq!(client.get("/me").dispatch().into_string().unwrap(), "kinan");
+
+    client.get("/logout").dispatch();
+    assert_eq!(
+        client.get("/me").dispatch().into_string().unwrap(),
+        "anonymous"
+    );
+}
+
+#[test]
+fn test_session_fixation() {
+    let client =
+        SesameClient::untracked(build(chrono::Duration::hours(1))).expect("valid `Rocket`");
+
+    // An id the server never issued is not adopted.
+    let response = client
+        .get("/login")
+        .cookie(Cookie::new("sid", "planted"))
+        .dispatch();
+    assert_eq!(response.status(), Status::new(200));
+    let id = response.cookies().get("sid").unwrap().value().to_string();
+    assert_ne!(id, "planted");
+
+    let me = |id: &str| {
+        client
+            .get("/me")
+            .cookie(Cookie::new("sid", String::from(id)))
+            .dispatch()
+            .into_string()
+            .unwrap()
+    };
+    assert_eq!(me("planted"), "anonymous");
+    assert_eq!(me(&id), "kinan");
+}
+
+#[test]
+fn test_session_expiry() {
+    let client = client(chrono::Duration::zero());
+    client.get("/login").dispatch();
+    assert_eq!(
+        client.get("/me").dispatch().into_string().unwrap(),
+        "anonymous"
+    );
+}
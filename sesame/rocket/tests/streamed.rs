@@ -0,0 +1,159 @@
+use futures::stream;
+
+use sesame::context::{Context, UnprotectedContext};
+use sesame::pcon::PCon;
+use sesame::policy::{Reason, SimplePolicy};
+
+use sesame_rocket::rocket::{
+    DeniedValues, OutputPConValue, PConCsvStream, PConData, PConNdjsonStream, PConRequest,
+    PConResponseOutcome, ResponsePConCsv, SesameRocket,
+};
+use sesame_rocket::test_route;
+use sesame_rocket::testing::SesameClient;
+
+use rocket::http::{ContentType, Status};
+
+#[derive(Clone)]
+pub struct HardcodedPolicy(pub bool);
+impl SimplePolicy for HardcodedPolicy {
+    fn simple_name(&self) -> String {
+        String::from("HardcodedPolicy")
+    }
+    fn simple_check(&self, _context: &UnprotectedContext, reason: Reason<'_>) -> bool {
+        self.0 && matches!(reason, Reason::Response)
+    }
+    fn simple_join_direct(&mut self, other: &mut Self) {
+        self.0 = self.0 && other.0;
+    }
+}
+
+pub struct Grade {
+    name: PCon<String, HardcodedPolicy>,
+    grade: PCon<u64, HardcodedPolicy>,
+}
+impl ResponsePConCsv for Grade {
+    fn csv_columns() -> Vec<String> {
+        vec![String::from("name"), String::from("grade")]
+    }
+    fn to_csv_cells(self) -> Vec<OutputPConValue> {
+        let mut cells = self.name.to_csv_cells();
+        cells.extend(self.grade.to_csv_cells());
+        cells
+    }
+}
+
+fn names() -> Vec<PCon<String, HardcodedPolicy>> {
+    vec![
+        PCon::new(String::from("first"), HardcodedPolicy(true)),
+        PCon::new(String::from("secret"), HardcodedPolicy(false)),
+        PCon::new(String::from("last"), HardcodedPolicy(true)),
+    ]
+}
+
+fn grades() -> Vec<Grade> {
+    vec![
+        Grade {
+            name: PCon::new(String::from("Kinan, D."), HardcodedPolicy(true)),
+            grade: PCon::new(90, HardcodedPolicy(false)),
+        },
+        Grade {
+            name: PCon::new(String::from("Corinn"), HardcodedPolicy(true)),
+            grade: PCon::new(95, HardcodedPolicy(true)),
+        },
+    ]
+}
+
+pub async fn failed<'a, 'r>(
+    request: PConRequest<'a, 'r>,
+    _data: PConData<'a>,
+) -> PConResponseOutcome<'a> {
+    let stream = PConNdjsonStream::new(stream::iter(names()), Context::test(()));
+    PConResponseOutcome::from(request, stream)
+}
+
+pub async fn redacted<'a, 'r>(
+    request: PConRequest<'a, 'r>,
+    _data: PConData<'a>,
+) -> PConResponseOutcome<'a> {
+    let rows = names().into_iter().map(|name| vec![name]);
+    let stream = PConNdjsonStream::new(stream::iter(rows), Context::test(()))
+        .denied(DeniedValues::Redact(String::from("[redacted]")));
+    PConResponseOutcome::from(request, stream)
+}
+
+pub async fn csv<'a, 'r>(
+    request: PConRequest<'a, 'r>,
+    _data: PConData<'a>,
+) -> PConResponseOutcome<'a> {
+    let stream = PConCsvStream::new(stream::iter(grades()), Context::test(()))
+        .denied(DeniedValues::Redact(String::from("N/A")));
+    PConResponseOutcome::from(request, stream)
+}
+
+pub async fn csv_failed<'a, 'r>(
+    request: PConRequest<'a, 'r>,
+    _data: PConData<'a>,
+) -> PConResponseOutcome<'a> {
+    let stream = PConCsvStream::new(stream::iter(grades()), Context::test(()));
+    PConResponseOutcome::from(request, stream)
+}
+
+fn client() -> SesameClient {
+    let rocket = SesameRocket::build().mount(
+        "/",
+        vec![
+            test_route!(Get, "/failed", failed),
+            test_route!(Get, "/redacted", redacted),
+            test_route!(Get, "/csv", csv),
+            test_route!(Get, "/csv_failed", csv_failed),
+        ],
+    );
+    SesameClient::tracked(rocket).expect("valid `Rocket`")
+}
+
+#[test]
+fn test_ndjson_stream_fail() {
+    let client = client();
+    let response = client.get("/failed").dispatch();
+    assert_eq!(response.status(), Status::new(200));
+    assert_eq!(
+        response.content_type(),
+        Some(ContentType::new("application", "x-ndjson"))
+    );
+    // The stream ends with an error marker at the first row that fails its check.
+    assert_eq!(
+        response.into_string().unwrap(),
+        "\"first\"\n{\"error\":\"policy check failed\"}\n"
+    );
+}
+
+#[test]
+fn test_ndjson_stream_redact() {
+    let client = client();
+    let response = client.get("/redacted").dispatch();
+    assert_eq!(
+        response.into_string().unwrap(),
+        "[\"first\"]\n[\"[redacted]\"]\n[\"last\"]\n"
+    );
+}
+
+#[test]
+fn test_csv_stream() {
+    let client = client();
+    let response = client.get("/csv").dispatch();
+    assert_eq!(response.status(), Status::new(200));
+    assert_eq!(
+        response.into_string().unwrap(),
+        "name,grade\r\n\"Kinan, D.\",N/A\r\nCorinn,95\r\n"
+    );
+}
+
+#[test]
+fn test_csv_stream_fail() {
+    let client = client();
+    let response = client.get("/csv_failed").dispatch();
+    assert_eq!(
+        response.into_string().unwrap(),
+        "name,grade\r\n#ERROR: policy check failed\r\n"
+    );
+}
@@ -0,0 +1,95 @@
+use sesame::context::{Context, UnprotectedContext};
+use sesame::policy::{Reason, SimplePolicy};
+use sesame::testing::TestPolicy;
+
+use sesame_rocket::policy::FrontendPolicy;
+use sesame_rocket::rocket::{
+    FromPConData, PConData, PConForm, PConPersistError, PConRequest, PConResponseOutcome,
+    PConTempFile, SesameRocket,
+};
+use sesame_rocket::test_route;
+use sesame_rocket::testing::SesameClient;
+
+use rocket::http::{ContentType, Cookie, Status};
+use rocket::Request;
+
+// Uploads may only be written to files named allowed.txt.
+pub struct UploadPolicy {}
+impl SimplePolicy for UploadPolicy {
+    fn simple_name(&self) -> String {
+        String::from("UploadPolicy")
+    }
+    fn simple_check(&self, _context: &UnprotectedContext, reason: Reason<'_>) -> bool {
+        match reason {
+            Reason::FileWrite(path) => path.ends_with("allowed.txt"),
+            _ => false,
+        }
+    }
+    fn simple_join_direct(&mut self, _other: &mut Self) {}
+}
+impl FrontendPolicy for UploadPolicy {
+    fn from_request(_request: &'_ Request<'_>) -> Self {
+        UploadPolicy {}
+    }
+    fn from_cookie<'a, 'r>(
+        _name: &str,
+        _cookie: &'a Cookie<'static>,
+        _request: &'a Request<'r>,
+    ) -> Self {
+        UploadPolicy {}
+    }
+}
+
+pub async fn upload<'a, 'r>(
+    request: PConRequest<'a, 'r>,
+    data: PConData<'a>,
+) -> PConResponseOutcome<'a> {
+    type MyForm = PConForm<PConTempFile<TestPolicy<UploadPolicy>>>;
+    let mut file = MyForm::from_data(request, data).await.unwrap().into_inner();
+
+    // Everything about the file is protected.
+    let name = file.file_name().unwrap();
+    assert_eq!(name.as_ref().discard_box(), "notes");
+    let content_type = file.content_type().as_ref().discard_box();
+    assert_eq!(*content_type, ContentType::Plain);
+
+    // Persisting checks the policy first.
+    let dir = std::env::temp_dir();
+    let denied = dir.join("sesame_upload_denied.txt");
+    let result = file.persist_to(&denied, Context::test(())).await;
+    assert!(matches!(result, Err(PConPersistError::SesameError(_))));
+    assert!(!denied.exists());
+
+    let allowed = dir.join("sesame_upload_allowed.txt");
+    file.persist_to(&allowed, Context::test(())).await.unwrap();
+    let written = std::fs::read_to_string(&allowed).unwrap();
+
+    // The file was moved, the content is read from its new location.
+    let content = file.into_bytes().await.unwrap().discard_box();
+    assert_eq!(content, b"hello world");
+    assert!(allowed.exists());
+    std::fs::remove_file(&allowed).unwrap();
+
+    PConResponseOutcome::from(request, written)
+}
+
+#[test]
+fn test_upload() {
+    let rocket = SesameRocket::build().mount("/", vec![test_route!(Post, "/", upload)]);
+    let client = SesameClient::tracked(rocket).expect("valid `Rocket`");
+
+    let body = "--BOUNDARY\r\n\
+        Content-Disposition: form-data; name=\"file\"; filename=\"notes.txt\"\r\n\
+        Content-Type: text/plain\r\n\
+        \r\n\
+        hello world\r\n\
+        --BOUNDARY--\r\n";
+    let response = client
+        .post("/")
+        .header(ContentType::new("multipart", "form-data").with_params(("boundary", "BOUNDARY")))
+        .body(body)
+        .dispatch();
+
+    assert_eq!(response.status(), Status::new(200));
+    assert_eq!(response.into_string().unwrap(), "hello world");
+}
@@ -0,0 +1,119 @@
+use std::net::TcpListener;
+use std::time::Duration;
+
+use futures::future::FutureExt;
+use futures::{SinkExt, StreamExt};
+use tokio_tungstenite::tungstenite::Message;
+
+use sesame::context::{Context, UnprotectedContext};
+use sesame::pcon::PCon;
+use sesame::policy::{NoPolicy, Reason, SimplePolicy};
+
+use sesame_rocket::rocket::{
+    PConData, PConRequest, PConResponseOutcome, PConWebSocket, PConWebSocketError, SesameRocket,
+};
+use sesame_rocket::test_route;
+
+use rocket::tokio::io::{AsyncReadExt, AsyncWriteExt};
+use rocket::tokio::net::TcpStream;
+
+// Only allows streaming to the chat path.
+#[derive(Clone)]
+pub struct ChatPolicy {}
+impl SimplePolicy for ChatPolicy {
+    fn simple_name(&self) -> String {
+        String::from("ChatPolicy")
+    }
+    fn simple_check(&self, _context: &UnprotectedContext, reason: Reason<'_>) -> bool {
+        matches!(reason, Reason::Stream("/chat"))
+    }
+    fn simple_join_direct(&mut self, _other: &mut Self) {}
+}
+
+#[derive(Clone)]
+pub struct RejectPolicy {}
+impl SimplePolicy for RejectPolicy {
+    fn simple_name(&self) -> String {
+        String::from("RejectPolicy")
+    }
+    fn simple_check(&self, _context: &UnprotectedContext, _reason: Reason<'_>) -> bool {
+        false
+    }
+    fn simple_join_direct(&mut self, _other: &mut Self) {}
+}
+
+// Echoes every message, followed by a JSON message, and checks denied messages are not sent.
+pub async fn chat<'a, 'r>(
+    request: PConRequest<'a, 'r>,
+    _data: PConData<'a>,
+) -> PConResponseOutcome<'a> {
+    let socket = PConWebSocket::<NoPolicy, _>::new(request, Context::test(()));
+    let channel = socket.map(|socket| {
+        socket.channel(|mut stream| {
+            async move {
+                while let Some(message) = stream.recv().await {
+                    // Inbound messages are protected.
+                    stream.send_text(message?).await?;
+
+                    // Outbound messages are checked.
+                    let json = vec![PCon::new(String::from("a"), ChatPolicy {})];
+                    stream.send(json).await?;
+
+                    let denied = PCon::new(String::from("secret"), RejectPolicy {});
+                    let result = stream.send_text(denied).await;
+                    assert!(matches!(result, Err(PConWebSocketError::SesameError(_))));
+                    let denied = vec![PCon::new(String::from("secret"), RejectPolicy {})];
+                    assert!(stream.send(denied).await.is_err());
+                }
+                Ok(())
+            }
+            .boxed()
+        })
+    });
+    PConResponseOutcome::from(request, channel)
+}
+
+// Launches a server on a free port, since local clients cannot upgrade connections.
+fn launch() -> u16 {
+    let port = TcpListener::bind("127.0.0.1:0")
+        .unwrap()
+        .local_addr()
+        .unwrap()
+        .port();
+    let figment = rocket::Config::figment()
+        .merge(("port", port))
+        .merge(("log_level", "off"));
+    let rocket = SesameRocket::custom(figment).mount("/", vec![test_route!(Get, "/chat", chat)]);
+    rocket::tokio::spawn(rocket.launch());
+    port
+}
+
+#[rocket::async_test]
+async fn test_websocket() {
+    let port = launch();
+    let url = format!("ws://127.0.0.1:{}/chat", port);
+    let mut socket = loop {
+        match tokio_tungstenite::connect_async(url.as_str()).await {
+            Ok((socket, _)) => break socket,
+            Err(_) => rocket::tokio::time::sleep(Duration::from_millis(50)).await,
+        }
+    };
+
+    socket
+        .send(Message::Text(String::from("hello")))
+        .await
+        .unwrap();
+    let message = socket.next().await.unwrap().unwrap();
+    assert_eq!(message, Message::Text(String::from("hello")));
+    let message = socket.next().await.unwrap().unwrap();
+    assert_eq!(message, Message::Text(String::from("[\"a\"]")));
+    socket.close(None).await.unwrap();
+
+    // Requests that are not upgrades are rejected.
+    let mut stream = TcpStream::connect(("127.0.0.1", port)).await.unwrap();
+    let request = "GET /chat HTTP/1.1\r\nHost: localhost\r\nConnection: close\r\n\r\n";
+    stream.write_all(request.as_bytes()).await.unwrap();
+    let mut response = String::new();
+    stream.read_to_string(&mut response).await.unwrap();
+    assert!(response.starts_with("HTTP/1.1 400"));
+}
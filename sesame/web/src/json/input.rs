@@ -0,0 +1,141 @@
+use serde::de::DeserializeOwned;
+use serde_json::{Map, Value};
+use std::collections::HashMap;
+
+// Implemented by the PConRequest of every frontend (e.g. sesame_rocket's), turns values into types
+// implementing that frontend's RequestPConJson.
+pub trait JsonRequest<T> {
+    fn from_json(self, value: InputPConValue) -> Result<T, &'static str>;
+}
+
+// Wrapper around serde_json::Value created from requests and consumed by our types.
+pub struct InputPConValue {
+    value: Value,
+    // Values from forms (and queries) are always strings, e.g. "10", and are parsed when they are
+    // turned into PCons.
+    form: bool,
+}
+impl InputPConValue {
+    pub fn new(value: Value) -> Self {
+        Self { value, form: false }
+    }
+    // Nests fields with dotted names (e.g. dog.name=Max) into objects, repeated fields become
+    // arrays.
+    pub fn from_form(fields: Vec<(String, String)>) -> Result<Self, &'static str> {
+        let mut root = Map::new();
+        for (name, value) in fields {
+            let mut keys: Vec<&str> = name.split('.').collect();
+            let last = keys.pop().unwrap();
+            let mut map = &mut root;
+            for key in keys {
+                let entry = map.entry(key).or_insert_with(|| Value::Object(Map::new()));
+                map = match entry {
+                    Value::Object(map) => map,
+                    _ => return Err("Bad form"),
+                };
+            }
+            match map.get_mut(last) {
+                None => {
+                    map.insert(String::from(last), Value::String(value));
+                }
+                Some(Value::Array(vec)) => vec.push(Value::String(value)),
+                Some(entry @ Value::String(_)) => {
+                    let old = entry.take();
+                    *entry = Value::Array(vec![old, Value::String(value)]);
+                }
+                Some(_) => return Err("Bad form"),
+            }
+        }
+        Ok(Self {
+            value: Value::Object(root),
+            form: true,
+        })
+    }
+    pub fn get(&mut self, key: &str) -> Result<InputPConValue, &'static str> {
+        let form = self.form;
+        match self.value.as_object_mut() {
+            None => Err("Bad JSON"),
+            Some(map) => match map.remove(key) {
+                None => Ok(InputPConValue {
+                    value: Value::Null,
+                    form,
+                }),
+                Some(value) => Ok(InputPConValue { value, form }),
+            },
+        }
+    }
+    pub fn is_null(&self) -> bool {
+        self.value.is_null()
+    }
+    // The request is the PConRequest of the frontend, e.g. sesame_rocket's.
+    pub fn into_json<T, R: JsonRequest<T>>(self, request: R) -> Result<T, &'static str> {
+        request.from_json(self)
+    }
+
+    // Used by the frontends to implement their RequestPConJson for predefined types.
+    pub fn deserialize<T: DeserializeOwned>(self) -> Result<T, &'static str> {
+        let result = match self.value {
+            // Form values are strings, parse them if T is not a string, e.g. "10" for u8.
+            Value::String(string) if self.form => {
+                serde_json::from_value(Value::String(string.clone()))
+                    .or_else(|_| serde_json::from_str(&string))
+            }
+            value => serde_json::from_value(value),
+        };
+        result.map_err(|_| "Bad JSON")
+    }
+    pub fn into_array(self) -> Result<Vec<InputPConValue>, &'static str> {
+        let form = self.form;
+        let vec = match self.value {
+            Value::Array(vec) => vec,
+            // A field that appears once in a form is not an array.
+            Value::Null if form => Vec::new(),
+            value if form => vec![value],
+            _ => return Err("Bad JSON"),
+        };
+        Ok(vec
+            .into_iter()
+            .map(|value| InputPConValue { value, form })
+            .collect())
+    }
+    pub fn into_object(self) -> Result<HashMap<String, InputPConValue>, &'static str> {
+        let form = self.form;
+        match self.value {
+            Value::Object(map) => Ok(map
+                .into_iter()
+                .map(|(k, value)| (k, InputPConValue { value, form }))
+                .collect()),
+            _ => Err("Bad JSON"),
+        }
+    }
+}
+
+// Unit tests.
+#[cfg(test)]
+mod tests {
+    use crate::json::InputPConValue;
+    use serde_json::json;
+
+    #[test]
+    fn test_from_form() {
+        let fields = vec![
+            (String::from("owner"), String::from("Kinan")),
+            (String::from("dog.name"), String::from("Max")),
+            (String::from("dog.tags"), String::from("good")),
+            (String::from("dog.tags"), String::from("boy")),
+        ];
+        let mut value = InputPConValue::from_form(fields).unwrap();
+        assert!(value.form);
+        assert_eq!(
+            value.value,
+            json!({"owner": "Kinan", "dog": {"name": "Max", "tags": ["good", "boy"]}})
+        );
+        assert!(value.get("age").unwrap().is_null());
+
+        let fields = vec![
+            (String::from("dog"), String::from("Max")),
+            (String::from("dog.name"), String::from("Max")),
+        ];
+        assert!(InputPConValue::from_form(fields).is_err());
+    }
+}
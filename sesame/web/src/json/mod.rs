@@ -0,0 +1,7 @@
+mod input;
+mod output;
+mod traits;
+
+pub use input::*;
+pub use output::*;
+pub use traits::*;
@@ -0,0 +1,85 @@
+use serde_json::{Map, Value};
+use std::collections::HashMap;
+
+use sesame::error::SesameResult;
+use sesame::extensions::{ExtensionContext, SesameExtension};
+use sesame::pcon::PCon;
+use sesame::policy::{AnyPolicy, Reason};
+
+// Check the policy then return value as JSON if successful.
+struct JsonResponsePolicyCheck {}
+impl SesameExtension<Box<OutputPConValue>, AnyPolicy, Box<OutputPConValue>>
+    for JsonResponsePolicyCheck
+{
+    fn apply(&mut self, data: Box<OutputPConValue>, _policy: AnyPolicy) -> Box<OutputPConValue> {
+        data
+    }
+}
+
+// Wrapper around serde_json::Value generated by our types and consumed during responses.
+pub enum OutputPConValue {
+    Value(Value),
+    PCon(PCon<Box<OutputPConValue>, AnyPolicy>),
+    Array(Vec<OutputPConValue>),
+    Object(HashMap<String, OutputPConValue>),
+}
+impl OutputPConValue {
+    // Checks the policies of all PCons inside with the given reason.
+    // Used by the frontends to respond (e.g. JsonResponse), applications return the value instead.
+    pub fn transform_with(
+        self,
+        context: &ExtensionContext,
+        reason: &Reason<'_>,
+    ) -> SesameResult<Value> {
+        match self {
+            OutputPConValue::PCon(pcon) => {
+                let mut ext = JsonResponsePolicyCheck {};
+                let value = pcon.checked_extension(&mut ext, context, reason.clone())?;
+                value.transform_with(context, reason)
+            }
+            OutputPConValue::Value(value) => Ok(value),
+            OutputPConValue::Array(vec) => {
+                let mut v = Vec::with_capacity(vec.len());
+                for val in vec {
+                    v.push(val.transform_with(context, reason)?);
+                }
+                Ok(Value::Array(v))
+            }
+            OutputPConValue::Object(map) => {
+                let mut m = Map::with_capacity(map.len());
+                for (key, val) in map {
+                    m.insert(key, val.transform_with(context, reason)?);
+                }
+                Ok(Value::Object(m))
+            }
+        }
+    }
+    // Like transform_with, but PCons that fail their check are replaced by redacted.
+    pub fn transform_redacted(
+        self,
+        context: &ExtensionContext,
+        reason: &Reason<'_>,
+        redacted: &Value,
+    ) -> Value {
+        match self {
+            OutputPConValue::PCon(pcon) => {
+                let mut ext = JsonResponsePolicyCheck {};
+                match pcon.checked_extension(&mut ext, context, reason.clone()) {
+                    Ok(value) => value.transform_redacted(context, reason, redacted),
+                    Err(_) => redacted.clone(),
+                }
+            }
+            OutputPConValue::Value(value) => value,
+            OutputPConValue::Array(vec) => Value::Array(
+                vec.into_iter()
+                    .map(|val| val.transform_redacted(context, reason, redacted))
+                    .collect(),
+            ),
+            OutputPConValue::Object(map) => Value::Object(
+                map.into_iter()
+                    .map(|(key, val)| (key, val.transform_redacted(context, reason, redacted)))
+                    .collect(),
+            ),
+        }
+    }
+}
@@ -0,0 +1,73 @@
+use chrono::{NaiveDate, NaiveDateTime, NaiveTime};
+use serde_json::Value;
+use std::collections::HashMap;
+
+use sesame::extensions::{SesameExtension, UncheckedSesameExtension};
+use sesame::pcon::PCon;
+use sesame::policy::{AnyPolicy, AnyPolicyable};
+
+use crate::json::OutputPConValue;
+
+// Trait for transformation from structs to JSON data.
+// The inverse, RequestPConJson, is defined by each frontend, since it constructs the policies of
+// the PCons it creates from the frontend's request.
+pub trait ResponsePConJson {
+    fn to_json(self) -> OutputPConValue;
+}
+
+// Now, we implement response trait.
+// Anything that is json serializable can be made to be a response.
+macro_rules! impl_base_types {
+    ($T: ty) => {
+        impl ResponsePConJson for $T {
+            fn to_json(self) -> OutputPConValue {
+                OutputPConValue::Value(serde_json::to_value(self).unwrap())
+            }
+        }
+    };
+}
+impl_base_types!(String);
+impl_base_types!(bool);
+impl_base_types!(u64);
+impl_base_types!(i64);
+impl_base_types!(f64);
+impl_base_types!(i32);
+impl_base_types!(u32);
+impl_base_types!(NaiveDateTime);
+impl_base_types!(NaiveDate);
+impl_base_types!(NaiveTime);
+
+// PCon of anything that is ResponsePConJson is also ResponsePConJson.
+impl<T: ResponsePConJson, P: AnyPolicyable> ResponsePConJson for PCon<T, P> {
+    fn to_json(self) -> OutputPConValue {
+        struct Converter {}
+        impl UncheckedSesameExtension for Converter {}
+        impl<T: ResponsePConJson> SesameExtension<T, AnyPolicy, OutputPConValue> for Converter {
+            fn apply(&mut self, data: T, policy: AnyPolicy) -> OutputPConValue {
+                OutputPConValue::PCon(PCon::new(Box::new(data.to_json()), policy))
+            }
+        }
+        let pcon = self.into_any_policy_no_clone();
+        pcon.unchecked_extension(&mut Converter {})
+    }
+}
+
+// Response containers.
+impl<T: ResponsePConJson> ResponsePConJson for Option<T> {
+    fn to_json(self) -> OutputPConValue {
+        match self {
+            None => OutputPConValue::Value(Value::Null),
+            Some(v) => v.to_json(),
+        }
+    }
+}
+impl<T: ResponsePConJson> ResponsePConJson for Vec<T> {
+    fn to_json(self) -> OutputPConValue {
+        OutputPConValue::Array(self.into_iter().map(|v| v.to_json()).collect())
+    }
+}
+impl<T: ResponsePConJson> ResponsePConJson for HashMap<String, T> {
+    fn to_json(self) -> OutputPConValue {
+        OutputPConValue::Object(self.into_iter().map(|(k, v)| (k, v.to_json())).collect())
+    }
+}
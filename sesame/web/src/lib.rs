@@ -0,0 +1,6 @@
+// Framework independent parts of the web frontends (sesame_rocket, sesame_axum).
+// Applications use these through the frontend crates, which re-export them.
+
+// Export these
+pub mod json;
+pub mod redirect;
@@ -57,10 +57,25 @@ redirect_param_impl!(u8, u16, u32, u64, u128, usize,);
 redirect_param_impl!(i8, i16, i32, i64, i128, isize,);
 redirect_param_impl!(bool, char, f32, f64,);
 
-// Parameters.
+// Parameters, after their policies were checked for redirecting to url.
 pub struct RedirectParams {
     // Private: client code cannot see these.
-    pub(super) parameters: Vec<String>,
+    url: String,
+    parameters: Vec<String>,
+}
+impl RedirectParams {
+    // The url with each {} replaced by the corresponding parameter.
+    // Used by the frontends to redirect (e.g. PConRedirect).
+    pub fn into_url(self) -> String {
+        let mut parameters = self.parameters.into_iter();
+        let mut pieces = self.url.split("{}");
+        let mut result = String::from(pieces.next().unwrap_or_default());
+        for piece in pieces {
+            result.push_str(&parameters.next().unwrap_or_default());
+            result.push_str(piece);
+        }
+        result
+    }
 }
 
 pub trait IntoRedirectParams {
@@ -69,12 +84,9 @@ pub trait IntoRedirectParams {
 
 // Can make Params from empty tuple.
 impl IntoRedirectParams for () {
-    fn into<D: ContextData>(
-        self,
-        _url: &str,
-        _context: Context<D>,
-    ) -> SesameResult<RedirectParams> {
+    fn into<D: ContextData>(self, url: &str, _context: Context<D>) -> SesameResult<RedirectParams> {
         Ok(RedirectParams {
+            url: String::from(url),
             parameters: Vec::new(),
         })
     }
@@ -91,8 +103,9 @@ impl RedirectPolicyCheck {
     pub fn push(&mut self, v: String) {
         self.params.push(v);
     }
-    pub fn into_redirect_params(self) -> RedirectParams {
+    pub fn into_redirect_params(self, url: &str) -> RedirectParams {
         RedirectParams {
+            url: String::from(url),
             parameters: self.params,
         }
     }
@@ -100,7 +113,7 @@ impl RedirectPolicyCheck {
 impl<'a> SesameExtension<&'a dyn ToString, RefPolicy<'a, dyn Policy + 'a>, ()>
     for RedirectPolicyCheck
 {
-    fn apply(&mut self, data: &'a dyn ToString, _policy: RefPolicy<'a, dyn Policy + 'a>) -> () {
+    fn apply(&mut self, data: &'a dyn ToString, _policy: RefPolicy<'a, dyn Policy + 'a>) {
         self.params.push(data.to_string());
     }
 }
@@ -121,7 +134,7 @@ macro_rules! into_params_impl {
             },
         };)*
 
-        Ok(ext.into_redirect_params())
+        Ok(ext.into_redirect_params(url))
       }
     }
   );
@@ -231,3 +244,23 @@ into_params_impl!(
     [M, m, 'm],
     [N, n, 'n]
 );
+
+// Unit tests.
+#[cfg(test)]
+mod tests {
+    use crate::redirect::IntoRedirectParams;
+    use sesame::context::Context;
+    use sesame::pcon::PCon;
+    use sesame::policy::NoPolicy;
+
+    #[test]
+    fn test_into_url() {
+        let b1 = PCon::new(String::from("hello"), NoPolicy {});
+        let b2 = 10u32;
+        let params = IntoRedirectParams::into((&b1, &b2), "/test/{}/more/{}", Context::test(()));
+        assert_eq!(params.unwrap().into_url(), "/test/hello/more/10");
+
+        let params = IntoRedirectParams::into((), "/test", Context::test(()));
+        assert_eq!(params.unwrap().into_url(), "/test");
+    }
+}